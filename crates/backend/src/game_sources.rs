@@ -4,6 +4,8 @@ use unified_sim_model::model::{Day, SessionPhase, SessionType};
 use uuid::{uuid, Uuid};
 
 use crate::{
+    position_history::{PositionReference, PositionTrend},
+    race_events::RaceEventKind,
     value_store::{AnyValueProducer, ModelContext, ProducerId, ValueProducer, ValueStore},
     value_types::{AnyProducerRef, Boolean, Number, Text, ValueType},
 };
@@ -301,6 +303,13 @@ pub fn get_game_sources() -> Vec<&'static GameSource> {
                         context.entry.map(|e| *e.car_number as f32)
                     },
                 ),
+                GameSource::new_number(
+                    uuid!("e8903321-3226-4ed8-aaca-9d036e726c05"),
+                    "Entry id",
+                    |_: &ValueStore, context: ModelContext<'_>| {
+                        context.entry.map(|e| e.id.0 as f32)
+                    },
+                ),
                 GameSource::new_number(
                     uuid!("6330a6bb-51d1-4af7-9bd0-efeb00b1ff52"),
                     "Position",
@@ -460,6 +469,177 @@ pub fn get_game_sources() -> Vec<&'static GameSource> {
                             })
                     },
                 ),
+                GameSource::new_number(
+                    uuid!("dc304aba-b285-4f11-ad78-917f59c68f17"),
+                    "Start position",
+                    |value_store: &ValueStore, context: ModelContext<'_>| {
+                        context.entry.and_then(|e| {
+                            value_store
+                                .position_history()
+                                .start_position(&e.id)
+                                .map(|p| p as f32)
+                        })
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("9e2c6e8a-5af7-45ab-b5c4-636e50b35cfd"),
+                    "Positions gained",
+                    |value_store: &ValueStore, context: ModelContext<'_>| {
+                        context.entry.and_then(|e| {
+                            value_store
+                                .position_history()
+                                .positions_gained(
+                                    &e.id,
+                                    *e.position as i32,
+                                    PositionReference::RaceStart,
+                                )
+                                .map(|p| p as f32)
+                        })
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("eefded65-d8ef-4a2f-85df-7e74de84453f"),
+                    "Position delta last lap",
+                    |value_store: &ValueStore, context: ModelContext<'_>| {
+                        context.entry.and_then(|e| {
+                            value_store
+                                .position_history()
+                                .positions_gained(
+                                    &e.id,
+                                    *e.position as i32,
+                                    PositionReference::LastLap,
+                                )
+                                .map(|p| p as f32)
+                        })
+                    },
+                ),
+                GameSource::new_text(
+                    uuid!("68048d87-d748-4ab9-a911-f07c53c255b8"),
+                    "Position trend",
+                    |value_store: &ValueStore, context: ModelContext<'_>| {
+                        context.entry.and_then(|e| {
+                            value_store
+                                .position_history()
+                                .trend(&e.id, *e.position as i32, PositionReference::LastLap)
+                                .map(|trend| match trend {
+                                    PositionTrend::Up => String::from("Up"),
+                                    PositionTrend::Down => String::from("Down"),
+                                    PositionTrend::Same => String::from("Same"),
+                                })
+                        })
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("0c33ea42-fd6d-4ade-bc3a-902bf30e94c9"),
+                    "Position trend nr",
+                    |value_store: &ValueStore, context: ModelContext<'_>| {
+                        context.entry.and_then(|e| {
+                            value_store
+                                .position_history()
+                                .trend(&e.id, *e.position as i32, PositionReference::LastLap)
+                                .map(|trend| match trend {
+                                    PositionTrend::Up => 1.0,
+                                    PositionTrend::Down => -1.0,
+                                    PositionTrend::Same => 0.0,
+                                })
+                        })
+                    },
+                ),
+                GameSource::new_text(
+                    uuid!("f954ba9a-a3b6-4631-898d-f59d8b5c5bff"),
+                    "Last race event",
+                    |value_store: &ValueStore, _: ModelContext<'_>| {
+                        value_store
+                            .race_event_log()
+                            .last()
+                            .map(|last| last.event.kind().name().to_string())
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("c1c12765-4a44-42be-a912-1c846a30c371"),
+                    "Last race event age sec",
+                    |value_store: &ValueStore, _: ModelContext<'_>| {
+                        value_store
+                            .race_event_log()
+                            .last()
+                            .map(|last| last.age().as_secs_f32())
+                    },
+                ),
+                GameSource::new_bool(
+                    uuid!("6a205c41-57e7-422d-ad97-ba0599debabd"),
+                    "Is in last race event",
+                    |value_store: &ValueStore, context: ModelContext<'_>| {
+                        let entry = context.entry?;
+                        Some(
+                            value_store
+                                .race_event_log()
+                                .last()
+                                .is_some_and(|last| last.event.entries().contains(&entry.id)),
+                        )
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("47a49899-b097-414b-aac5-296935c68f94"),
+                    "Last fastest lap entry id",
+                    |value_store: &ValueStore, _: ModelContext<'_>| {
+                        value_store
+                            .race_event_log()
+                            .last_of_kind(RaceEventKind::FastestLap)
+                            .and_then(|last| last.event.entries().first().map(|id| id.0 as f32))
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("1d4ab786-6c41-43a0-ace9-6ab3b809bef3"),
+                    "Last race event entry id",
+                    |value_store: &ValueStore, _: ModelContext<'_>| {
+                        value_store
+                            .race_event_log()
+                            .last()
+                            .and_then(|last| last.event.entries().first().map(|id| id.0 as f32))
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("a933caac-5c1e-497b-8c49-3a57812d8b93"),
+                    "Repeat index",
+                    |_: &ValueStore, context: ModelContext<'_>| {
+                        context.repeat.map(|repeat| repeat.index as f32)
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("5896b03a-2b95-4c9e-9b02-947a36a38f2e"),
+                    "Repeat count",
+                    |_: &ValueStore, context: ModelContext<'_>| {
+                        context.repeat.map(|repeat| repeat.count as f32)
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("4abc5d4d-e198-42bf-936f-6f77b94f0e8b"),
+                    "Repeated lap time",
+                    |_: &ValueStore, context: ModelContext<'_>| {
+                        context.lap.map(|lap| lap.time.ms as f32 / 1000.0)
+                    },
+                ),
+                GameSource::new_text(
+                    uuid!("7590e33a-7300-41d5-8620-a085509720f1"),
+                    "Repeated lap time text",
+                    |_: &ValueStore, context: ModelContext<'_>| {
+                        context.lap.map(|lap| lap.time.format())
+                    },
+                ),
+                GameSource::new_number(
+                    uuid!("0f6f4c43-6a0e-4bfe-8d3b-2f1f6a3c9e57"),
+                    "Repeated sector time",
+                    |_: &ValueStore, context: ModelContext<'_>| {
+                        context.sector.map(|sector| sector.ms as f32 / 1000.0)
+                    },
+                ),
+                GameSource::new_text(
+                    uuid!("c2b8e0a1-51d7-4f39-9a6e-7d04b8e5f312"),
+                    "Repeated sector time text",
+                    |_: &ValueStore, context: ModelContext<'_>| {
+                        context.sector.map(|sector| sector.format())
+                    },
+                ),
                 GameSource::new_number(
                     uuid!("4d519d42-52e9-435c-b614-8d70b42ed3b0"),
                     "ACC: Cup category",
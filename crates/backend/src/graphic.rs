@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{cmp::Ordering, collections::HashMap, time::Instant};
 
 use bevy::{
     app::{Plugin, Update},
@@ -9,24 +9,33 @@ use bevy::{
         system::{Commands, Local, Query, Res, ResMut, Resource},
     },
     hierarchy::DespawnRecursiveExt,
-    math::vec3,
+    math::{vec2, vec3, Vec2, Vec3},
     utils::hashbrown::HashSet,
 };
 
+use common::communication::CellStyle;
 use unified_sim_model::model::{Entry, Model};
 
 use crate::{
     savefile::Savefile,
     style::{
         graphic::{
-            graphic_items::{entry_context::EntrySelection, ComputedGraphicItem},
-            GraphicStateId,
+            graphic_items::{
+                cell::ComputedCell,
+                driver_table::{Easing, ScrollMode, SortOrder},
+                entry_context::EntrySelection,
+                layout::{ComputedLayout, LayoutDirection},
+                repeater::{RepeaterLayout, RepeaterSource},
+                ComputedGraphicItem, GraphicItemId,
+            },
+            GraphicFolder, GraphicStateId,
         },
         StyleId, StyleItem,
     },
     style_batcher::{CellId, StyleBatcher},
     tree_iterator::TreeIterator,
-    value_store::ValueStore,
+    value_store::{ParameterScope, RepeatKey, ValueStore},
+    value_types::{AnyProducerRef, Boolean, Number, Text, ValueType},
     GameAdapterResource,
 };
 
@@ -42,12 +51,14 @@ mod style_resolver;
 pub struct GraphicPlugin;
 impl Plugin for GraphicPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.init_resource::<GraphicStates>().add_systems(
-            Update,
-            (spawn_or_delete_graphics, update_graphics)
-                .chain()
-                .in_set(StyleElementUpdate),
-        );
+        app.init_resource::<GraphicStates>()
+            .init_resource::<DrawnCells>()
+            .add_systems(
+                Update,
+                (spawn_or_delete_graphics, update_graphics)
+                    .chain()
+                    .in_set(StyleElementUpdate),
+            );
     }
 }
 
@@ -56,6 +67,62 @@ pub struct GraphicStates {
     pub states: HashMap<StyleId, GraphicStateId>,
 }
 
+/// The cells of each graphic that were drawn in the last update.
+///
+/// Cells that are drawn as part of a component instance are not included
+/// since they belong to the component and not the graphic.
+#[derive(Resource, Default)]
+pub struct DrawnCells {
+    pub graphics: HashMap<StyleId, Vec<DrawnCell>>,
+}
+
+/// The area a cell was drawn in.
+#[derive(Clone)]
+pub struct DrawnCell {
+    pub id: GraphicItemId,
+    /// Position of the top left corner in world space.
+    pub position: Vec3,
+    /// Rotation in radians counter clockwise.
+    pub rotation: f32,
+    pub scale: Vec2,
+    pub size: Vec2,
+    /// Rotation of the space the position of the cell is defined in.
+    pub parent_rotation: f32,
+    /// Scale of the space the position of the cell is defined in.
+    pub parent_scale: Vec2,
+}
+impl DrawnCell {
+    /// Transform a point from world space into the local space of the cell.
+    /// The local space has its origin in the top left corner with y pointing down.
+    pub fn to_local(&self, point: Vec2) -> Vec2 {
+        let local =
+            Vec2::from_angle(-self.rotation).rotate(point - self.position.truncate()) / self.scale;
+        vec2(local.x, -local.y)
+    }
+
+    /// Transform a point from the local space of the cell into world space.
+    pub fn to_world(&self, point: Vec2) -> Vec2 {
+        self.position.truncate()
+            + Vec2::from_angle(self.rotation).rotate(vec2(point.x, -point.y) * self.scale)
+    }
+
+    /// Transform a vector from world space into the space the position
+    /// of the cell is defined in.
+    pub fn to_parent_vector(&self, vector: Vec2) -> Vec2 {
+        let local = Vec2::from_angle(-self.parent_rotation).rotate(vector) / self.parent_scale;
+        vec2(local.x, -local.y)
+    }
+
+    /// Test if a point in world space is inside of the cell.
+    pub fn contains(&self, point: Vec2) -> bool {
+        let local = self.to_local(point);
+        local.x >= 0.0 && local.y >= 0.0 && local.x <= self.size.x && local.y <= self.size.y
+    }
+}
+
+/// The maximum number of component instances that can be nested in each other.
+const MAX_COMPONENT_DEPTH: usize = 16;
+
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
 pub struct StyleElementUpdate;
 
@@ -94,16 +161,17 @@ fn update_graphics(
     graphic_states: Res<GraphicStates>,
     savefile: Res<Savefile>,
     mut batcher: ResMut<StyleBatcher>,
+    mut drawn_cells: ResMut<DrawnCells>,
     mut graphic_item_data_storage: Local<GraphicItemDataStorage>,
     value_store: Res<ValueStore>,
     game_adapter: Res<GameAdapterResource>,
 ) {
-    let Some(game_adapter) = &game_adapter.adapter else {
+    drawn_cells.graphics.clear();
+
+    let Some(model) = game_adapter.model() else {
         graphic_item_data_storage.clear();
         return;
     };
-
-    let model = game_adapter.model.read_raw();
     let Some(session) = model.current_session() else {
         return;
     };
@@ -116,9 +184,11 @@ fn update_graphics(
                 update_graphic_item(
                     &computed_style.root,
                     &mut *batcher,
+                    drawn_cells.graphics.entry(graphic.id).or_default(),
                     &mut graphic_item_data_storage.make_context(0),
                     &resolver,
                     &*model,
+                    &savefile.style().components,
                 );
             }
         });
@@ -131,53 +201,79 @@ fn update_graphics(
 fn update_graphic_item(
     item: &ComputedGraphicItem,
     batcher: &mut StyleBatcher,
+    drawn_cells: &mut Vec<DrawnCell>,
     graphic_item_data_storage: &mut GraphicItemDataStorageContext<'_>,
     resolver: &StyleResolver,
     _model: &Model,
+    components: &GraphicFolder,
 ) {
     match item {
         ComputedGraphicItem::Root(root) => {
-            let new_resolver = resolver.clone().with_position(vec3(
-                resolver.property(&root.position.x).unwrap_or_default().0,
-                -resolver.property(&root.position.y).unwrap_or_default().0,
-                0.0,
-            ));
+            let new_resolver = resolver
+                .clone()
+                .with_item_transform(
+                    vec3(
+                        resolver.property(&root.position.x).unwrap_or_default().0,
+                        -resolver.property(&root.position.y).unwrap_or_default().0,
+                        0.0,
+                    ),
+                    &root.rotation,
+                    &root.scale,
+                    &root.pivot,
+                )
+                .with_opacity(resolver.property(&root.opacity).unwrap_or(Number(1.0)).0);
             root.items.iter().for_each(|item| {
                 update_graphic_item(
                     item,
                     batcher,
+                    drawn_cells,
                     graphic_item_data_storage,
                     &new_resolver,
                     _model,
+                    components,
                 )
             });
         }
         ComputedGraphicItem::Cell(cell) => {
-            let cell_id = graphic_item_data_storage.get_or_create(cell.id, || CellId::new());
-            batcher.add(&cell_id, resolver.cell(&cell));
+            draw_cell(
+                cell,
+                resolver.cell(&cell),
+                batcher,
+                drawn_cells,
+                graphic_item_data_storage,
+                resolver,
+            );
         }
         ComputedGraphicItem::ClipArea(clip_area) => {
             let cell_id = graphic_item_data_storage.get_or_create(clip_area.id, || CellId::new());
             let clip_area_style = resolver.clip_area(&clip_area);
             let new_resolver = resolver
                 .clone()
+                // The content is drawn without transform at full opacity.
+                // Transform and opacity are applied to the clip area as a whole.
                 .with_position(clip_area_style.pos)
-                .with_render_layer(clip_area_style.render_layer);
+                .with_render_layer(clip_area_style.render_layer)
+                .with_full_opacity();
             batcher.add_clip_area(&cell_id, clip_area_style);
             for item in clip_area.items.iter() {
                 update_graphic_item(
                     item,
                     batcher,
+                    drawn_cells,
                     graphic_item_data_storage,
                     &new_resolver,
                     _model,
+                    components,
                 );
             }
         }
         ComputedGraphicItem::DriverTable(driver_table) => {
-            let DriverTableData { scroll_position } =
-                graphic_item_data_storage.get_or_default(driver_table.id);
-
+            let resolver = &resolver.clone().with_opacity(
+                resolver
+                    .property(&driver_table.opacity)
+                    .unwrap_or(Number(1.0))
+                    .0,
+            );
             let position = vec3(
                 resolver
                     .property(&driver_table.position.x)
@@ -207,65 +303,801 @@ fn update_graphic_item(
             let mut entries: Vec<&Entry> = resolver.session().entries.values().collect();
             entries.sort_by_key(|e| *e.position);
 
-            // Update scroll position to make sure the focused entry is visible
-            if let Some(focused_entry_index) = entries.iter().position(|entry| entry.focused) {
-                let rows_to_skip = (focused_entry_index as f32 - 12.0)
-                    .min(entries.len() as f32 - 23.0)
-                    .max(0.0);
-                *scroll_position = *scroll_position - (*scroll_position - rows_to_skip) * 0.2;
+            if let Some(filter) = &driver_table.filter {
+                entries.retain(|entry| {
+                    resolver
+                        .clone()
+                        .with_entry(entry)
+                        .producer(filter)
+                        .is_some_and(|show| show.0)
+                });
+            }
+            if let Some(sort_key) = &driver_table.sort_key {
+                sort_entries(&mut entries, sort_key, driver_table.sort_order, resolver);
+            }
+
+            // A visible row count of 0 shows all rows.
+            let visible_rows = match resolver
+                .property(&driver_table.visible_rows)
+                .unwrap_or_default()
+                .0
+                .max(0.0) as usize
+            {
+                0 => entries.len(),
+                rows => rows,
+            };
+            let pinned_rows = (resolver
+                .property(&driver_table.pinned_rows)
+                .unwrap_or_default()
+                .0
+                .max(0.0) as usize)
+                .min(visible_rows)
+                .min(entries.len());
+            let window_rows = visible_rows - pinned_rows;
+            let scrollable_rows = entries.len() - pinned_rows;
+            let max_scroll = scrollable_rows.saturating_sub(window_rows) as f32;
+
+            let data: &mut DriverTableData =
+                graphic_item_data_storage.get_or_default(driver_table.id);
+            let now = Instant::now();
+
+            // Update the scroll target based on the scroll mode.
+            match driver_table.scroll_mode {
+                ScrollMode::FollowFocus => {
+                    // Keep the focused entry in the middle of the scrolling rows.
+                    if let Some(focused_index) = entries
+                        .iter()
+                        .position(|entry| entry.focused)
+                        .filter(|index| *index >= pinned_rows)
+                    {
+                        data.scroll_target = ((focused_index - pinned_rows) as f32
+                            - (window_rows as f32 / 2.0).ceil())
+                        .min(max_scroll)
+                        .max(0.0);
+                    }
+                }
+                ScrollMode::Fixed => data.scroll_target = 0.0,
+                ScrollMode::Paged => {
+                    let page_interval = resolver
+                        .property(&driver_table.page_interval)
+                        .unwrap_or_default()
+                        .0;
+                    let page_started = *data.page_started.get_or_insert(now);
+                    if page_interval > 0.0
+                        && now.duration_since(page_started).as_secs_f32() >= page_interval
+                    {
+                        data.page += 1;
+                        data.page_started = Some(now);
+                    }
+                    let page_count = if window_rows == 0 {
+                        1
+                    } else {
+                        scrollable_rows.div_ceil(window_rows).max(1)
+                    };
+                    data.page %= page_count;
+                    data.scroll_target = ((data.page * window_rows) as f32).min(max_scroll);
+                }
             }
-            let scroll_offset = row_offset * *scroll_position - position;
+
+            // Move the scroll position towards the target.
+            let scroll_smoothing = resolver
+                .property(&driver_table.scroll_smoothing)
+                .unwrap_or_default()
+                .0;
+            let seconds = data
+                .last_update
+                .map(|last_update| now.duration_since(last_update).as_secs_f32())
+                .unwrap_or_default();
+            let factor = if scroll_smoothing > 0.0 {
+                1.0 - (-seconds / scroll_smoothing).exp()
+            } else {
+                1.0
+            };
+            data.scroll_position += (data.scroll_target - data.scroll_position) * factor;
+            data.last_update = Some(now);
+            let scroll_position = data.scroll_position;
+
+            let reorder_duration = resolver
+                .property(&driver_table.reorder_duration)
+                .unwrap_or_default()
+                .0;
 
             // Each column for all entries.
             for (index, entry) in entries.iter().enumerate() {
+                // Animate the row towards its new index.
+                let mut row_storage = graphic_item_data_storage.make_context(entry.id);
+                let animation = row_storage.get_or_create(driver_table.id, || RowAnimation {
+                    from: index as f32,
+                    to: index as f32,
+                    started: now,
+                });
+                if animation.to != index as f32 {
+                    animation.from =
+                        animation.current(now, reorder_duration, driver_table.reorder_easing);
+                    animation.to = index as f32;
+                    animation.started = now;
+                }
+                let animated_index =
+                    animation.current(now, reorder_duration, driver_table.reorder_easing);
+                let is_moving = animated_index != animation.to;
+
+                let row = if index < pinned_rows {
+                    animated_index
+                } else {
+                    animated_index - scroll_position
+                };
+                // Rows that are scrolled out of the window are not shown.
+                if index >= pinned_rows
+                    && (row <= pinned_rows as f32 - 1.0 || row >= visible_rows as f32)
+                {
+                    continue;
+                }
+                // Rows that are partly scrolled out of the window fade out so they
+                // do not cover the pinned rows or spill past the end of the window.
+                let fade = if index < pinned_rows {
+                    1.0
+                } else {
+                    (row - (pinned_rows as f32 - 1.0))
+                        .min(visible_rows as f32 - row)
+                        .clamp(0.0, 1.0)
+                };
+
+                let columns = match &driver_table.moving_columns {
+                    Some(moving_columns) if is_moving => moving_columns,
+                    _ => &driver_table.columns,
+                };
+
                 let new_resolver = resolver
                     .clone()
-                    .with_position(*resolver.position() - scroll_offset + row_offset * index as f32)
+                    .with_offset(position + row_offset * row)
+                    .with_opacity(fade)
                     .with_entry(entry);
-                for column in driver_table.columns.iter() {
+                for column in columns.iter() {
                     update_graphic_item(
                         column,
                         batcher,
-                        &mut graphic_item_data_storage.make_context(entry.id),
+                        drawn_cells,
+                        &mut row_storage,
                         &new_resolver,
                         _model,
+                        components,
                     );
                 }
             }
         }
+        ComputedGraphicItem::Repeater(repeater) => {
+            let resolver = &resolver.clone().with_opacity(
+                resolver
+                    .property(&repeater.opacity)
+                    .unwrap_or(Number(1.0))
+                    .0,
+            );
+            let position = vec3(
+                resolver
+                    .property(&repeater.position.x)
+                    .unwrap_or_default()
+                    .0,
+                -resolver
+                    .property(&repeater.position.y)
+                    .unwrap_or_default()
+                    .0,
+                0.0,
+            );
+            let item_offset = vec2(
+                resolver
+                    .property(&repeater.item_offset.x)
+                    .unwrap_or_default()
+                    .0,
+                resolver
+                    .property(&repeater.item_offset.y)
+                    .unwrap_or_default()
+                    .0,
+            );
+            let base_resolver = resolver.clone().with_offset(position);
+
+            // Create a resolver for every element of the list.
+            let elements: Vec<(RepeatKey, StyleResolver)> = match &repeater.source {
+                RepeaterSource::Count(count) => {
+                    let count = resolver.property(count).unwrap_or_default().0.max(0.0) as usize;
+                    (0..count)
+                        .map(|index| (RepeatKey::Index(index), base_resolver.clone()))
+                        .collect()
+                }
+                RepeaterSource::Entries(filter) => {
+                    let mut entries: Vec<&Entry> = resolver.session().entries.values().collect();
+                    entries.sort_by_key(|e| *e.position);
+                    entries
+                        .into_iter()
+                        .map(|entry| {
+                            (
+                                RepeatKey::Entry(entry.id),
+                                base_resolver.clone().with_entry(entry),
+                            )
+                        })
+                        .filter(|(_, entry_resolver)| match filter {
+                            Some(filter) => {
+                                entry_resolver.producer(filter).is_some_and(|show| show.0)
+                            }
+                            None => true,
+                        })
+                        .collect()
+                }
+                RepeaterSource::Laps(count) => {
+                    let count = resolver.property(count).unwrap_or_default().0.max(0.0) as usize;
+                    let laps = resolver
+                        .entry()
+                        .map(|entry| entry.laps.as_slice())
+                        .unwrap_or_default();
+                    let first = laps.len().saturating_sub(count);
+                    laps[first..]
+                        .iter()
+                        .enumerate()
+                        .map(|(index, lap)| {
+                            (
+                                RepeatKey::Lap(first + index + 1),
+                                base_resolver.clone().with_lap(lap),
+                            )
+                        })
+                        .collect()
+                }
+                RepeaterSource::Sectors => {
+                    let lap = resolver
+                        .lap()
+                        .or_else(|| resolver.entry().map(|entry| &*entry.current_lap));
+                    lap.map(|lap| {
+                        lap.splits
+                            .iter()
+                            .enumerate()
+                            .map(|(index, sector)| {
+                                (
+                                    RepeatKey::Sector(index + 1),
+                                    base_resolver.clone().with_sector(sector),
+                                )
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+                }
+            };
+
+            let columns = match &repeater.layout {
+                RepeaterLayout::Grid { columns } => {
+                    resolver.property(columns).unwrap_or_default().0.max(0.0) as usize
+                }
+                RepeaterLayout::Flow { .. } => 0,
+            };
+            let mut flow_cursor = Vec2::ZERO;
+
+            let count = elements.len();
+            for (index, (key, item_resolver)) in elements.into_iter().enumerate() {
+                let item_resolver = item_resolver.with_repeat(index, count, key);
+                let offset = match &repeater.layout {
+                    RepeaterLayout::Grid { .. } => {
+                        let (column, row) = match columns {
+                            0 => (index, 0),
+                            columns => (index % columns, index / columns),
+                        };
+                        vec2(column as f32 * item_offset.x, row as f32 * item_offset.y)
+                    }
+                    RepeaterLayout::Flow {
+                        item_width,
+                        max_width,
+                    } => {
+                        let item_width = item_resolver.property(item_width).unwrap_or_default().0;
+                        let max_width = resolver.property(max_width).unwrap_or_default().0;
+                        // Start a new row if this item does not fit anymore.
+                        if flow_cursor.x > 0.0 && flow_cursor.x + item_width > max_width {
+                            flow_cursor = vec2(0.0, flow_cursor.y + item_offset.y);
+                        }
+                        let offset = flow_cursor;
+                        flow_cursor.x += item_width + item_offset.x;
+                        offset
+                    }
+                };
+
+                let item_resolver = item_resolver.with_offset(vec3(offset.x, -offset.y, 0.0));
+                for item in repeater.items.iter() {
+                    update_graphic_item(
+                        item,
+                        batcher,
+                        drawn_cells,
+                        &mut graphic_item_data_storage.make_context(key),
+                        &item_resolver,
+                        _model,
+                        components,
+                    );
+                }
+            }
+        }
+        ComputedGraphicItem::Layout(layout) => {
+            let resolver = &resolver
+                .clone()
+                .with_opacity(resolver.property(&layout.opacity).unwrap_or(Number(1.0)).0);
+            let position = vec3(
+                resolver.property(&layout.position.x).unwrap_or_default().0,
+                -resolver.property(&layout.position.y).unwrap_or_default().0,
+                0.0,
+            );
+            let spacing = resolver.property(&layout.spacing).unwrap_or_default().0;
+
+            let sized_items: Vec<SizedLayoutItem> = layout
+                .items
+                .iter()
+                .map(|item| size_layout_item(item, resolver))
+                .collect();
+            let content_size = layout_content_size(
+                layout.direction,
+                sized_items.iter().filter_map(|sized_item| sized_item.size),
+                spacing,
+            );
+            let size = if layout.auto_size {
+                content_size
+            } else {
+                layout_size(layout, resolver)
+            };
+            let (size_main, size_cross) = main_cross(layout.direction, size);
+            let (content_main, _) = main_cross(layout.direction, content_size);
+
+            let mut cursor = layout.justify.offset(size_main, content_main);
+            for (item, sized_item) in layout.items.iter().zip(sized_items) {
+                let offset = match sized_item.size {
+                    Some(item_size) => {
+                        let (item_main, item_cross) = main_cross(layout.direction, item_size);
+                        let offset = from_main_cross(
+                            layout.direction,
+                            cursor,
+                            layout.alignment.offset(size_cross, item_cross),
+                        );
+                        cursor += item_main + spacing;
+                        offset
+                    }
+                    // Items without a size are placed at the cursor and take up no space.
+                    // Invisible items are still updated to hide them.
+                    None => from_main_cross(layout.direction, cursor, 0.0),
+                };
+                let item_resolver = resolver
+                    .clone()
+                    .with_offset(position + vec3(offset.x, -offset.y, 0.0));
+                match (item, sized_item.cell_style) {
+                    // Cells were already resolved while sizing them and only need to be moved.
+                    (ComputedGraphicItem::Cell(cell), Some(mut style)) => {
+                        style.pos += item_resolver.position() - resolver.position();
+                        draw_cell(
+                            cell,
+                            style,
+                            batcher,
+                            drawn_cells,
+                            graphic_item_data_storage,
+                            &item_resolver,
+                        );
+                    }
+                    _ => update_graphic_item(
+                        item,
+                        batcher,
+                        drawn_cells,
+                        graphic_item_data_storage,
+                        &item_resolver,
+                        _model,
+                        components,
+                    ),
+                }
+            }
+        }
+        ComputedGraphicItem::Group(group) => {
+            if !resolver.property(&group.visible).unwrap_or(Boolean(true)).0 {
+                return;
+            }
+            let new_resolver = resolver
+                .clone()
+                .with_item_transform(
+                    vec3(
+                        resolver.property(&group.position.x).unwrap_or_default().0,
+                        -resolver.property(&group.position.y).unwrap_or_default().0,
+                        0.0,
+                    ),
+                    &group.rotation,
+                    &group.scale,
+                    &group.pivot,
+                )
+                .with_opacity(resolver.property(&group.opacity).unwrap_or(Number(1.0)).0);
+            for item in group.items.iter() {
+                update_graphic_item(
+                    item,
+                    batcher,
+                    drawn_cells,
+                    graphic_item_data_storage,
+                    &new_resolver,
+                    _model,
+                    components,
+                );
+            }
+        }
+        ComputedGraphicItem::ComponentInstance(instance) => {
+            if !resolver
+                .property(&instance.visible)
+                .unwrap_or(Boolean(true))
+                .0
+            {
+                return;
+            }
+            // A component that contains an instance of itself would never stop nesting.
+            if resolver
+                .parameters()
+                .is_some_and(|parameters| parameters.depth() >= MAX_COMPONENT_DEPTH)
+            {
+                return;
+            }
+            let Some(component) = instance.component.and_then(|id| {
+                components
+                    .contained_graphics()
+                    .into_iter()
+                    .find(|component| component.id == id)
+            }) else {
+                return;
+            };
+
+            let parameters = ParameterScope::new(
+                component
+                    .parameters
+                    .iter()
+                    .map(|parameter| {
+                        // Overrides are ignored if the parameter type has changed since.
+                        let value = instance
+                            .parameters
+                            .get(&parameter.id)
+                            .filter(|value| value.value_type() == parameter.default.value_type())
+                            .unwrap_or(&parameter.default);
+                        (parameter.id, value.as_typed_producer())
+                    })
+                    .collect(),
+                resolver.parameters(),
+            );
+            let new_resolver = resolver
+                .clone()
+                .with_item_transform(
+                    vec3(
+                        resolver
+                            .property(&instance.position.x)
+                            .unwrap_or_default()
+                            .0,
+                        -resolver
+                            .property(&instance.position.y)
+                            .unwrap_or_default()
+                            .0,
+                        0.0,
+                    ),
+                    &instance.rotation,
+                    &instance.scale,
+                    &instance.pivot,
+                )
+                .with_opacity(
+                    resolver
+                        .property(&instance.opacity)
+                        .unwrap_or(Number(1.0))
+                        .0,
+                )
+                .with_parameters(&parameters);
+
+            // Components are always drawn in their template state.
+            let computed_component = component.compute_style(None);
+            update_graphic_item(
+                &computed_component.root,
+                batcher,
+                drawn_cells,
+                &mut graphic_item_data_storage.make_context(instance.id),
+                &new_resolver,
+                _model,
+                components,
+            );
+        }
         ComputedGraphicItem::EntryContext(entry_context) => {
+            let resolver = &resolver.clone().with_opacity(
+                resolver
+                    .property(&entry_context.opacity)
+                    .unwrap_or(Number(1.0))
+                    .0,
+            );
             let mut entries: Vec<&Entry> = resolver.session().entries.values().collect();
             entries.sort_by_key(|e| *e.position);
             let focused_index = entries.iter().position(|e| e.focused);
 
-            let entry = match entry_context.selection {
+            let entry_at_offset = |offset: i64| {
+                focused_index
+                    .and_then(|idx| usize::try_from(idx as i64 + offset).ok())
+                    .and_then(|idx| entries.get(idx))
+            };
+            let entry = match &entry_context.selection {
                 EntrySelection::First => entries.get(0),
                 EntrySelection::Second => entries.get(1),
                 EntrySelection::Third => entries.get(2),
-                EntrySelection::AheadOfFocus => focused_index.and_then(|idx| entries.get(idx - 1)),
-                EntrySelection::Focus => focused_index.and_then(|idx| entries.get(idx)),
-                EntrySelection::BehindFocus => focused_index.and_then(|idx| entries.get(idx + 1)),
+                EntrySelection::AheadOfFocus => entry_at_offset(-1),
+                EntrySelection::Focus => entry_at_offset(0),
+                EntrySelection::BehindFocus => entry_at_offset(1),
+                EntrySelection::Position(position) => resolver
+                    .property(position)
+                    .and_then(|position| usize::try_from(position.0.round() as i64 - 1).ok())
+                    .and_then(|idx| entries.get(idx)),
+                EntrySelection::FocusOffset(offset) => resolver
+                    .property(offset)
+                    .and_then(|offset| entry_at_offset(offset.0.round() as i64)),
+                EntrySelection::CarNumber(car_number) => {
+                    resolver.property(car_number).and_then(|car_number| {
+                        entries
+                            .iter()
+                            .find(|e| *e.car_number as i64 == car_number.0.round() as i64)
+                    })
+                }
+                EntrySelection::EntryId(entry_id) => entries.iter().find(|e| e.id.0 == *entry_id),
+                EntrySelection::Producer(producer) => {
+                    resolver.producer(producer).and_then(|entry_id| {
+                        entries
+                            .iter()
+                            .find(|e| e.id.0 as i64 == entry_id.0.round() as i64)
+                    })
+                }
             };
 
-            let mut new_resolver = resolver.clone();
-            if let Some(entry) = entry {
-                new_resolver = new_resolver.with_entry(entry);
-            }
+            // Without an entry there is nothing to show.
+            let Some(entry) = entry else {
+                return;
+            };
+            let new_resolver = resolver.clone().with_entry(entry);
 
             for item in entry_context.items.iter() {
                 update_graphic_item(
                     item,
                     batcher,
+                    drawn_cells,
                     graphic_item_data_storage,
                     &new_resolver,
                     _model,
+                    components,
                 );
             }
         }
     }
 }
 
+fn draw_cell(
+    cell: &ComputedCell,
+    style: CellStyle,
+    batcher: &mut StyleBatcher,
+    drawn_cells: &mut Vec<DrawnCell>,
+    graphic_item_data_storage: &mut GraphicItemDataStorageContext<'_>,
+    resolver: &StyleResolver,
+) {
+    let cell_id = graphic_item_data_storage.get_or_create(cell.id, || CellId::new());
+    if style.visible && resolver.parameters().is_none() {
+        drawn_cells.push(DrawnCell {
+            id: cell.id,
+            position: style.pos,
+            rotation: style.rotation,
+            scale: style.scale,
+            size: style.size,
+            parent_rotation: resolver.rotation(),
+            parent_scale: resolver.scale(),
+        });
+    }
+    batcher.add(&cell_id, style);
+}
+
+/// A value to sort entries by.
+enum SortValue {
+    Number(f32),
+    Text(String),
+}
+
+/// Sort entries by the value of a producer for each entry.
+/// Entries without a value are placed at the end. Entries with equal values
+/// keep their order.
+fn sort_entries<'a>(
+    entries: &mut Vec<&'a Entry>,
+    sort_key: &AnyProducerRef,
+    sort_order: SortOrder,
+    resolver: &StyleResolver<'a>,
+) {
+    let mut keyed_entries: Vec<(Option<SortValue>, &'a Entry)> = entries
+        .iter()
+        .map(|entry| {
+            let resolver = resolver.clone().with_entry(entry);
+            let value = match sort_key.ty() {
+                ValueType::Number => sort_key
+                    .to_typed::<Number>()
+                    .and_then(|key| resolver.producer(&key))
+                    .map(|number| SortValue::Number(number.0)),
+                ValueType::Text => sort_key
+                    .to_typed::<Text>()
+                    .and_then(|key| resolver.producer(&key))
+                    .map(|text| SortValue::Text(text.0)),
+                _ => None,
+            };
+            (value, *entry)
+        })
+        .collect();
+
+    keyed_entries.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => {
+            let ordering = match (a, b) {
+                (SortValue::Number(a), SortValue::Number(b)) => {
+                    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+                }
+                (SortValue::Text(a), SortValue::Text(b)) => a.cmp(b),
+                _ => Ordering::Equal,
+            };
+            match sort_order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+
+    *entries = keyed_entries.into_iter().map(|(_, entry)| entry).collect();
+}
+
+/// An item of a layout with its size.
+struct SizedLayoutItem {
+    size: Option<Vec2>,
+    /// The style of a cell item, resolved while sizing the cell.
+    cell_style: Option<CellStyle>,
+}
+
+/// Size an item of a layout. Cells keep their resolved style so they
+/// do not have to be resolved again when they are drawn.
+fn size_layout_item(item: &ComputedGraphicItem, resolver: &StyleResolver) -> SizedLayoutItem {
+    match item {
+        ComputedGraphicItem::Cell(cell) => {
+            let cell_style = resolver.cell(cell);
+            SizedLayoutItem {
+                size: cell_style.visible.then_some(cell_style.size),
+                cell_style: Some(cell_style),
+            }
+        }
+        _ => SizedLayoutItem {
+            size: layout_item_size(item, resolver),
+            cell_style: None,
+        },
+    }
+}
+
+/// The size of an item inside a layout. `None` if the item takes up no space.
+fn layout_item_size(item: &ComputedGraphicItem, resolver: &StyleResolver) -> Option<Vec2> {
+    match item {
+        ComputedGraphicItem::Cell(cell) => {
+            let cell_style = resolver.cell(cell);
+            cell_style.visible.then_some(cell_style.size)
+        }
+        ComputedGraphicItem::ClipArea(clip_area) => Some(vec2(
+            resolver.property(&clip_area.size.x).unwrap_or_default().0,
+            resolver.property(&clip_area.size.y).unwrap_or_default().0,
+        )),
+        ComputedGraphicItem::Layout(layout) => Some(layout_size(layout, resolver)),
+        ComputedGraphicItem::Group(group) => {
+            if !resolver.property(&group.visible).unwrap_or(Boolean(true)).0 {
+                return None;
+            }
+            let scale = vec2(
+                resolver.property(&group.scale.x).unwrap_or(Number(1.0)).0,
+                resolver.property(&group.scale.y).unwrap_or(Number(1.0)).0,
+            );
+            let extent = group
+                .items
+                .iter()
+                .filter_map(|item| layout_item_extent(item, resolver))
+                .fold(Vec2::ZERO, Vec2::max);
+            Some(extent * scale)
+        }
+        // The content of these items depends on the model and has no size up front.
+        ComputedGraphicItem::Root(_)
+        | ComputedGraphicItem::DriverTable(_)
+        | ComputedGraphicItem::EntryContext(_)
+        | ComputedGraphicItem::Repeater(_)
+        | ComputedGraphicItem::ComponentInstance(_) => None,
+    }
+}
+
+/// The bottom right corner of an item relative to the origin of its parent.
+/// `None` if the item takes up no space.
+fn layout_item_extent(item: &ComputedGraphicItem, resolver: &StyleResolver) -> Option<Vec2> {
+    let position = match item {
+        ComputedGraphicItem::Cell(cell) => vec2(
+            resolver.property(&cell.pos.x).unwrap_or_default().0,
+            resolver.property(&cell.pos.y).unwrap_or_default().0,
+        ),
+        ComputedGraphicItem::ClipArea(clip_area) => vec2(
+            resolver.property(&clip_area.pos.x).unwrap_or_default().0,
+            resolver.property(&clip_area.pos.y).unwrap_or_default().0,
+        ),
+        ComputedGraphicItem::Layout(layout) => vec2(
+            resolver.property(&layout.position.x).unwrap_or_default().0,
+            resolver.property(&layout.position.y).unwrap_or_default().0,
+        ),
+        ComputedGraphicItem::Group(group) => vec2(
+            resolver.property(&group.position.x).unwrap_or_default().0,
+            resolver.property(&group.position.y).unwrap_or_default().0,
+        ),
+        _ => Vec2::ZERO,
+    };
+    layout_item_size(item, resolver).map(|size| position + size)
+}
+
+/// The size of a layout. Either the fixed size or the size of its visible children.
+fn layout_size(layout: &ComputedLayout, resolver: &StyleResolver) -> Vec2 {
+    if layout.auto_size {
+        layout_content_size(
+            layout.direction,
+            layout
+                .items
+                .iter()
+                .filter_map(|item| layout_item_size(item, resolver)),
+            resolver.property(&layout.spacing).unwrap_or_default().0,
+        )
+    } else {
+        vec2(
+            resolver.property(&layout.size.x).unwrap_or_default().0,
+            resolver.property(&layout.size.y).unwrap_or_default().0,
+        )
+    }
+}
+
+/// The size of items placed next to each other with spacing in between.
+fn layout_content_size(
+    direction: LayoutDirection,
+    item_sizes: impl Iterator<Item = Vec2>,
+    spacing: f32,
+) -> Vec2 {
+    let mut main = 0.0_f32;
+    let mut cross = 0.0_f32;
+    for (index, item_size) in item_sizes.enumerate() {
+        let (item_main, item_cross) = main_cross(direction, item_size);
+        if index > 0 {
+            main += spacing;
+        }
+        main += item_main;
+        cross = cross.max(item_cross);
+    }
+    from_main_cross(direction, main, cross)
+}
+
+/// Split a vector into its component along and across the layout direction.
+fn main_cross(direction: LayoutDirection, v: Vec2) -> (f32, f32) {
+    match direction {
+        LayoutDirection::Horizontal => (v.x, v.y),
+        LayoutDirection::Vertical => (v.y, v.x),
+    }
+}
+
+fn from_main_cross(direction: LayoutDirection, main: f32, cross: f32) -> Vec2 {
+    match direction {
+        LayoutDirection::Horizontal => vec2(main, cross),
+        LayoutDirection::Vertical => vec2(cross, main),
+    }
+}
+
+/// The animated row index of an entry in a driver table.
+struct RowAnimation {
+    from: f32,
+    to: f32,
+    started: Instant,
+}
+impl RowAnimation {
+    fn current(&self, now: Instant, duration: f32, easing: Easing) -> f32 {
+        let progress = if duration > 0.0 {
+            now.duration_since(self.started).as_secs_f32() / duration
+        } else {
+            1.0
+        };
+        self.from + (self.to - self.from) * easing.apply(progress)
+    }
+}
+
 #[derive(Default)]
 struct DriverTableData {
     scroll_position: f32,
+    scroll_target: f32,
+    last_update: Option<Instant>,
+    page: usize,
+    page_started: Option<Instant>,
 }
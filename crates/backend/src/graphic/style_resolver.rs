@@ -2,13 +2,23 @@ use bevy::{
     math::{vec2, Vec2, Vec3},
     render::color::Color,
 };
-use common::communication::{CellStyle, ClipAreaStyle};
-use unified_sim_model::model::{Entry, Session};
+use common::communication::{
+    BorderStyle, CellStyle, ClipAreaStyle, Gradient, ShadowStyle, TextAlignment, TextOutlineStyle,
+    TextShadowStyle,
+};
+use unified_sim_model::model::{Entry, Lap, Session, Time};
 
 use crate::{
-    style::graphic::graphic_items::{cell::ComputedCell, clip_area::ComputedClipArea},
-    value_store::{ModelContext, ValueResolver, ValueStore},
-    value_types::{Boolean, Font, Number, Property, Text, Texture, Tint, Vec2Property},
+    style::graphic::graphic_items::{
+        cell::{CellGradient, ComputedCell, TextFit, TextOverflow},
+        clip_area::ComputedClipArea,
+    },
+    value_store::{
+        ModelContext, ParameterScope, RepeatIndex, RepeatKey, ValueResolver, ValueStore,
+    },
+    value_types::{
+        Boolean, Font, Number, ProducerRef, Property, Text, Texture, Tint, Vec2Property,
+    },
 };
 
 #[derive(Clone)]
@@ -16,23 +26,67 @@ pub struct StyleResolver<'a> {
     value_store: &'a ValueStore,
     context: ModelContext<'a>,
     position: Vec3,
+    /// Rotation in radians counter clockwise.
+    rotation: f32,
+    scale: Vec2,
     render_layer: u8,
+    opacity: f32,
 }
 impl<'a> StyleResolver<'a> {
     pub fn new(value_store: &'a ValueStore, session: &'a Session) -> Self {
         Self {
             value_store,
             position: Vec3::ZERO,
+            rotation: 0.0,
+            scale: Vec2::ONE,
             render_layer: 0,
+            opacity: 1.0,
             context: ModelContext {
                 session: Some(session),
                 entry: None,
+                lap: None,
+                sector: None,
+                repeat: None,
+                parameters: None,
             },
         }
     }
 
+    /// Place the resolver at a position without any rotation or scale.
     pub fn with_position(mut self, position: Vec3) -> Self {
         self.position = position;
+        self.rotation = 0.0;
+        self.scale = Vec2::ONE;
+        self
+    }
+
+    /// Move the resolver by an offset in its local space.
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.position = self.transform_point(offset);
+        self
+    }
+
+    /// Move the resolver to the position of an item and apply the
+    /// rotation and scale of the item around its pivot.
+    pub fn with_item_transform(
+        mut self,
+        position: Vec3,
+        rotation: &Property<Number>,
+        scale: &Vec2Property,
+        pivot: &Vec2Property,
+    ) -> Self {
+        // Rotation is clockwise in degrees in the style but counter clockwise
+        // in radians in the resolver.
+        let rotation = -self.number(rotation).to_radians();
+        let scale = vec2(
+            self.property(&scale.x).unwrap_or(Number(1.0)).0,
+            self.property(&scale.y).unwrap_or(Number(1.0)).0,
+        );
+        let pivot = self.vec2(pivot) * vec2(1.0, -1.0);
+        let origin = pivot - Vec2::from_angle(rotation).rotate(pivot * scale);
+        self.position = self.transform_point(position + origin.extend(0.0));
+        self.rotation += rotation;
+        self.scale *= scale;
         self
     }
 
@@ -41,11 +95,71 @@ impl<'a> StyleResolver<'a> {
         self
     }
 
+    /// Multiply the opacity of all resolved styles with this opacity.
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity *= opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Resolve styles at full opacity.
+    /// Used when the opacity is applied to a group of styles as a whole.
+    pub fn with_full_opacity(mut self) -> Self {
+        self.opacity = 1.0;
+        self
+    }
+
     pub fn with_entry(mut self, entry: &'a Entry) -> Self {
         self.context.entry = Some(entry);
         self
     }
 
+    pub fn with_lap(mut self, lap: &'a Lap) -> Self {
+        self.context.lap = Some(lap);
+        self
+    }
+
+    pub fn with_sector(mut self, sector: &'a Time) -> Self {
+        self.context.sector = Some(sector);
+        self
+    }
+
+    pub fn with_repeat(mut self, index: usize, count: usize, key: RepeatKey) -> Self {
+        self.context.repeat = Some(RepeatIndex { index, count, key });
+        self
+    }
+
+    pub fn with_parameters(mut self, parameters: &'a ParameterScope<'a>) -> Self {
+        self.context.parameters = Some(parameters);
+        self
+    }
+
+    pub fn entry(&self) -> Option<&'a Entry> {
+        self.context.entry
+    }
+
+    pub fn lap(&self) -> Option<&'a Lap> {
+        self.context.lap
+    }
+
+    pub fn parameters(&self) -> Option<&'a ParameterScope<'a>> {
+        self.context.parameters
+    }
+
+    /// Origin of the local space.
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    /// Rotation of the local space in radians counter clockwise.
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Scale of the local space.
+    pub fn scale(&self) -> Vec2 {
+        self.scale
+    }
+
     pub fn property<T>(&self, property: &Property<T>) -> Option<T>
     where
         ValueStore: ValueResolver<T>,
@@ -54,19 +168,28 @@ impl<'a> StyleResolver<'a> {
         self.value_store.get_property(property, self.context)
     }
 
+    pub fn producer<T>(&self, producer_ref: &ProducerRef<T>) -> Option<T>
+    where
+        ValueStore: ValueResolver<T>,
+    {
+        self.value_store.get(producer_ref, self.context)
+    }
+
     pub fn session(&self) -> &Session {
         self.context
             .session
             .expect("Context should always have a session")
     }
 
-    pub fn position(&self) -> &Vec3 {
-        &self.position
+    /// Transform a point from the local space of the resolver into world space.
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        let xy = Vec2::from_angle(self.rotation).rotate(point.truncate() * self.scale);
+        self.position + xy.extend(point.z)
     }
 
     pub fn clip_area(&self, clip_area: &ComputedClipArea) -> ClipAreaStyle {
-        ClipAreaStyle {
-            pos: Vec3::new(
+        let transform = self.clone().with_item_transform(
+            Vec3::new(
                 self.value_store
                     .get_property(&clip_area.pos.x, self.context)
                     .unwrap_or_default()
@@ -80,7 +203,15 @@ impl<'a> StyleResolver<'a> {
                     .get_property(&clip_area.pos.z, self.context)
                     .unwrap_or_default()
                     .0,
-            ) + self.position,
+            ),
+            &clip_area.rotation,
+            &clip_area.scale,
+            &clip_area.pivot,
+        );
+        ClipAreaStyle {
+            pos: transform.position,
+            rotation: transform.rotation,
+            scale: transform.scale,
             size: Vec2::new(
                 self.value_store
                     .get_property(&clip_area.size.x, self.context)
@@ -123,11 +254,32 @@ impl<'a> StyleResolver<'a> {
                     .0,
             ],
             render_layer: clip_area.render_layer,
+            opacity: self.opacity * self.number(&clip_area.opacity).clamp(0.0, 1.0),
         }
     }
 
     pub fn cell(&self, cell: &ComputedCell) -> CellStyle {
-        CellStyle {
+        let transform = self.clone().with_item_transform(
+            Vec3::new(
+                self.value_store
+                    .get_property(&cell.pos.x, self.context)
+                    .unwrap_or(Number(0.0))
+                    .0,
+                self.value_store
+                    .get_property(&cell.pos.y, self.context)
+                    .unwrap_or(Number(0.0))
+                    .0
+                    * -1.0,
+                self.value_store
+                    .get_property(&cell.pos.z, self.context)
+                    .unwrap_or(Number(0.0))
+                    .0,
+            ),
+            &cell.rotation,
+            &cell.scale,
+            &cell.pivot,
+        );
+        let mut style = CellStyle {
             text: self
                 .value_store
                 .get_property(&cell.text, self.context)
@@ -154,6 +306,19 @@ impl<'a> StyleResolver<'a> {
                     .unwrap_or(Number(0.0))
                     .0,
             ),
+            line_height: self
+                .value_store
+                .get_property(&cell.line_height, self.context)
+                .unwrap_or(Number(1.2))
+                .0,
+            text_outline: TextOutlineStyle {
+                width: self.number(&cell.text_outline.width),
+                color: self.tint(&cell.text_outline.color),
+            },
+            text_shadow: TextShadowStyle {
+                offset: self.vec2(&cell.text_shadow.offset),
+                color: self.tint(&cell.text_shadow.color),
+            },
             font: self
                 .value_store
                 .get_property(&cell.font, self.context)
@@ -166,6 +331,16 @@ impl<'a> StyleResolver<'a> {
                 .get_property(&cell.color, self.context)
                 .unwrap_or(Tint(Color::RED))
                 .0,
+            gradient: self.gradient(&cell.gradient),
+            border: BorderStyle {
+                width: self.number(&cell.border.width),
+                color: self.tint(&cell.border.color),
+            },
+            shadow: ShadowStyle {
+                offset: self.vec2(&cell.shadow.offset),
+                blur: self.number(&cell.shadow.blur),
+                color: self.tint(&cell.shadow.color),
+            },
             texture: self
                 .value_store
                 .get_property(&cell.image, self.context)
@@ -173,21 +348,9 @@ impl<'a> StyleResolver<'a> {
                     Texture::None => None,
                     Texture::Handle(handle) => Some(handle),
                 }),
-            pos: Vec3::new(
-                self.value_store
-                    .get_property(&cell.pos.x, self.context)
-                    .unwrap_or(Number(0.0))
-                    .0,
-                self.value_store
-                    .get_property(&cell.pos.y, self.context)
-                    .unwrap_or(Number(0.0))
-                    .0
-                    * -1.0,
-                self.value_store
-                    .get_property(&cell.pos.z, self.context)
-                    .unwrap_or(Number(0.0))
-                    .0,
-            ) + self.position,
+            pos: transform.position,
+            rotation: transform.rotation,
+            scale: transform.scale,
             size: Vec2::new(
                 self.value_store
                     .get_property(&cell.size.x, self.context)
@@ -248,6 +411,156 @@ impl<'a> StyleResolver<'a> {
                     .0,
             ],
             render_layer: self.render_layer,
+            opacity: self.opacity * self.number(&cell.opacity).clamp(0.0, 1.0),
+        };
+        self.fit_text(cell, &mut style);
+        self.layout_text(cell, &mut style);
+        style
+    }
+
+    fn number(&self, property: &Property<Number>) -> f32 {
+        self.value_store
+            .get_property(property, self.context)
+            .unwrap_or_default()
+            .0
+    }
+
+    fn tint(&self, property: &Property<Tint>) -> Color {
+        self.value_store
+            .get_property(property, self.context)
+            .unwrap_or(Tint(Color::NONE))
+            .0
+    }
+
+    fn vec2(&self, property: &Vec2Property) -> Vec2 {
+        vec2(self.number(&property.x), self.number(&property.y))
+    }
+
+    fn gradient(&self, gradient: &CellGradient) -> Gradient {
+        match gradient {
+            CellGradient::None => Gradient::None,
+            CellGradient::Linear {
+                color,
+                position,
+                spread,
+                angle,
+            } => Gradient::Linear {
+                color: self.tint(color),
+                position: self.vec2(position),
+                spread: self.number(spread),
+                angle: self.number(angle),
+            },
+            CellGradient::Radial {
+                color,
+                position,
+                spread,
+                distance,
+            } => Gradient::Radial {
+                color: self.tint(color),
+                position: self.vec2(position),
+                spread: self.number(spread),
+                distance: self.number(distance),
+            },
+            CellGradient::Conical {
+                color,
+                position,
+                angle,
+            } => Gradient::Conical {
+                color: self.tint(color),
+                position: self.vec2(position),
+                angle: self.number(angle),
+            },
+        }
+    }
+
+    /// Fit the cell and its text to each other.
+    fn fit_text(&self, cell: &ComputedCell, style: &mut CellStyle) {
+        if cell.text_fit == TextFit::None {
+            return;
+        }
+        let padding = self
+            .value_store
+            .get_property(&cell.text_padding, self.context)
+            .unwrap_or_default()
+            .0;
+        let font = self
+            .value_store
+            .get_property(&cell.font, self.context)
+            .unwrap_or_default();
+        let text_width =
+            self.value_store
+                .text_measurement()
+                .text_width(&style.text, &font, style.text_size);
+        match cell.text_fit {
+            TextFit::None => (),
+            TextFit::FitWidth => {
+                style.size.x = text_width + 2.0 * padding;
+                style.text_position.x = match style.text_alignment {
+                    TextAlignment::Left => padding,
+                    TextAlignment::Center => style.size.x / 2.0,
+                    TextAlignment::Right => style.size.x - padding,
+                };
+            }
+            TextFit::ShrinkText => {
+                let available_width = (style.size.x - 2.0 * padding).max(0.0);
+                if text_width > available_width {
+                    style.text_size *= available_width / text_width;
+                }
+            }
+        }
+    }
+
+    /// Wrap and cut the text to the bounds of the cell.
+    fn layout_text(&self, cell: &ComputedCell, style: &mut CellStyle) {
+        if !cell.text_wrap && cell.text_overflow == TextOverflow::Visible {
+            return;
+        }
+        let text_measurement = self.value_store.text_measurement();
+        let padding = self
+            .value_store
+            .get_property(&cell.text_padding, self.context)
+            .unwrap_or_default()
+            .0;
+        let font = self
+            .value_store
+            .get_property(&cell.font, self.context)
+            .unwrap_or_default();
+        let max_width = (style.size.x - 2.0 * padding).max(0.0);
+
+        let mut lines = if cell.text_wrap {
+            text_measurement.wrap(&style.text, &font, style.text_size, max_width)
+        } else {
+            style.text.split('\n').map(String::from).collect()
+        };
+
+        if cell.text_overflow != TextOverflow::Visible {
+            let line_advance = style.text_size * style.line_height;
+            let max_lines = if line_advance > 0.0 {
+                ((style.size.y / line_advance).floor() as usize).max(1)
+            } else {
+                lines.len()
+            };
+            let lines_cut = lines.len() > max_lines;
+            lines.truncate(max_lines);
+
+            let suffix = match cell.text_overflow {
+                TextOverflow::Ellipsis => "…",
+                _ => "",
+            };
+            let line_count = lines.len();
+            for (index, line) in lines.iter_mut().enumerate() {
+                // The last line gets an ellipsis if lines were cut.
+                let force_suffix = lines_cut && index == line_count - 1;
+                *line = text_measurement.truncate(
+                    line,
+                    &font,
+                    style.text_size,
+                    max_width,
+                    suffix,
+                    force_suffix,
+                );
+            }
         }
+        style.text = lines.join("\n");
     }
 }
@@ -1,17 +1,34 @@
-use bevy::{app::Plugin, ecs::system::Resource};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use bevy::{
+    app::{First, Plugin},
+    ecs::system::{ResMut, Resource},
+};
 use graphic::GraphicPlugin;
+use position_history::PositionHistoryPlugin;
+use race_events::RaceEventsPlugin;
 use savefile::SavefilePlugin;
+use scenario::Scenario;
 use style_batcher::StyleBatcherPlugin;
+use text_measurement::TextMeasurementPlugin;
 use tracing::{error, info};
-use unified_sim_model::{Adapter, AdapterCommand};
+use unified_sim_model::{model::Model, Adapter, AdapterCommand};
 use value_store::ValueStorePlugin;
 
 pub mod exact_variant;
 pub mod game_sources;
 pub mod graphic;
+pub mod position_history;
+pub mod race_events;
 pub mod savefile;
+pub mod scenario;
 pub mod style;
 pub mod style_batcher;
+pub mod text_measurement;
 pub mod tree_iterator;
 pub mod ui;
 pub mod value_store;
@@ -20,17 +37,30 @@ pub mod value_types;
 pub struct BackendPlugin;
 impl Plugin for BackendPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.insert_resource(GameAdapterResource { adapter: None })
-            .add_plugins(StyleBatcherPlugin)
-            .add_plugins(ValueStorePlugin)
-            .add_plugins(SavefilePlugin)
-            .add_plugins(GraphicPlugin);
+        app.insert_resource(GameAdapterResource {
+            adapter: None,
+            scenario: None,
+            scenario_model: None,
+        })
+        .add_systems(First, apply_scenario)
+        .add_plugins(StyleBatcherPlugin)
+        .add_plugins(ValueStorePlugin)
+        .add_plugins(SavefilePlugin)
+        .add_plugins(GraphicPlugin)
+        .add_plugins(RaceEventsPlugin)
+        .add_plugins(PositionHistoryPlugin)
+        .add_plugins(TextMeasurementPlugin);
     }
 }
 
 #[derive(Resource)]
 pub struct GameAdapterResource {
     adapter: Option<Adapter>,
+    /// The scenario that is applied to the model of the adapter.
+    scenario: Option<Scenario>,
+    /// The model of the adapter with the scenario applied and the version of the
+    /// adapter model it was created from.
+    scenario_model: Option<(u64, Model)>,
 }
 impl GameAdapterResource {
     pub fn adapter(&self) -> Option<&Adapter> {
@@ -41,8 +71,81 @@ impl GameAdapterResource {
     }
     pub fn set(&mut self, adapter: Adapter) {
         self.adapter = Some(adapter);
+        self.scenario_model = None;
+    }
+    /// The model of the adapter. If a scenario is active the scenario is applied to the model.
+    pub fn model(&self) -> Option<ModelRef<'_>> {
+        if let Some((_, model)) = &self.scenario_model {
+            return Some(ModelRef(Box::new(model)));
+        }
+        self.adapter
+            .as_ref()
+            .map(|adapter| ModelRef(Box::new(adapter.model.read_raw())))
+    }
+    pub fn scenario(&self) -> Option<&Scenario> {
+        self.scenario.as_ref()
+    }
+    pub fn set_scenario(&mut self, scenario: Option<Scenario>) {
+        self.scenario = scenario;
+        self.scenario_model = None;
+    }
+}
+
+/// Shared access to the model of the game adapter.
+pub struct ModelRef<'a>(Box<dyn Deref<Target = Model> + 'a>);
+impl Deref for ModelRef<'_> {
+    type Target = Model;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
+
+/// Apply the active scenario to a copy of the model.
+///
+/// Copying the model is expensive so the copy is only made again when the
+/// model of the adapter or the scenario has changed.
+fn apply_scenario(mut game_adapter: ResMut<GameAdapterResource>) {
+    let game_adapter = &mut *game_adapter;
+    let (Some(adapter), Some(scenario)) = (&game_adapter.adapter, &game_adapter.scenario) else {
+        game_adapter.scenario_model = None;
+        return;
+    };
+    let model = adapter.model.read_raw();
+    let version = model_version(&model);
+    if game_adapter
+        .scenario_model
+        .as_ref()
+        .is_some_and(|(scenario_version, _)| *scenario_version == version)
+    {
+        return;
+    }
+    let mut scenario_model = (*model).clone();
+    scenario.apply(&mut scenario_model);
+    game_adapter.scenario_model = Some((version, scenario_model));
+}
+
+/// A cheap fingerprint of the model that changes whenever the adapter updates it.
+///
+/// The session time and the current lap of every entry advance with every
+/// update while a session is running. Positions, laps and pit state are included
+/// so that changes outside of a running session are picked up as well.
+fn model_version(model: &Model) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    if let Some(session) = model.current_session() {
+        session.session_time.ms.to_bits().hash(&mut hasher);
+        session.time_remaining.ms.to_bits().hash(&mut hasher);
+        for entry in session.entries.values() {
+            entry.id.0.hash(&mut hasher);
+            (*entry.position).hash(&mut hasher);
+            (*entry.lap_count).hash(&mut hasher);
+            (*entry.in_pits).hash(&mut hasher);
+            entry.current_lap.time.ms.to_bits().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
 impl Drop for GameAdapterResource {
     fn drop(&mut self) {
         info!("Dropping game adapter resource, making sure the adapter quits properly");
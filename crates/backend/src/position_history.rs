@@ -0,0 +1,182 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::{
+    app::{Plugin, Update},
+    ecs::{
+        schedule::IntoSystemConfigs,
+        system::{Res, ResMut},
+    },
+};
+use serde::{Deserialize, Serialize};
+use unified_sim_model::model::{EntryId, SessionId, SessionPhase};
+
+use crate::{graphic::StyleElementUpdate, value_store::ValueStore, GameAdapterResource};
+
+pub struct PositionHistoryPlugin;
+impl Plugin for PositionHistoryPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, update_position_history.before(StyleElementUpdate));
+    }
+}
+
+/// Direction in which the position of an entry has changed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PositionTrend {
+    Up,
+    Down,
+    Same,
+}
+
+/// The point in the session that a position change is measured from.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum PositionReference {
+    /// The position when the session went active.
+    #[default]
+    RaceStart,
+    /// The position when the entry started its previous lap.
+    /// This covers the last completed lap and the current lap, so the change
+    /// does not reset when the entry crosses the line.
+    LastLap,
+    /// The position when the entry started the lap this many laps before the current lap.
+    LapsAgo(u32),
+}
+
+/// Remembers the positions of entries at reference points during the session.
+///
+/// The start position is the position an entry had when the session went
+/// active. For entries that joined later it is the first position they were seen in.
+/// Separately from that, the position at the start of every lap is kept.
+///
+/// The history also remembers the last lap each entry was in the pits, so an
+/// out lap can be told from the model without watching the entry leave the pits.
+#[derive(Default)]
+pub struct PositionHistory {
+    session: Option<SessionId>,
+    /// Time of the session in milliseconds when the history was last updated.
+    session_time: f64,
+    start_positions: HashMap<EntryId, i32>,
+    /// The position of an entry at the start of each lap, keyed by the lap count.
+    lap_start_positions: HashMap<EntryId, BTreeMap<i32, i32>>,
+    lap_counts: HashMap<EntryId, i32>,
+    /// The last lap count at which an entry was seen in the pits.
+    pit_laps: HashMap<EntryId, i32>,
+}
+
+impl PositionHistory {
+    pub fn start_position(&self, entry_id: &EntryId) -> Option<i32> {
+        self.start_positions.get(entry_id).copied()
+    }
+
+    /// The position of an entry at the given reference.
+    ///
+    /// If the entry was not seen at the start of the reference lap, the
+    /// earliest lap start after it is used instead.
+    pub fn reference_position(
+        &self,
+        entry_id: &EntryId,
+        reference: PositionReference,
+    ) -> Option<i32> {
+        let laps_ago = match reference {
+            PositionReference::RaceStart => return self.start_position(entry_id),
+            PositionReference::LastLap => 1,
+            PositionReference::LapsAgo(laps) => laps as i32,
+        };
+        let lap_count = self.lap_counts.get(entry_id)?;
+        self.lap_start_positions
+            .get(entry_id)?
+            .range(lap_count - laps_ago..)
+            .next()
+            .map(|(_, position)| *position)
+    }
+
+    /// Positions gained since the reference. Negative if positions were lost.
+    pub fn positions_gained(
+        &self,
+        entry_id: &EntryId,
+        position: i32,
+        reference: PositionReference,
+    ) -> Option<i32> {
+        self.reference_position(entry_id, reference)
+            .map(|reference_position| reference_position - position)
+    }
+
+    /// Test if the entry is on the lap it left the pits on.
+    pub fn is_out_lap(&self, entry_id: &EntryId, lap_count: i32, in_pits: bool) -> bool {
+        !in_pits && self.pit_laps.get(entry_id) == Some(&lap_count)
+    }
+
+    pub fn trend(
+        &self,
+        entry_id: &EntryId,
+        position: i32,
+        reference: PositionReference,
+    ) -> Option<PositionTrend> {
+        self.positions_gained(entry_id, position, reference)
+            .map(|delta| match delta {
+                d if d > 0 => PositionTrend::Up,
+                d if d < 0 => PositionTrend::Down,
+                _ => PositionTrend::Same,
+            })
+    }
+
+    fn clear(&mut self) {
+        self.start_positions.clear();
+        self.lap_start_positions.clear();
+        self.lap_counts.clear();
+        self.pit_laps.clear();
+    }
+}
+
+fn update_position_history(
+    game_adapter: Res<GameAdapterResource>,
+    mut value_store: ResMut<ValueStore>,
+) {
+    let history = value_store.position_history_mut();
+    let Some(model) = game_adapter.model() else {
+        history.session = None;
+        history.clear();
+        return;
+    };
+    let Some(session) = model.current_session() else {
+        return;
+    };
+
+    // A session that is restarted keeps its id but its time goes back.
+    let session_time = session.session_time.ms;
+    if history.session != Some(session.id) || session_time < history.session_time {
+        history.session = Some(session.id);
+        history.clear();
+    }
+    history.session_time = session_time;
+
+    // Until the session is active the grid can still change.
+    let before_start = matches!(
+        *session.phase,
+        SessionPhase::None
+            | SessionPhase::Waiting
+            | SessionPhase::Preparing
+            | SessionPhase::Formation
+    );
+
+    for entry in session.entries.values() {
+        let position = *entry.position as i32;
+        let lap_count = *entry.lap_count as i32;
+
+        if before_start {
+            history.start_positions.insert(entry.id, position);
+        } else {
+            history.start_positions.entry(entry.id).or_insert(position);
+        }
+
+        history.lap_counts.insert(entry.id, lap_count);
+        let lap_starts = history.lap_start_positions.entry(entry.id).or_default();
+        if before_start {
+            lap_starts.clear();
+        }
+        lap_starts.entry(lap_count).or_insert(position);
+
+        if *entry.in_pits {
+            history.pit_laps.insert(entry.id, lap_count);
+        }
+    }
+}
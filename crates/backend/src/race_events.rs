@@ -0,0 +1,434 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use bevy::{
+    app::{Plugin, Update},
+    ecs::{
+        event::{Event, EventReader, EventWriter},
+        schedule::IntoSystemConfigs,
+        system::{Local, Res, ResMut},
+    },
+};
+use serde::{Deserialize, Serialize};
+use unified_sim_model::model::{Entry, EntryId, Session, SessionId, SessionType};
+
+use crate::{
+    graphic::{GraphicStates, StyleElementUpdate},
+    savefile::Savefile,
+    style::{graphic::GraphicStateId, StyleId},
+    value_store::ValueStore,
+    GameAdapterResource,
+};
+
+pub struct RaceEventsPlugin;
+impl Plugin for RaceEventsPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_event::<RaceEvent>().add_systems(
+            Update,
+            (detect_race_events, trigger_graphic_states)
+                .chain()
+                .before(StyleElementUpdate),
+        );
+    }
+}
+
+/// An event that happend during a session.
+///
+/// Race events are detected by comparing the current state of the model
+/// with the state of the model in the previous frame.
+#[derive(Event, Clone, Debug)]
+pub enum RaceEvent {
+    /// An entry has overtaken another entry on track.
+    Overtake {
+        overtaking: EntryId,
+        overtaken: EntryId,
+    },
+    /// An entry has set a new fastest lap of the session.
+    FastestLap { entry: EntryId },
+    /// An entry has entered the pits.
+    PitEntry { entry: EntryId },
+    /// An entry has left the pits.
+    PitExit { entry: EntryId },
+    /// An entry has disconnected from the session.
+    /// The model does not report when an entry enters the garage, so an entry
+    /// that parks in the garage and stays connected is not detected.
+    Retired { entry: EntryId },
+    /// The leader of the session has changed.
+    LeaderChange {
+        new_leader: EntryId,
+        previous_leader: EntryId,
+    },
+    /// The leader has started the final lap of the race.
+    FinalLap { leader: EntryId },
+}
+impl RaceEvent {
+    pub fn kind(&self) -> RaceEventKind {
+        match self {
+            RaceEvent::Overtake { .. } => RaceEventKind::Overtake,
+            RaceEvent::FastestLap { .. } => RaceEventKind::FastestLap,
+            RaceEvent::PitEntry { .. } => RaceEventKind::PitEntry,
+            RaceEvent::PitExit { .. } => RaceEventKind::PitExit,
+            RaceEvent::Retired { .. } => RaceEventKind::Retired,
+            RaceEvent::LeaderChange { .. } => RaceEventKind::LeaderChange,
+            RaceEvent::FinalLap { .. } => RaceEventKind::FinalLap,
+        }
+    }
+
+    /// The entries that take part in this event.
+    /// The first entry is the main subject of the event.
+    pub fn entries(&self) -> Vec<EntryId> {
+        match self {
+            RaceEvent::Overtake {
+                overtaking,
+                overtaken,
+            } => vec![*overtaking, *overtaken],
+            RaceEvent::FastestLap { entry }
+            | RaceEvent::PitEntry { entry }
+            | RaceEvent::PitExit { entry }
+            | RaceEvent::Retired { entry } => vec![*entry],
+            RaceEvent::LeaderChange {
+                new_leader,
+                previous_leader,
+            } => vec![*new_leader, *previous_leader],
+            RaceEvent::FinalLap { leader } => vec![*leader],
+        }
+    }
+}
+
+/// The kind of a race event without any of its data.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum RaceEventKind {
+    #[default]
+    Overtake,
+    FastestLap,
+    PitEntry,
+    PitExit,
+    Retired,
+    LeaderChange,
+    FinalLap,
+}
+impl RaceEventKind {
+    /// All kinds of race events.
+    pub const ALL: [RaceEventKind; 7] = [
+        RaceEventKind::Overtake,
+        RaceEventKind::FastestLap,
+        RaceEventKind::PitEntry,
+        RaceEventKind::PitExit,
+        RaceEventKind::Retired,
+        RaceEventKind::LeaderChange,
+        RaceEventKind::FinalLap,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RaceEventKind::Overtake => "Overtake",
+            RaceEventKind::FastestLap => "Fastest lap",
+            RaceEventKind::PitEntry => "Pit entry",
+            RaceEventKind::PitExit => "Pit exit",
+            RaceEventKind::Retired => "Retired",
+            RaceEventKind::LeaderChange => "Leader change",
+            RaceEventKind::FinalLap => "Final lap",
+        }
+    }
+}
+
+/// A race event with the time it happend.
+#[derive(Clone, Debug)]
+pub struct LoggedRaceEvent {
+    pub event: RaceEvent,
+    pub time: Instant,
+}
+impl LoggedRaceEvent {
+    /// Time since the event happend.
+    pub fn age(&self) -> Duration {
+        self.time.elapsed()
+    }
+}
+
+/// Keeps the most recent race events so they can be used by value producers.
+#[derive(Default)]
+pub struct RaceEventLog {
+    last: Option<LoggedRaceEvent>,
+    last_of_kind: HashMap<RaceEventKind, LoggedRaceEvent>,
+}
+impl RaceEventLog {
+    /// The most recent event of any kind.
+    pub fn last(&self) -> Option<&LoggedRaceEvent> {
+        self.last.as_ref()
+    }
+
+    /// The most recent event of a given kind.
+    pub fn last_of_kind(&self, kind: RaceEventKind) -> Option<&LoggedRaceEvent> {
+        self.last_of_kind.get(&kind)
+    }
+
+    fn push(&mut self, event: RaceEvent) {
+        let logged = LoggedRaceEvent {
+            event,
+            time: Instant::now(),
+        };
+        self.last_of_kind.insert(logged.event.kind(), logged.clone());
+        self.last = Some(logged);
+    }
+
+    fn clear(&mut self) {
+        self.last = None;
+        self.last_of_kind.clear();
+    }
+}
+
+/// Changes the state of a graphic for a time when a race event happens.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphicStateTrigger {
+    pub event: RaceEventKind,
+    pub state: GraphicStateId,
+    /// How long the graphic stays in the state in seconds.
+    pub duration: f32,
+}
+impl GraphicStateTrigger {
+    pub fn new() -> Self {
+        Self {
+            event: RaceEventKind::default(),
+            state: GraphicStateId::default(),
+            duration: 5.0,
+        }
+    }
+}
+
+struct ModelSnapshot {
+    session_id: SessionId,
+    /// Time of the session in milliseconds.
+    session_time: f64,
+    entries: HashMap<EntryId, EntrySnapshot>,
+    best_lap: Option<(Option<EntryId>, f32)>,
+    leader: Option<EntryId>,
+    final_lap_started: bool,
+}
+
+struct EntrySnapshot {
+    position: i32,
+    lap_count: i32,
+    in_pits: bool,
+    connected: bool,
+}
+impl EntrySnapshot {
+    fn new(entry: &Entry) -> Self {
+        Self {
+            position: *entry.position as i32,
+            lap_count: *entry.lap_count as i32,
+            in_pits: *entry.in_pits,
+            connected: *entry.connected,
+        }
+    }
+}
+
+fn detect_race_events(
+    game_adapter: Res<GameAdapterResource>,
+    mut value_store: ResMut<ValueStore>,
+    mut race_events: EventWriter<RaceEvent>,
+    mut snapshot: Local<Option<ModelSnapshot>>,
+) {
+    let Some(model) = game_adapter.model() else {
+        *snapshot = None;
+        return;
+    };
+    let Some(session) = model.current_session() else {
+        *snapshot = None;
+        return;
+    };
+
+    let mut current = take_snapshot(session);
+
+    // A new session does not produce any events, only a new baseline.
+    // A session that is restarted keeps its id but its time goes back.
+    let is_same_session = |previous: &ModelSnapshot| {
+        previous.session_id == current.session_id && previous.session_time <= current.session_time
+    };
+    let Some(previous) = snapshot.take().filter(is_same_session) else {
+        value_store.race_event_log_mut().clear();
+        *snapshot = Some(current);
+        return;
+    };
+
+    let events = compare_snapshots(session, &previous, &current);
+    current.final_lap_started = previous.final_lap_started
+        || events
+            .iter()
+            .any(|event| matches!(event, RaceEvent::FinalLap { .. }));
+
+    for event in events {
+        value_store.race_event_log_mut().push(event.clone());
+        race_events.send(event);
+    }
+    *snapshot = Some(current);
+}
+
+fn take_snapshot(session: &Session) -> ModelSnapshot {
+    ModelSnapshot {
+        session_id: session.id,
+        session_time: session.session_time.ms,
+        entries: session
+            .entries
+            .values()
+            .map(|entry| (entry.id, EntrySnapshot::new(entry)))
+            .collect(),
+        best_lap: session
+            .best_lap
+            .as_ref()
+            .as_ref()
+            .map(|lap| (lap.entry_id, lap.time.ms as f32)),
+        leader: session
+            .entries
+            .values()
+            .min_by_key(|entry| *entry.position)
+            .map(|entry| entry.id),
+        final_lap_started: false,
+    }
+}
+
+fn compare_snapshots(
+    session: &Session,
+    previous: &ModelSnapshot,
+    current: &ModelSnapshot,
+) -> Vec<RaceEvent> {
+    let mut events = Vec::new();
+    let is_race = matches!(*session.session_type, SessionType::Race);
+
+    for (id, now) in current.entries.iter() {
+        let Some(before) = previous.entries.get(id) else {
+            continue;
+        };
+        if !before.in_pits && now.in_pits {
+            events.push(RaceEvent::PitEntry { entry: *id });
+        }
+        if before.in_pits && !now.in_pits {
+            events.push(RaceEvent::PitExit { entry: *id });
+        }
+        if before.connected && !now.connected {
+            events.push(RaceEvent::Retired { entry: *id });
+        }
+    }
+
+    // An overtake is when two entries swap their order. Position changes
+    // caused by pit stops are not overtakes.
+    if is_race {
+        for (id, now) in current.entries.iter() {
+            let Some(before) = previous.entries.get(id) else {
+                continue;
+            };
+            if now.position >= before.position || now.in_pits {
+                continue;
+            }
+            for (other_id, other_now) in current.entries.iter() {
+                let Some(other_before) = previous.entries.get(other_id) else {
+                    continue;
+                };
+                if other_before.position < before.position
+                    && other_now.position > now.position
+                    && !other_now.in_pits
+                {
+                    events.push(RaceEvent::Overtake {
+                        overtaking: *id,
+                        overtaken: *other_id,
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some((entry_id, time)) = current.best_lap {
+        let is_new = previous
+            .best_lap
+            .map_or(true, |(previous_id, previous_time)| {
+                previous_id != entry_id || previous_time != time
+            });
+        if let Some(entry) = entry_id.filter(|_| is_new) {
+            events.push(RaceEvent::FastestLap { entry });
+        }
+    }
+
+    if let (Some(previous_leader), Some(new_leader)) = (previous.leader, current.leader) {
+        if previous_leader != new_leader {
+            events.push(RaceEvent::LeaderChange {
+                new_leader,
+                previous_leader,
+            });
+        }
+    }
+
+    // The final lap starts when the leader crosses the line with one lap to go
+    // or after the time of a timed race has run out.
+    if is_race && !previous.final_lap_started {
+        if let Some(leader) = current.leader {
+            let crossed_line = current
+                .entries
+                .get(&leader)
+                .zip(previous.entries.get(&leader))
+                .is_some_and(|(now, before)| now.lap_count > before.lap_count);
+            let last_lap =
+                *session.laps_remaining == 1 || session.time_remaining.ms as f32 <= 0.0;
+            if crossed_line && last_lap {
+                events.push(RaceEvent::FinalLap { leader });
+            }
+        }
+    }
+
+    events
+}
+
+struct ActiveTrigger {
+    state: GraphicStateId,
+    previous_state: Option<GraphicStateId>,
+    until: Instant,
+}
+
+fn trigger_graphic_states(
+    mut race_events: EventReader<RaceEvent>,
+    savefile: Res<Savefile>,
+    mut graphic_states: ResMut<GraphicStates>,
+    mut active_triggers: Local<HashMap<StyleId, ActiveTrigger>>,
+) {
+    for event in race_events.read() {
+        for graphic in savefile.style().graphics.contained_graphics() {
+            let Some(trigger) = graphic
+                .triggers
+                .iter()
+                .find(|trigger| trigger.event == event.kind())
+            else {
+                continue;
+            };
+            // Keep the state from before the first trigger so it can be restored
+            // when triggers happen back to back.
+            let previous_state = match active_triggers.get(&graphic.id) {
+                Some(active) => active.previous_state,
+                None => graphic_states.states.get(&graphic.id).copied(),
+            };
+            graphic_states.states.insert(graphic.id, trigger.state);
+            active_triggers.insert(
+                graphic.id,
+                ActiveTrigger {
+                    state: trigger.state,
+                    previous_state,
+                    until: Instant::now() + Duration::from_secs_f32(trigger.duration.max(0.0)),
+                },
+            );
+        }
+    }
+
+    let now = Instant::now();
+    active_triggers.retain(|graphic_id, active| {
+        if active.until > now {
+            return true;
+        }
+        // Only restore the state if it was not changed by someone else in the meantime.
+        if graphic_states.states.get(graphic_id) == Some(&active.state) {
+            match active.previous_state {
+                Some(state) => graphic_states.states.insert(*graphic_id, state),
+                None => graphic_states.states.remove(graphic_id),
+            };
+        }
+        false
+    });
+}
@@ -40,6 +40,7 @@ pub struct SavefileChanged {
 #[derive(Resource)]
 pub struct Savefile {
     style: ExactVariant<StyleItem, StyleDefinition>,
+    path: PathBuf,
     base_path: PathBuf,
     working_directory_path: PathBuf,
 }
@@ -47,6 +48,7 @@ impl Default for Savefile {
     fn default() -> Self {
         Self {
             style: StyleDefinition::default().into(),
+            path: Default::default(),
             base_path: Default::default(),
             working_directory_path: Default::default(),
         }
@@ -95,6 +97,7 @@ impl Savefile {
         // });
         Savefile {
             style: style.into(),
+            path: path.as_ref().to_owned(),
             base_path: base_path.to_owned(),
             working_directory_path: working_directory_path.to_owned(),
         }
@@ -125,6 +128,11 @@ impl Savefile {
         &mut self.style
     }
 
+    /// The path of the style file as it was loaded.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
     pub fn base_path(&self) -> &Path {
         self.base_path.as_path()
     }
@@ -0,0 +1,221 @@
+//! Scenarios put the model of the game adapter into a specific state.
+//!
+//! A scenario is applied on top of the model every frame. Values that are not
+//! set by the scenario keep updating as usual. This makes it possible to
+//! reproduce situations like lapped cars or a leader in the pits while
+//! designing a style.
+
+use serde::{Deserialize, Serialize};
+use unified_sim_model::{
+    model::{Entry, EntryId, Model, SessionPhase, SessionType},
+    time::Time,
+};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Scenario {
+    pub id: Uuid,
+    pub name: String,
+    /// Amount of entries the dummy adapter should create for this scenario.
+    pub entry_amount: usize,
+    pub session: SessionOverride,
+    pub entries: Vec<EntryOverride>,
+}
+impl Scenario {
+    pub fn new() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: String::from("Scenario"),
+            entry_amount: 20,
+            session: SessionOverride::default(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Apply the scenario to the current session of the model.
+    pub fn apply(&self, model: &mut Model) {
+        let Some(session) = model.current_session_mut() else {
+            return;
+        };
+
+        if let Some(session_type) = self.session.session_type {
+            session.session_type.set(session_type.into());
+        }
+        if let Some(phase) = self.session.phase {
+            session.phase.set(phase.into());
+        }
+        if let Some(time_remaining) = self.session.time_remaining {
+            session.time_remaining.set(seconds(time_remaining));
+        }
+
+        for entry_override in self.entries.iter() {
+            // The car of another entry is used to change the class of an entry.
+            let car = entry_override.class.as_ref().and_then(|class| {
+                session
+                    .entries
+                    .values()
+                    .find(|entry| entry.car.category().name == class.as_str())
+                    .map(|entry| entry.car.clone())
+            });
+            let Some(entry) = session.entries.get_mut(&EntryId(entry_override.entry_id)) else {
+                continue;
+            };
+            if let Some(car) = car {
+                entry.car = car;
+            }
+            entry_override.apply(entry);
+        }
+
+        let positions_changed = self
+            .entries
+            .iter()
+            .any(|entry| entry.position.is_some() || entry.gap_to_leader.is_some());
+        if positions_changed {
+            self.update_positions(session.entries.values_mut().collect());
+        }
+    }
+
+    /// Give every entry a unique position after some entries were moved and
+    /// update the gaps to the position ahead.
+    fn update_positions(&self, mut entries: Vec<&mut Entry>) {
+        entries.sort_by_key(|entry| *entry.position);
+
+        // Move the entries with a fixed position to their place.
+        let mut moved: Vec<(usize, &mut Entry)> = Vec::new();
+        for entry_override in self.entries.iter() {
+            let Some(position) = entry_override.position else {
+                continue;
+            };
+            let Some(index) = entries
+                .iter()
+                .position(|entry| entry.id == EntryId(entry_override.entry_id))
+            else {
+                continue;
+            };
+            moved.push((position.max(1) as usize - 1, entries.remove(index)));
+        }
+        moved.sort_by_key(|(index, _)| *index);
+        for (index, entry) in moved {
+            let index = index.min(entries.len());
+            entries.insert(index, entry);
+        }
+
+        let mut gap_ahead = Time::from(0.0);
+        for (index, entry) in entries.into_iter().enumerate() {
+            entry.position.set(index as i32 + 1);
+            let gap = (*entry.time_behind_leader).clone();
+            entry
+                .time_behind_position_ahead
+                .set(Time::from((gap.ms - gap_ahead.ms).max(0.0)));
+            gap_ahead = gap;
+        }
+    }
+}
+
+/// Values of the session that are changed by a scenario.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SessionOverride {
+    pub session_type: Option<ScenarioSessionType>,
+    pub phase: Option<ScenarioSessionPhase>,
+    /// Time remaining in seconds.
+    pub time_remaining: Option<f64>,
+}
+
+/// Values of an entry that are changed by a scenario.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct EntryOverride {
+    /// Id of the entry that is changed.
+    pub entry_id: i32,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub car_number: Option<i32>,
+    /// The car category of the entry. The entry takes the car of another entry
+    /// in the same category.
+    pub class: Option<String>,
+    /// Position of the entry. Other entries move to make room.
+    pub position: Option<i32>,
+    /// Gap to the leader in seconds.
+    pub gap_to_leader: Option<f64>,
+    pub in_pits: Option<bool>,
+    pub lap_count: Option<i32>,
+    /// Best lap time in seconds.
+    pub best_lap: Option<f64>,
+    /// Time of the current lap in seconds.
+    pub current_lap: Option<f64>,
+}
+impl EntryOverride {
+    fn apply(&self, entry: &mut Entry) {
+        if let Some(driver) = entry.drivers.get_mut(&entry.current_driver) {
+            if let Some(first_name) = &self.first_name {
+                driver.first_name.set(first_name.clone());
+            }
+            if let Some(last_name) = &self.last_name {
+                driver.last_name.set(last_name.clone());
+            }
+        }
+        if let Some(car_number) = self.car_number {
+            entry.car_number.set(car_number);
+        }
+        if let Some(gap_to_leader) = self.gap_to_leader {
+            entry.time_behind_leader.set(seconds(gap_to_leader));
+        }
+        if let Some(in_pits) = self.in_pits {
+            entry.in_pits.set(in_pits);
+        }
+        if let Some(lap_count) = self.lap_count {
+            entry.lap_count.set(lap_count);
+        }
+        if let Some(best_lap) = self.best_lap {
+            let mut lap = (*entry.current_lap).clone();
+            lap.time.set(seconds(best_lap));
+            entry.best_lap.set(Some(lap));
+        }
+        if let Some(current_lap) = self.current_lap {
+            let mut lap = (*entry.current_lap).clone();
+            lap.time.set(seconds(current_lap));
+            entry.current_lap.set(lap);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ScenarioSessionType {
+    Race,
+    Qualifying,
+    Practice,
+}
+impl From<ScenarioSessionType> for SessionType {
+    fn from(value: ScenarioSessionType) -> Self {
+        match value {
+            ScenarioSessionType::Race => SessionType::Race,
+            ScenarioSessionType::Qualifying => SessionType::Qualifying,
+            ScenarioSessionType::Practice => SessionType::Practice,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ScenarioSessionPhase {
+    Waiting,
+    Preparing,
+    Formation,
+    Active,
+    Ending,
+    Finished,
+}
+impl From<ScenarioSessionPhase> for SessionPhase {
+    fn from(value: ScenarioSessionPhase) -> Self {
+        match value {
+            ScenarioSessionPhase::Waiting => SessionPhase::Waiting,
+            ScenarioSessionPhase::Preparing => SessionPhase::Preparing,
+            ScenarioSessionPhase::Formation => SessionPhase::Formation,
+            ScenarioSessionPhase::Active => SessionPhase::Active,
+            ScenarioSessionPhase::Ending => SessionPhase::Ending,
+            ScenarioSessionPhase::Finished => SessionPhase::Finished,
+        }
+    }
+}
+
+fn seconds(seconds: f64) -> Time {
+    Time::from(seconds * 1000.0)
+}
@@ -37,6 +37,10 @@ pub struct StyleDefinition {
     pub vars: Box<ExactVariant<StyleItem, VariableFolder>>,
     pub scene: Box<ExactVariant<StyleItem, SceneDefinition>>,
     pub graphics: Box<ExactVariant<StyleItem, GraphicFolder>>,
+    /// Graphics that are not shown on their own but are placed inside
+    /// other graphics as components.
+    #[serde(default = "default_components")]
+    pub components: Box<ExactVariant<StyleItem, GraphicFolder>>,
 }
 impl Default for StyleDefinition {
     fn default() -> Self {
@@ -46,10 +50,15 @@ impl Default for StyleDefinition {
             vars: Box::new(VariableFolder::new().into()),
             scene: Box::new(SceneDefinition::new().into()),
             graphics: Box::new(GraphicFolder::new().into()),
+            components: default_components(),
         }
     }
 }
 
+fn default_components() -> Box<ExactVariant<StyleItem, GraphicFolder>> {
+    Box::new(GraphicFolder::new_components().into())
+}
+
 #[derive(Serialize, Deserialize, Clone, Encapsulate)]
 #[serde(tag = "style_item_type")]
 pub enum StyleItem {
@@ -94,6 +103,7 @@ impl TreeIterator for StyleItem {
                 style.vars.walk(f)?;
                 style.scene.walk(f)?;
                 style.graphics.walk(f)?;
+                style.components.walk(f)?;
             }
             StyleItem::Variable(_) => (),
             StyleItem::VariableFolder(var_folder) => {
@@ -136,6 +146,7 @@ impl TreeIteratorMut for StyleItem {
                 style.vars.walk_mut(f)?;
                 style.scene.walk_mut(f)?;
                 style.graphics.walk_mut(f)?;
+                style.components.walk_mut(f)?;
             }
             StyleItem::Variable(_) => (),
             StyleItem::VariableFolder(var_folder) => {
@@ -4,7 +4,16 @@ use enumcapsulate::{VariantDiscriminant, VariantDowncast};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::exact_variant::ExactVariant;
+use crate::{
+    exact_variant::ExactVariant,
+    race_events::GraphicStateTrigger,
+    value_store::{
+        AnyValueProducer, ModelContext, ProducerId, ValueProducer, ValueResolver, ValueStore,
+    },
+    value_types::{
+        AnyProducerRef, Boolean, Font, Number, Property, Text, Texture, Tint, ValueType,
+    },
+};
 
 use graphic_items::{root::Root, ComputedGraphicItem, GraphicItem};
 
@@ -44,6 +53,11 @@ pub struct GraphicDefinition {
     pub name: String,
     pub items: ExactVariant<GraphicItem, Root>,
     pub states: Vec<GraphicState>,
+    #[serde(default)]
+    pub triggers: Vec<GraphicStateTrigger>,
+    /// Parameters that are exposed when the graphic is used as a component.
+    #[serde(default)]
+    pub parameters: Vec<ComponentParameter>,
 }
 impl GraphicDefinition {
     pub fn new() -> Self {
@@ -52,17 +66,104 @@ impl GraphicDefinition {
             name: String::from("Graphic"),
             items: Root::new().into(),
             states: Vec::new(),
+            triggers: Vec::new(),
+            parameters: Vec::new(),
         }
     }
 
     pub fn compute_style(&self, state: Option<&GraphicStateId>) -> ComputedGraphic {
         ComputedGraphic {
             graphic_id: self.id.0,
-            root: self.items.as_enum_ref().compute_for_state(state),
+            root: self
+                .items
+                .as_enum_ref()
+                .compute_for_state(state.map(std::slice::from_ref).unwrap_or_default()),
         }
     }
 }
 
+/// A value that a component exposes to its instances.
+///
+/// Items of the component reference the parameter like any other producer.
+/// Each instance of the component can override the default value.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ComponentParameter {
+    pub id: ProducerId,
+    pub name: String,
+    pub default: ParameterValue,
+}
+impl ComponentParameter {
+    pub fn new() -> Self {
+        Self {
+            id: ProducerId(Uuid::new_v4()),
+            name: String::from("Parameter"),
+            default: ParameterValue::Number(Property::Fixed(Number(0.0))),
+        }
+    }
+    pub fn producer_ref(&self) -> AnyProducerRef {
+        AnyProducerRef::new(self.id, self.default.value_type())
+    }
+}
+
+/// The value of a component parameter.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "value_type", content = "value")]
+pub enum ParameterValue {
+    Number(Property<Number>),
+    Text(Property<Text>),
+    Tint(Property<Tint>),
+    Boolean(Property<Boolean>),
+    Texture(Property<Texture>),
+    Font(Property<Font>),
+}
+impl ParameterValue {
+    /// Create the default value for a value type.
+    pub fn new(value_type: ValueType) -> Self {
+        match value_type {
+            ValueType::Number => ParameterValue::Number(Property::default()),
+            ValueType::Text => ParameterValue::Text(Property::default()),
+            ValueType::Tint => ParameterValue::Tint(Property::default()),
+            ValueType::Boolean => ParameterValue::Boolean(Property::default()),
+            ValueType::Texture => ParameterValue::Texture(Property::default()),
+            ValueType::Font => ParameterValue::Font(Property::default()),
+        }
+    }
+
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            ParameterValue::Number(_) => ValueType::Number,
+            ParameterValue::Text(_) => ValueType::Text,
+            ParameterValue::Tint(_) => ValueType::Tint,
+            ParameterValue::Boolean(_) => ValueType::Boolean,
+            ParameterValue::Texture(_) => ValueType::Texture,
+            ParameterValue::Font(_) => ValueType::Font,
+        }
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        match self.clone() {
+            ParameterValue::Number(p) => PropertyValueProducer(p).into(),
+            ParameterValue::Text(p) => PropertyValueProducer(p).into(),
+            ParameterValue::Tint(p) => PropertyValueProducer(p).into(),
+            ParameterValue::Boolean(p) => PropertyValueProducer(p).into(),
+            ParameterValue::Texture(p) => PropertyValueProducer(p).into(),
+            ParameterValue::Font(p) => PropertyValueProducer(p).into(),
+        }
+    }
+}
+
+/// Produces the value of a property.
+struct PropertyValueProducer<T>(Property<T>);
+impl<T: Clone> ValueProducer for PropertyValueProducer<T>
+where
+    ValueStore: ValueResolver<T>,
+{
+    type Output = T;
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<T> {
+        value_store.get_property(&self.0, context)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct GraphicFolder {
     pub id: StyleId,
@@ -77,6 +178,14 @@ impl GraphicFolder {
             content: Vec::new(),
         }
     }
+    /// The folder that holds the components of a style.
+    pub fn new_components() -> Self {
+        Self {
+            id: StyleId::new(),
+            name: String::from("Components"),
+            content: Vec::new(),
+        }
+    }
     pub fn contained_graphics(&self) -> Vec<&GraphicDefinition> {
         self.content
             .iter()
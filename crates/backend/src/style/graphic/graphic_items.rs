@@ -1,7 +1,11 @@
 pub mod cell;
 pub mod clip_area;
+pub mod component_instance;
 pub mod driver_table;
 pub mod entry_context;
+pub mod group;
+pub mod layout;
+pub mod repeater;
 pub mod root;
 
 use std::{
@@ -13,13 +17,20 @@ use enumcapsulate::macros::Encapsulate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::tree_iterator::{Method, TreeItem, TreeIterator, TreeIteratorMut};
+use crate::{
+    tree_iterator::{Method, TreeItem, TreeIterator, TreeIteratorMut},
+    value_types::{Number, Property, Vec2Property},
+};
 
 use self::{
     cell::{Cell, ComputedCell},
     clip_area::{ClipArea, ComputedClipArea},
+    component_instance::{ComponentInstance, ComputedComponentInstance},
     driver_table::{ComputedDriverTable, DriverTable},
     entry_context::{ComputedEntryContext, EntryContext},
+    group::{ComputedGroup, Group},
+    layout::{ComputedLayout, Layout},
+    repeater::{ComputedRepeater, Repeater},
     root::{ComputedRoot, Root},
 };
 
@@ -44,9 +55,13 @@ pub enum GraphicItem {
     ClipArea(ClipArea),
     DriverTable(DriverTable),
     EntryContext(EntryContext),
+    Repeater(Repeater),
+    Layout(Layout),
+    Group(Group),
+    ComponentInstance(ComponentInstance),
 }
 impl GraphicItem {
-    pub fn compute_for_state(&self, state: Option<&GraphicStateId>) -> ComputedGraphicItem {
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedGraphicItem {
         match self {
             GraphicItem::Root(o) => ComputedGraphicItem::Root(o.compute_for_state(state)),
             GraphicItem::Cell(o) => ComputedGraphicItem::Cell(o.compute_for_state(state)),
@@ -57,6 +72,12 @@ impl GraphicItem {
             GraphicItem::EntryContext(o) => {
                 ComputedGraphicItem::EntryContext(o.compute_for_state(state))
             }
+            GraphicItem::Repeater(o) => ComputedGraphicItem::Repeater(o.compute_for_state(state)),
+            GraphicItem::Layout(o) => ComputedGraphicItem::Layout(o.compute_for_state(state)),
+            GraphicItem::Group(o) => ComputedGraphicItem::Group(o.compute_for_state(state)),
+            GraphicItem::ComponentInstance(o) => {
+                ComputedGraphicItem::ComponentInstance(o.compute_for_state(state))
+            }
         }
     }
 }
@@ -71,6 +92,10 @@ impl TreeItem for GraphicItem {
             GraphicItem::ClipArea(clip_area) => clip_area.id,
             GraphicItem::DriverTable(driver_table) => driver_table.id,
             GraphicItem::EntryContext(entry_context) => entry_context.id,
+            GraphicItem::Repeater(repeater) => repeater.id,
+            GraphicItem::Layout(layout) => layout.id,
+            GraphicItem::Group(group) => group.id,
+            GraphicItem::ComponentInstance(instance) => instance.id,
         }
     }
 }
@@ -88,6 +113,7 @@ impl TreeIterator for GraphicItem {
                 root.items.iter().try_for_each(|e| e.walk(f))?;
             }
             GraphicItem::Cell(_) => (),
+            GraphicItem::ComponentInstance(_) => (),
             GraphicItem::ClipArea(clip_area) => {
                 clip_area.items.iter().try_for_each(|e| e.walk(f))?;
             }
@@ -97,6 +123,15 @@ impl TreeIterator for GraphicItem {
             GraphicItem::EntryContext(entry_context) => {
                 entry_context.items.iter().try_for_each(|e| e.walk(f))?;
             }
+            GraphicItem::Repeater(repeater) => {
+                repeater.items.iter().try_for_each(|e| e.walk(f))?;
+            }
+            GraphicItem::Layout(layout) => {
+                layout.items.iter().try_for_each(|e| e.walk(f))?;
+            }
+            GraphicItem::Group(group) => {
+                group.items.iter().try_for_each(|e| e.walk(f))?;
+            }
         }
         f(self, Method::Leave)
     }
@@ -115,6 +150,7 @@ impl TreeIteratorMut for GraphicItem {
                 root.items.iter_mut().try_for_each(|e| e.walk_mut(f))?;
             }
             GraphicItem::Cell(_) => (),
+            GraphicItem::ComponentInstance(_) => (),
             GraphicItem::ClipArea(clip_area) => {
                 clip_area.items.iter_mut().try_for_each(|e| e.walk_mut(f))?;
             }
@@ -130,10 +166,34 @@ impl TreeIteratorMut for GraphicItem {
                     .iter_mut()
                     .try_for_each(|e| e.walk_mut(f))?;
             }
+            GraphicItem::Repeater(repeater) => {
+                repeater.items.iter_mut().try_for_each(|e| e.walk_mut(f))?;
+            }
+            GraphicItem::Layout(layout) => {
+                layout.items.iter_mut().try_for_each(|e| e.walk_mut(f))?;
+            }
+            GraphicItem::Group(group) => {
+                group.items.iter_mut().try_for_each(|e| e.walk_mut(f))?;
+            }
         }
         f(self, Method::Leave)
     }
 }
+
+/// Default opacity of a graphic item. Items are fully opaque by default.
+fn default_opacity() -> Attribute<Property<Number>> {
+    Property::Fixed(Number(1.0)).into()
+}
+
+/// Default scale of a graphic item.
+fn default_scale() -> Attribute<Vec2Property> {
+    Vec2Property {
+        x: Property::Fixed(Number(1.0)),
+        y: Property::Fixed(Number(1.0)),
+    }
+    .into()
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Attribute<T> {
     template: T,
@@ -162,12 +222,15 @@ impl<T> Attribute<T> {
     pub fn has_state(&self, state_id: &GraphicStateId) -> bool {
         self.states.contains_key(&state_id)
     }
-    pub fn get_state_or_template(&self, state_id: Option<&GraphicStateId>) -> T
+    /// Get the value of the first state that this attribute has a value for.
+    /// States are given in order of priority. If no state has a value, the template is used.
+    pub fn get_state_or_template(&self, state_ids: &[GraphicStateId]) -> T
     where
         T: Clone,
     {
-        state_id
-            .and_then(|id| self.states.get(id))
+        state_ids
+            .iter()
+            .find_map(|id| self.states.get(id))
             .map(|maybe_state| maybe_state.clone())
             .unwrap_or_else(|| self.template.clone())
     }
@@ -200,4 +263,8 @@ pub enum ComputedGraphicItem {
     ClipArea(ComputedClipArea),
     DriverTable(ComputedDriverTable),
     EntryContext(ComputedEntryContext),
+    Repeater(ComputedRepeater),
+    Layout(ComputedLayout),
+    Group(ComputedGroup),
+    ComponentInstance(ComputedComponentInstance),
 }
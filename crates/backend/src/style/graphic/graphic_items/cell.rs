@@ -9,19 +9,35 @@ use crate::{
     },
 };
 
-use super::{Attribute, GraphicItemId};
+use super::{default_opacity, default_scale, Attribute, GraphicItemId};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Cell {
     pub id: GraphicItemId,
     pub name: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
     pub text: Attribute<Property<Text>>,
     pub text_color: Attribute<Property<Tint>>,
     pub text_size: Attribute<Property<Number>>,
     pub font: Attribute<Property<Font>>,
     pub color: Attribute<Property<Tint>>,
+    #[serde(default)]
+    pub gradient: Attribute<CellGradient>,
+    #[serde(default)]
+    pub border: Attribute<Border>,
+    #[serde(default)]
+    pub shadow: Attribute<Shadow>,
     pub image: Attribute<Property<Texture>>,
     pub pos: Attribute<Vec3Property>,
+    /// Rotation in degrees clockwise around the pivot.
+    #[serde(default)]
+    pub rotation: Attribute<Property<Number>>,
+    #[serde(default = "default_scale")]
+    pub scale: Attribute<Vec2Property>,
+    /// Point the item is rotated and scaled around. Relative to the position of the item.
+    #[serde(default)]
+    pub pivot: Attribute<Vec2Property>,
     pub size: Attribute<Vec2Property>,
     pub skew: Attribute<Property<Number>>,
     pub corner_offsets: Attribute<CornerOffsets>,
@@ -29,6 +45,27 @@ pub struct Cell {
     pub rounding: Attribute<Rounding>,
     pub text_alginment: Attribute<TextAlignment>,
     pub text_position: Attribute<Vec2Property>,
+    #[serde(default)]
+    pub text_fit: Attribute<TextFit>,
+    /// Space between the text and the edge of the cell when fitting text.
+    #[serde(default)]
+    pub text_padding: Attribute<Property<Number>>,
+    /// Break the text into multiple lines to fit the width of the cell.
+    #[serde(default)]
+    pub text_wrap: Attribute<bool>,
+    #[serde(default)]
+    pub text_overflow: Attribute<TextOverflow>,
+    /// Distance between two lines of text relative to the text size.
+    #[serde(default = "default_line_height")]
+    pub line_height: Attribute<Property<Number>>,
+    #[serde(default)]
+    pub text_outline: Attribute<TextOutline>,
+    #[serde(default)]
+    pub text_shadow: Attribute<TextShadow>,
+}
+
+fn default_line_height() -> Attribute<Property<Number>> {
+    Property::Fixed(Number(1.2)).into()
 }
 
 impl Cell {
@@ -36,17 +73,24 @@ impl Cell {
         Self {
             id: GraphicItemId::new(),
             name: String::from("Cell"),
+            opacity: default_opacity(),
             text: Property::Fixed(Text("Cell".to_string())).into(),
             text_color: Property::Fixed(Tint(Color::WHITE)).into(),
             text_size: Property::Fixed(Number(20.0)).into(),
             font: Property::Fixed(Font::Default).into(),
             color: Property::Fixed(Tint(Color::PURPLE)).into(),
+            gradient: CellGradient::None.into(),
+            border: Border::default().into(),
+            shadow: Shadow::default().into(),
             pos: Vec3Property {
                 x: Property::Fixed(Number(0.0)),
                 y: Property::Fixed(Number(0.0)),
                 z: Property::Fixed(Number(0.0)),
             }
             .into(),
+            rotation: Property::Fixed(Number(0.0)).into(),
+            scale: default_scale(),
+            pivot: Vec2Property::default().into(),
             size: Vec2Property {
                 x: Property::Fixed(Number(100.0)),
                 y: Property::Fixed(Number(100.0)),
@@ -69,19 +113,33 @@ impl Cell {
             }
             .into(),
             image: Property::<Texture>::default().into(),
+            text_fit: TextFit::None.into(),
+            text_padding: Property::Fixed(Number(5.0)).into(),
+            text_wrap: false.into(),
+            text_overflow: TextOverflow::Visible.into(),
+            line_height: default_line_height(),
+            text_outline: TextOutline::default().into(),
+            text_shadow: TextShadow::default().into(),
         }
     }
 
-    pub fn compute_for_state(&self, state: Option<&GraphicStateId>) -> ComputedCell {
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedCell {
         ComputedCell {
             id: self.id,
+            opacity: self.opacity.get_state_or_template(state),
             text: self.text.get_state_or_template(state),
             text_color: self.text_color.get_state_or_template(state),
             text_size: self.text_size.get_state_or_template(state),
             font: self.font.get_state_or_template(state),
             color: self.color.get_state_or_template(state),
+            gradient: self.gradient.get_state_or_template(state),
+            border: self.border.get_state_or_template(state),
+            shadow: self.shadow.get_state_or_template(state),
             image: self.image.get_state_or_template(state),
             pos: self.pos.get_state_or_template(state),
+            rotation: self.rotation.get_state_or_template(state),
+            scale: self.scale.get_state_or_template(state),
+            pivot: self.pivot.get_state_or_template(state),
             size: self.size.get_state_or_template(state),
             skew: self.skew.get_state_or_template(state),
             corner_offsets: self.corner_offsets.get_state_or_template(state),
@@ -91,10 +149,140 @@ impl Cell {
             rounding: self.rounding.get_state_or_template(state),
             text_alginment: self.text_alginment.get_state_or_template(state),
             text_position: self.text_position.get_state_or_template(state),
+            text_fit: self.text_fit.get_state_or_template(state),
+            text_padding: self.text_padding.get_state_or_template(state),
+            text_wrap: self.text_wrap.get_state_or_template(state),
+            text_overflow: self.text_overflow.get_state_or_template(state),
+            line_height: self.line_height.get_state_or_template(state),
+            text_outline: self.text_outline.get_state_or_template(state),
+            text_shadow: self.text_shadow.get_state_or_template(state),
+        }
+    }
+}
+
+/// A gradient from the background color of a cell to a second color.
+/// Positions are relative to the top left corner of the cell and angles are in degrees.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub enum CellGradient {
+    #[default]
+    None,
+    /// Blends along a line through the position. The spread is the width of the blend.
+    Linear {
+        color: Property<Tint>,
+        position: Vec2Property,
+        spread: Property<Number>,
+        angle: Property<Number>,
+    },
+    /// Blends outward from the position. The blend starts at the distance
+    /// from the position and ends after the spread.
+    Radial {
+        color: Property<Tint>,
+        position: Vec2Property,
+        spread: Property<Number>,
+        distance: Property<Number>,
+    },
+    /// Blends around the position.
+    Conical {
+        color: Property<Tint>,
+        position: Vec2Property,
+        angle: Property<Number>,
+    },
+}
+
+/// A border along the inside edge of a cell.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Border {
+    pub width: Property<Number>,
+    pub color: Property<Tint>,
+}
+impl Default for Border {
+    fn default() -> Self {
+        Self {
+            width: Property::Fixed(Number(0.0)),
+            color: Property::Fixed(Tint(Color::BLACK)),
+        }
+    }
+}
+
+/// A shadow behind a cell. The shadow is only drawn if its color is not transparent.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Shadow {
+    pub offset: Vec2Property,
+    /// Radius over which the edge of the shadow fades out.
+    pub blur: Property<Number>,
+    pub color: Property<Tint>,
+}
+impl Default for Shadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2Property {
+                x: Property::Fixed(Number(3.0)),
+                y: Property::Fixed(Number(3.0)),
+            },
+            blur: Property::Fixed(Number(5.0)),
+            color: Property::Fixed(Tint(Color::NONE)),
+        }
+    }
+}
+
+/// An outline around the text of a cell.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TextOutline {
+    pub width: Property<Number>,
+    pub color: Property<Tint>,
+}
+impl Default for TextOutline {
+    fn default() -> Self {
+        Self {
+            width: Property::Fixed(Number(0.0)),
+            color: Property::Fixed(Tint(Color::BLACK)),
+        }
+    }
+}
+
+/// A shadow behind the text of a cell. The shadow is only drawn if its color is not transparent.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TextShadow {
+    pub offset: Vec2Property,
+    pub color: Property<Tint>,
+}
+impl Default for TextShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2Property {
+                x: Property::Fixed(Number(2.0)),
+                y: Property::Fixed(Number(2.0)),
+            },
+            color: Property::Fixed(Tint(Color::NONE)),
         }
     }
 }
 
+/// How a cell and its text are fit to each other.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextFit {
+    #[default]
+    None,
+    /// Set the width of the cell to the width of the text plus padding.
+    /// The horizontal text position is set by the padding.
+    FitWidth,
+    /// Reduce the text size until the text fits inside the cell.
+    ShrinkText,
+}
+
+/// How text that does not fit inside a cell is handled.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// Text is drawn outside the cell.
+    #[default]
+    Visible,
+    /// Lines and characters that do not fit inside the cell are removed.
+    #[serde(alias = "Clip")]
+    Truncate,
+    /// Like truncate, but removed text is marked with an ellipsis.
+    Ellipsis,
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Rounding {
     pub top_left: Property<Number>,
@@ -113,13 +301,20 @@ pub struct CornerOffsets {
 
 pub struct ComputedCell {
     pub id: GraphicItemId,
+    pub opacity: Property<Number>,
     pub text: Property<Text>,
     pub text_color: Property<Tint>,
     pub text_size: Property<Number>,
     pub font: Property<Font>,
     pub color: Property<Tint>,
+    pub gradient: CellGradient,
+    pub border: Border,
+    pub shadow: Shadow,
     pub image: Property<Texture>,
     pub pos: Vec3Property,
+    pub rotation: Property<Number>,
+    pub scale: Vec2Property,
+    pub pivot: Vec2Property,
     pub size: Vec2Property,
     pub skew: Property<Number>,
     pub corner_offsets: CornerOffsets,
@@ -127,4 +322,11 @@ pub struct ComputedCell {
     pub rounding: Rounding,
     pub text_alginment: TextAlignment,
     pub text_position: Vec2Property,
+    pub text_fit: TextFit,
+    pub text_padding: Property<Number>,
+    pub text_wrap: bool,
+    pub text_overflow: TextOverflow,
+    pub line_height: Property<Number>,
+    pub text_outline: TextOutline,
+    pub text_shadow: TextShadow,
 }
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{style::graphic::GraphicStateId, value_types::{Number, Property, Vec2Property, Vec3Property}};
 
-use super::{cell::Rounding, Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
+use super::{cell::Rounding, default_opacity, default_scale, Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
 
 /// An item that restaints the contained elements
 /// to a sepcified area in the scene.
@@ -10,7 +10,17 @@ use super::{cell::Rounding, Attribute, ComputedGraphicItem, GraphicItem, Graphic
 pub struct ClipArea {
     pub id: GraphicItemId,
     pub name: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
     pub pos: Attribute<Vec3Property>,
+    /// Rotation in degrees clockwise around the pivot.
+    #[serde(default)]
+    pub rotation: Attribute<Property<Number>>,
+    #[serde(default = "default_scale")]
+    pub scale: Attribute<Vec2Property>,
+    /// Point the item is rotated and scaled around. Relative to the position of the item.
+    #[serde(default)]
+    pub pivot: Attribute<Vec2Property>,
     pub size: Attribute<Vec2Property>,
     pub skew: Attribute<Property<Number>>,
     pub rounding: Attribute<Rounding>,
@@ -22,7 +32,11 @@ impl ClipArea {
         Self {
             id: GraphicItemId::new(),
             name: String::from("Clip area"),
+            opacity: default_opacity(),
             pos: Vec3Property::default().into(),
+            rotation: Property::Fixed(Number(0.0)).into(),
+            scale: default_scale(),
+            pivot: Vec2Property::default().into(),
             size: Vec2Property {
                 x: Property::Fixed(Number(100.0)),
                 y: Property::Fixed(Number(100.0)),
@@ -34,10 +48,14 @@ impl ClipArea {
             items: Vec::new(),
         }
     }
-    pub fn compute_for_state(&self, state: Option<&GraphicStateId>) -> ComputedClipArea {
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedClipArea {
         ComputedClipArea {
             id: self.id,
+            opacity: self.opacity.get_state_or_template(state),
             pos: self.pos.get_state_or_template(state),
+            rotation: self.rotation.get_state_or_template(state),
+            scale: self.scale.get_state_or_template(state),
+            pivot: self.pivot.get_state_or_template(state),
             size: self.size.get_state_or_template(state),
             skew: self.skew.get_state_or_template(state),
             rounding: self.rounding.get_state_or_template(state),
@@ -53,7 +71,11 @@ impl ClipArea {
 
 pub struct ComputedClipArea {
     pub id: GraphicItemId,
+    pub opacity: Property<Number>,
     pub pos: Vec3Property,
+    pub rotation: Property<Number>,
+    pub scale: Vec2Property,
+    pub pivot: Vec2Property,
     pub size: Vec2Property,
     pub skew: Property<Number>,
     pub rounding: Rounding,
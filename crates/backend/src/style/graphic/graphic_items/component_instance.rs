@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    style::{
+        graphic::{GraphicStateId, ParameterValue, GRAPHIC_STATE_HIDDEN},
+        StyleId,
+    },
+    value_store::ProducerId,
+    value_types::{Boolean, Number, Property, Vec2Property},
+};
+
+use super::{default_opacity, default_scale, Attribute, GraphicItemId};
+
+/// An item that places a component into the graphic.
+///
+/// The items of the component are drawn as if they were children of the instance.
+/// Parameters of the component that are not overridden use their default value.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ComponentInstance {
+    pub id: GraphicItemId,
+    pub name: String,
+    pub component: Option<StyleId>,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
+    pub visible: Attribute<Property<Boolean>>,
+    pub position: Attribute<Vec2Property>,
+    /// Rotation in degrees clockwise around the pivot.
+    #[serde(default)]
+    pub rotation: Attribute<Property<Number>>,
+    #[serde(default = "default_scale")]
+    pub scale: Attribute<Vec2Property>,
+    /// Point the item is rotated and scaled around. Relative to the position of the item.
+    #[serde(default)]
+    pub pivot: Attribute<Vec2Property>,
+    /// Values of the component parameters that are overridden by this instance.
+    #[serde(default)]
+    pub parameters: HashMap<ProducerId, Attribute<ParameterValue>>,
+}
+
+impl ComponentInstance {
+    pub fn new() -> Self {
+        Self {
+            id: GraphicItemId::new(),
+            name: String::from("Component"),
+            component: None,
+            opacity: default_opacity(),
+            visible: Property::Fixed(Boolean(true)).into(),
+            position: Vec2Property::default().into(),
+            rotation: Property::Fixed(Number(0.0)).into(),
+            scale: default_scale(),
+            pivot: Vec2Property::default().into(),
+            parameters: HashMap::new(),
+        }
+    }
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedComponentInstance {
+        ComputedComponentInstance {
+            id: self.id,
+            component: self.component,
+            opacity: self.opacity.get_state_or_template(state),
+            visible: (state.is_some_and(|state| state == &GRAPHIC_STATE_HIDDEN))
+                .then_some(Property::Fixed(Boolean(false)))
+                .unwrap_or(self.visible.get_state_or_template(state)),
+            position: self.position.get_state_or_template(state),
+            rotation: self.rotation.get_state_or_template(state),
+            scale: self.scale.get_state_or_template(state),
+            pivot: self.pivot.get_state_or_template(state),
+            parameters: self
+                .parameters
+                .iter()
+                .map(|(id, value)| (*id, value.get_state_or_template(state)))
+                .collect(),
+        }
+    }
+}
+
+pub struct ComputedComponentInstance {
+    pub id: GraphicItemId,
+    pub component: Option<StyleId>,
+    pub opacity: Property<Number>,
+    pub visible: Property<Boolean>,
+    pub position: Vec2Property,
+    pub rotation: Property<Number>,
+    pub scale: Vec2Property,
+    pub pivot: Vec2Property,
+    pub parameters: HashMap<ProducerId, ParameterValue>,
+}
@@ -2,19 +2,51 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     style::graphic::GraphicStateId,
-    value_types::{Number, Property, Vec2Property},
+    value_types::{AnyProducerRef, Boolean, Number, ProducerRef, Property, Vec2Property},
 };
 
-use super::{Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
+use super::{default_opacity, Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
 
 // An item that displays a table of all drivers in the session.
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct DriverTable {
     pub id: GraphicItemId,
     pub name: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
     #[serde(default)]
     pub position: Attribute<Vec2Property>,
     pub row_offset: Attribute<Vec2Property>,
+    /// Number of rows that are visible. 0 shows all rows.
+    #[serde(default = "default_visible_rows")]
+    pub visible_rows: Attribute<Property<Number>>,
+    /// Number of rows at the top that are always visible.
+    #[serde(default)]
+    pub pinned_rows: Attribute<Property<Number>>,
+    #[serde(default)]
+    pub scroll_mode: Attribute<ScrollMode>,
+    /// Time in seconds between pages when scroll mode is paged.
+    #[serde(default = "default_page_interval")]
+    pub page_interval: Attribute<Property<Number>>,
+    /// Time constant in seconds of the scroll movement.
+    #[serde(default = "default_scroll_smoothing")]
+    pub scroll_smoothing: Attribute<Property<Number>>,
+    /// Producer to sort the entries by. Entries are sorted by position if not set.
+    #[serde(default)]
+    pub sort_key: Attribute<Option<AnyProducerRef>>,
+    #[serde(default)]
+    pub sort_order: Attribute<SortOrder>,
+    /// Only entries for which this producer is true are shown.
+    #[serde(default)]
+    pub filter: Attribute<Option<ProducerRef<Boolean>>>,
+    /// Time in seconds it takes a row to move to a new position.
+    #[serde(default)]
+    pub reorder_duration: Attribute<Property<Number>>,
+    #[serde(default)]
+    pub reorder_easing: Attribute<Easing>,
+    /// State the columns of a row are shown in while the row is moving.
+    #[serde(default)]
+    pub moving_state: Option<GraphicStateId>,
     pub columns: Vec<GraphicItem>,
 }
 impl DriverTable {
@@ -22,11 +54,23 @@ impl DriverTable {
         Self {
             id: GraphicItemId::new(),
             name: String::from("Driver table"),
+            opacity: default_opacity(),
             row_offset: Vec2Property {
                 x: Property::Fixed(Number(30.0)),
                 y: Property::Fixed(Number(30.0)),
             }
             .into(),
+            visible_rows: default_visible_rows(),
+            pinned_rows: Property::Fixed(Number(0.0)).into(),
+            scroll_mode: ScrollMode::FollowFocus.into(),
+            page_interval: default_page_interval(),
+            scroll_smoothing: default_scroll_smoothing(),
+            sort_key: None.into(),
+            sort_order: SortOrder::Ascending.into(),
+            filter: None.into(),
+            reorder_duration: Property::Fixed(Number(0.5)).into(),
+            reorder_easing: Easing::EaseInOut.into(),
+            moving_state: None,
             columns: Vec::new(),
             position: Vec2Property {
                 x: Property::Fixed(Number(0.0)),
@@ -35,23 +79,117 @@ impl DriverTable {
             .into(),
         }
     }
-    pub fn compute_for_state(&self, state: Option<&GraphicStateId>) -> ComputedDriverTable {
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedDriverTable {
         ComputedDriverTable {
             id: self.id,
+            opacity: self.opacity.get_state_or_template(state),
             position: self.position.get_state_or_template(state),
             row_offset: self.row_offset.get_state_or_template(state),
+            visible_rows: self.visible_rows.get_state_or_template(state),
+            pinned_rows: self.pinned_rows.get_state_or_template(state),
+            scroll_mode: self.scroll_mode.get_state_or_template(state),
+            page_interval: self.page_interval.get_state_or_template(state),
+            scroll_smoothing: self.scroll_smoothing.get_state_or_template(state),
+            sort_key: self.sort_key.get_state_or_template(state),
+            sort_order: self.sort_order.get_state_or_template(state),
+            filter: self.filter.get_state_or_template(state),
+            reorder_duration: self.reorder_duration.get_state_or_template(state),
+            reorder_easing: self.reorder_easing.get_state_or_template(state),
             columns: self
                 .columns
                 .iter()
                 .map(|item| item.compute_for_state(state))
                 .collect(),
+            // The moving state is layered on top of the active state.
+            moving_columns: self.moving_state.map(|moving_state| {
+                let states: Vec<GraphicStateId> = std::iter::once(moving_state)
+                    .chain(state.iter().copied())
+                    .collect();
+                self.columns
+                    .iter()
+                    .map(|item| item.compute_for_state(&states))
+                    .collect()
+            }),
         }
     }
 }
 
 pub struct ComputedDriverTable {
     pub id: GraphicItemId,
+    pub opacity: Property<Number>,
     pub position: Vec2Property,
     pub row_offset: Vec2Property,
+    pub visible_rows: Property<Number>,
+    pub pinned_rows: Property<Number>,
+    pub scroll_mode: ScrollMode,
+    pub page_interval: Property<Number>,
+    pub scroll_smoothing: Property<Number>,
+    pub sort_key: Option<AnyProducerRef>,
+    pub sort_order: SortOrder,
+    pub filter: Option<ProducerRef<Boolean>>,
+    pub reorder_duration: Property<Number>,
+    pub reorder_easing: Easing,
     pub columns: Vec<ComputedGraphicItem>,
+    /// Columns computed for the moving state if there is one.
+    pub moving_columns: Option<Vec<ComputedGraphicItem>>,
+}
+
+/// Easing function of an animation.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+impl Easing {
+    /// Apply the easing to the progress `t` of an animation in the range 0 to 1.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// How the rows of a driver table are scrolled when there are
+/// more rows than visible rows.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScrollMode {
+    /// Scroll to keep the focused entry in the middle of the table.
+    #[default]
+    FollowFocus,
+    /// Do not scroll.
+    Fixed,
+    /// Show one page of rows after another.
+    Paged,
+}
+
+fn default_visible_rows() -> Attribute<Property<Number>> {
+    Property::Fixed(Number(23.0)).into()
+}
+
+fn default_page_interval() -> Attribute<Property<Number>> {
+    Property::Fixed(Number(10.0)).into()
+}
+
+fn default_scroll_smoothing() -> Attribute<Property<Number>> {
+    Property::Fixed(Number(0.075)).into()
 }
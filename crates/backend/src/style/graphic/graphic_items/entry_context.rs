@@ -1,15 +1,20 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::style::graphic::GraphicStateId;
+use crate::{
+    style::graphic::GraphicStateId,
+    value_types::{Number, ProducerRef, Property},
+};
 
-use super::{Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
+use super::{default_opacity, Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
 
 // An item that creates a context around an entry.
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct EntryContext {
     pub id: GraphicItemId,
     pub name: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
     pub selection: Attribute<EntrySelection>,
     pub items: Vec<GraphicItem>,
 }
@@ -19,13 +24,15 @@ impl EntryContext {
         Self {
             id: GraphicItemId(Uuid::new_v4()),
             name: String::from("Entry context"),
+            opacity: default_opacity(),
             selection: EntrySelection::Focus.into(),
             items: Vec::new(),
         }
     }
-    pub fn compute_for_state(&self, state: Option<&GraphicStateId>) -> ComputedEntryContext {
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedEntryContext {
         ComputedEntryContext {
             id: self.id,
+            opacity: self.opacity.get_state_or_template(state),
             selection: self.selection.get_state_or_template(state),
             items: self
                 .items
@@ -36,7 +43,9 @@ impl EntryContext {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+/// Selects the entry of an entry context.
+/// If no entry matches the selection the children of the context are hidden.
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub enum EntrySelection {
     First,
     Second,
@@ -45,10 +54,20 @@ pub enum EntrySelection {
     #[default]
     Focus,
     BehindFocus,
+    /// The entry in the n-th position, starting at 1.
+    Position(Property<Number>),
+    /// The entry n positions behind the focused entry.
+    /// Negative values select entries ahead of the focused entry.
+    FocusOffset(Property<Number>),
+    CarNumber(Property<Number>),
+    EntryId(i32),
+    /// The entry with the id produced by a producer.
+    Producer(ProducerRef<Number>),
 }
 
 pub struct ComputedEntryContext {
     pub id: GraphicItemId,
+    pub opacity: Property<Number>,
     pub selection: EntrySelection,
     pub items: Vec<ComputedGraphicItem>,
 }
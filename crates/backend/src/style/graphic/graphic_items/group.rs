@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    style::graphic::{GraphicStateId, GRAPHIC_STATE_HIDDEN},
+    value_types::{Boolean, Number, Property, Vec2Property},
+};
+
+use super::{
+    default_opacity, default_scale, Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId,
+};
+
+/// An item that moves, scales and fades its children together.
+///
+/// Unlike a clip area the children are drawn directly into the scene
+/// and are not restricted to an area.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Group {
+    pub id: GraphicItemId,
+    pub name: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
+    pub visible: Attribute<Property<Boolean>>,
+    pub position: Attribute<Vec2Property>,
+    /// Rotation in degrees clockwise around the pivot.
+    #[serde(default)]
+    pub rotation: Attribute<Property<Number>>,
+    #[serde(default = "default_scale")]
+    pub scale: Attribute<Vec2Property>,
+    /// Point the item is rotated and scaled around. Relative to the position of the item.
+    #[serde(default)]
+    pub pivot: Attribute<Vec2Property>,
+    pub items: Vec<GraphicItem>,
+}
+
+impl Group {
+    pub fn new() -> Self {
+        Self {
+            id: GraphicItemId::new(),
+            name: String::from("Group"),
+            opacity: default_opacity(),
+            visible: Property::Fixed(Boolean(true)).into(),
+            position: Vec2Property::default().into(),
+            rotation: Property::Fixed(Number(0.0)).into(),
+            scale: default_scale(),
+            pivot: Vec2Property::default().into(),
+            items: Vec::new(),
+        }
+    }
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedGroup {
+        ComputedGroup {
+            id: self.id,
+            opacity: self.opacity.get_state_or_template(state),
+            visible: (state.is_some_and(|state| state == &GRAPHIC_STATE_HIDDEN))
+                .then_some(Property::Fixed(Boolean(false)))
+                .unwrap_or(self.visible.get_state_or_template(state)),
+            position: self.position.get_state_or_template(state),
+            rotation: self.rotation.get_state_or_template(state),
+            scale: self.scale.get_state_or_template(state),
+            pivot: self.pivot.get_state_or_template(state),
+            items: self
+                .items
+                .iter()
+                .map(|item| item.compute_for_state(state))
+                .collect(),
+        }
+    }
+}
+
+pub struct ComputedGroup {
+    pub id: GraphicItemId,
+    pub opacity: Property<Number>,
+    pub visible: Property<Boolean>,
+    pub position: Vec2Property,
+    pub rotation: Property<Number>,
+    pub scale: Vec2Property,
+    pub pivot: Vec2Property,
+    pub items: Vec<ComputedGraphicItem>,
+}
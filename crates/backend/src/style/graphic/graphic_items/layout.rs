@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    style::graphic::GraphicStateId,
+    value_types::{Number, Property, Vec2Property},
+};
+
+use super::{default_opacity, Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
+
+/// An item that places its children next to each other.
+///
+/// The position of a child is added as an offset to its place in the layout.
+/// Invisible children are skipped and take up no space.
+///
+/// A group takes up the space from its origin to the far corner of its children.
+/// The rotation of a group is not taken into account.
+/// Driver tables, entry contexts, repeaters and component instances have no size
+/// in a layout. They are placed at the current position and take up no space.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Layout {
+    pub id: GraphicItemId,
+    pub name: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
+    pub position: Attribute<Vec2Property>,
+    pub direction: Attribute<LayoutDirection>,
+    pub spacing: Attribute<Property<Number>>,
+    /// Alignment of the children across the layout direction.
+    pub alignment: Attribute<LayoutAlignment>,
+    /// Alignment of the children along the layout direction.
+    /// Only has an effect if the layout is not auto sized.
+    pub justify: Attribute<LayoutAlignment>,
+    /// Size the layout to fit its children instead of using a fixed size.
+    pub auto_size: Attribute<bool>,
+    pub size: Attribute<Vec2Property>,
+    pub items: Vec<GraphicItem>,
+}
+
+impl Layout {
+    pub fn new() -> Self {
+        Self {
+            id: GraphicItemId::new(),
+            name: String::from("Layout"),
+            opacity: default_opacity(),
+            position: Vec2Property::default().into(),
+            direction: LayoutDirection::Horizontal.into(),
+            spacing: Property::Fixed(Number(0.0)).into(),
+            alignment: LayoutAlignment::Start.into(),
+            justify: LayoutAlignment::Start.into(),
+            auto_size: true.into(),
+            size: Vec2Property {
+                x: Property::Fixed(Number(100.0)),
+                y: Property::Fixed(Number(100.0)),
+            }
+            .into(),
+            items: Vec::new(),
+        }
+    }
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedLayout {
+        ComputedLayout {
+            id: self.id,
+            opacity: self.opacity.get_state_or_template(state),
+            position: self.position.get_state_or_template(state),
+            direction: self.direction.get_state_or_template(state),
+            spacing: self.spacing.get_state_or_template(state),
+            alignment: self.alignment.get_state_or_template(state),
+            justify: self.justify.get_state_or_template(state),
+            auto_size: self.auto_size.get_state_or_template(state),
+            size: self.size.get_state_or_template(state),
+            items: self
+                .items
+                .iter()
+                .map(|item| item.compute_for_state(state))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayoutDirection {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayoutAlignment {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+impl LayoutAlignment {
+    /// Offset of an item inside the available space.
+    pub fn offset(&self, available: f32, item: f32) -> f32 {
+        match self {
+            LayoutAlignment::Start => 0.0,
+            LayoutAlignment::Center => (available - item) / 2.0,
+            LayoutAlignment::End => available - item,
+        }
+    }
+}
+
+pub struct ComputedLayout {
+    pub id: GraphicItemId,
+    pub opacity: Property<Number>,
+    pub position: Vec2Property,
+    pub direction: LayoutDirection,
+    pub spacing: Property<Number>,
+    pub alignment: LayoutAlignment,
+    pub justify: LayoutAlignment,
+    pub auto_size: bool,
+    pub size: Vec2Property,
+    pub items: Vec<ComputedGraphicItem>,
+}
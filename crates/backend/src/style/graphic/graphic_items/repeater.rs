@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    style::graphic::GraphicStateId,
+    value_types::{Boolean, Number, ProducerRef, Property, Vec2Property},
+};
+
+use super::{default_opacity, Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
+
+/// An item that repeats its children for every element of a list.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Repeater {
+    pub id: GraphicItemId,
+    pub name: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
+    pub position: Attribute<Vec2Property>,
+    pub source: Attribute<RepeaterSource>,
+    pub layout: Attribute<RepeaterLayout>,
+    /// Offset between two items. In a flow layout the x offset is the gap
+    /// between the end of one item and the start of the next.
+    pub item_offset: Attribute<Vec2Property>,
+    pub items: Vec<GraphicItem>,
+}
+
+impl Repeater {
+    pub fn new() -> Self {
+        Self {
+            id: GraphicItemId(Uuid::new_v4()),
+            name: String::from("Repeater"),
+            opacity: default_opacity(),
+            position: Vec2Property::default().into(),
+            source: RepeaterSource::Count(Property::Fixed(Number(3.0))).into(),
+            layout: RepeaterLayout::Grid {
+                columns: Property::Fixed(Number(1.0)),
+            }
+            .into(),
+            item_offset: Vec2Property {
+                x: Property::Fixed(Number(0.0)),
+                y: Property::Fixed(Number(30.0)),
+            }
+            .into(),
+            items: Vec::new(),
+        }
+    }
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedRepeater {
+        ComputedRepeater {
+            id: self.id,
+            opacity: self.opacity.get_state_or_template(state),
+            position: self.position.get_state_or_template(state),
+            source: self.source.get_state_or_template(state),
+            layout: self.layout.get_state_or_template(state),
+            item_offset: self.item_offset.get_state_or_template(state),
+            items: self
+                .items
+                .iter()
+                .map(|item| item.compute_for_state(state))
+                .collect(),
+        }
+    }
+}
+
+/// The list a repeater repeats its children over.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum RepeaterSource {
+    /// Repeat a number of times.
+    Count(Property<Number>),
+    /// Repeat once for every entry that matches the filter, in position order.
+    Entries(Option<ProducerRef<Boolean>>),
+    /// Repeat once for each of the last n laps of the current entry, oldest first.
+    Laps(Property<Number>),
+    /// Repeat once for each completed sector of the repeated lap, or of the
+    /// current lap of the entry outside of a lap repeater.
+    Sectors,
+}
+impl Default for RepeaterSource {
+    fn default() -> Self {
+        RepeaterSource::Count(Property::Fixed(Number(0.0)))
+    }
+}
+
+/// How the items of a repeater are placed.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum RepeaterLayout {
+    /// Place the items in a grid with a number of columns.
+    /// With 0 columns all items are placed in a single row.
+    Grid { columns: Property<Number> },
+    /// Place the items next to each other and start a new row when
+    /// the maximum width is exceeded. The item width is resolved for each item.
+    Flow {
+        item_width: Property<Number>,
+        max_width: Property<Number>,
+    },
+}
+impl Default for RepeaterLayout {
+    fn default() -> Self {
+        RepeaterLayout::Grid {
+            columns: Property::Fixed(Number(1.0)),
+        }
+    }
+}
+
+pub struct ComputedRepeater {
+    pub id: GraphicItemId,
+    pub opacity: Property<Number>,
+    pub position: Vec2Property,
+    pub source: RepeaterSource,
+    pub layout: RepeaterLayout,
+    pub item_offset: Vec2Property,
+    pub items: Vec<ComputedGraphicItem>,
+}
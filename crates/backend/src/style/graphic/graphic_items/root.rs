@@ -1,15 +1,30 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{style::graphic::GraphicStateId, value_types::Vec2Property};
+use crate::{
+    style::graphic::GraphicStateId,
+    value_types::{Number, Property, Vec2Property},
+};
 
-use super::{Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId};
+use super::{
+    default_opacity, default_scale, Attribute, ComputedGraphicItem, GraphicItem, GraphicItemId,
+};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Root {
     pub id: GraphicItemId,
     pub name: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: Attribute<Property<Number>>,
     pub items: Vec<GraphicItem>,
     pub position: Attribute<Vec2Property>,
+    /// Rotation in degrees clockwise around the pivot.
+    #[serde(default)]
+    pub rotation: Attribute<Property<Number>>,
+    #[serde(default = "default_scale")]
+    pub scale: Attribute<Vec2Property>,
+    /// Point the item is rotated and scaled around. Relative to the position of the item.
+    #[serde(default)]
+    pub pivot: Attribute<Vec2Property>,
 }
 
 impl Root {
@@ -17,15 +32,23 @@ impl Root {
         Self {
             id: GraphicItemId::new(),
             name: String::from("Graphic"),
+            opacity: default_opacity(),
             items: Vec::new(),
             position: Vec2Property::default().into(),
+            rotation: Property::Fixed(Number(0.0)).into(),
+            scale: default_scale(),
+            pivot: Vec2Property::default().into(),
         }
     }
 
-    pub fn compute_for_state(&self, state: Option<&GraphicStateId>) -> ComputedRoot {
+    pub fn compute_for_state(&self, state: &[GraphicStateId]) -> ComputedRoot {
         ComputedRoot {
             id: self.id,
+            opacity: self.opacity.get_state_or_template(state),
             position: self.position.get_state_or_template(state),
+            rotation: self.rotation.get_state_or_template(state),
+            scale: self.scale.get_state_or_template(state),
+            pivot: self.pivot.get_state_or_template(state),
             items: self
                 .items
                 .iter()
@@ -49,6 +72,10 @@ impl ToString for RootAttributes {
 
 pub struct ComputedRoot {
     pub id: GraphicItemId,
+    pub opacity: Property<Number>,
     pub position: Vec2Property,
+    pub rotation: Property<Number>,
+    pub scale: Vec2Property,
+    pub pivot: Vec2Property,
     pub items: Vec<ComputedGraphicItem>,
 }
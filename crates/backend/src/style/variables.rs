@@ -8,13 +8,27 @@ use crate::{
 use enumcapsulate::{VariantDiscriminant, VariantDowncast};
 use serde::{Deserialize, Serialize};
 
-use self::{condition::Condition, fixed_value::FixedValue, map::Map};
+use self::{
+    condition::Condition, debounce::Debounce, delta::Delta, fixed_value::FixedValue,
+    format::Format, gap::Gap, hold::Hold, map::Map, position_change::PositionChange,
+    smooth::Smooth, stopwatch::Stopwatch, text_width::TextWidth,
+};
 
 use super::{StyleId, StyleItem, StyleItemDiscriminant, TreePosition};
 
 pub mod condition;
+pub mod debounce;
+pub mod delta;
+pub mod entry_state;
 pub mod fixed_value;
+pub mod format;
+pub mod gap;
+pub mod hold;
 pub mod map;
+pub mod position_change;
+pub mod smooth;
+pub mod stopwatch;
+pub mod text_width;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct VariableDefinition {
@@ -30,6 +44,15 @@ pub enum VariableBehavior {
     FixedValue(FixedValue),
     Condition(Condition),
     Map(Map),
+    Hold(Hold),
+    Debounce(Debounce),
+    Stopwatch(Stopwatch),
+    Delta(Delta),
+    Smooth(Smooth),
+    Format(Format),
+    Gap(Gap),
+    TextWidth(TextWidth),
+    PositionChange(PositionChange),
 }
 
 impl VariableDefinition {
@@ -45,6 +68,15 @@ impl VariableDefinition {
             VariableBehavior::FixedValue(o) => o.as_typed_producer(),
             VariableBehavior::Condition(o) => o.as_typed_producer(),
             VariableBehavior::Map(o) => o.as_typed_producer(),
+            VariableBehavior::Hold(o) => o.as_typed_producer(),
+            VariableBehavior::Debounce(o) => o.as_typed_producer(),
+            VariableBehavior::Stopwatch(o) => o.as_typed_producer(),
+            VariableBehavior::Delta(o) => o.as_typed_producer(),
+            VariableBehavior::Smooth(o) => o.as_typed_producer(),
+            VariableBehavior::Format(o) => o.as_typed_producer(),
+            VariableBehavior::Gap(o) => o.as_typed_producer(),
+            VariableBehavior::TextWidth(o) => o.as_typed_producer(),
+            VariableBehavior::PositionChange(o) => o.as_typed_producer(),
         }
     }
     pub fn value_id(&self) -> ProducerId {
@@ -57,6 +89,15 @@ impl VariableDefinition {
                 VariableBehavior::FixedValue(o) => o.output_type(),
                 VariableBehavior::Condition(o) => o.output_type(),
                 VariableBehavior::Map(o) => o.output_type(),
+                VariableBehavior::Hold(o) => o.output_type(),
+                VariableBehavior::Debounce(o) => o.output_type(),
+                VariableBehavior::Stopwatch(o) => o.output_type(),
+                VariableBehavior::Delta(o) => o.output_type(),
+                VariableBehavior::Smooth(o) => o.output_type(),
+                VariableBehavior::Format(o) => o.output_type(),
+                VariableBehavior::Gap(o) => o.output_type(),
+                VariableBehavior::TextWidth(o) => o.output_type(),
+                VariableBehavior::PositionChange(o) => o.output_type(),
             },
         )
     }
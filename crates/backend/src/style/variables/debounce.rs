@@ -0,0 +1,76 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{Boolean, ProducerRef, ValueType},
+};
+
+use super::entry_state::EntryStateStorage;
+
+/// Follows the input but only changes once the input has kept
+/// its new value for the delay time.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Debounce {
+    pub input: ProducerRef<Boolean>,
+    /// Delay in seconds.
+    pub delay: f32,
+}
+
+impl Default for Debounce {
+    fn default() -> Self {
+        Self {
+            input: ProducerRef::default(),
+            delay: 1.0,
+        }
+    }
+}
+
+impl Debounce {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Boolean
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        DebounceProducer {
+            input: self.input.clone(),
+            delay: Duration::from_secs_f32(self.delay.max(0.0)),
+            states: EntryStateStorage::default(),
+        }
+        .into()
+    }
+}
+
+#[derive(Default)]
+struct DebounceState {
+    output: Option<bool>,
+    pending_since: Option<Instant>,
+}
+
+struct DebounceProducer {
+    input: ProducerRef<Boolean>,
+    delay: Duration,
+    states: EntryStateStorage<DebounceState>,
+}
+impl ValueProducer for DebounceProducer {
+    type Output = Boolean;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let input = value_store.get(&self.input, context)?.0;
+        let now = Instant::now();
+        self.states.with_state(context, |state| {
+            let output = *state.output.get_or_insert(input);
+            if input == output {
+                state.pending_since = None;
+            } else {
+                let pending_since = *state.pending_since.get_or_insert(now);
+                if now.duration_since(pending_since) >= self.delay {
+                    state.output = Some(input);
+                    state.pending_since = None;
+                }
+            }
+            state.output.map(Boolean)
+        })
+    }
+}
@@ -0,0 +1,103 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{Number, ProducerRef, ValueType},
+};
+
+use super::entry_state::EntryStateStorage;
+
+/// If the input did not change for this long, its rate of change is zero.
+const RATE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Produces a value based on how the input changes over time.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Delta {
+    pub input: ProducerRef<Number>,
+    pub mode: DeltaMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub enum DeltaMode {
+    /// The value the input had before it last changed.
+    #[default]
+    PreviousValue,
+    /// How much the input changes per second.
+    PerSecond,
+}
+
+impl Delta {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Number
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        DeltaProducer {
+            input: self.input.clone(),
+            mode: self.mode.clone(),
+            states: EntryStateStorage::default(),
+        }
+        .into()
+    }
+}
+
+#[derive(Default)]
+struct DeltaState {
+    value: Option<f32>,
+    previous_value: Option<f32>,
+    changed_at: Option<Instant>,
+    rate: f32,
+}
+
+struct DeltaProducer {
+    input: ProducerRef<Number>,
+    mode: DeltaMode,
+    states: EntryStateStorage<DeltaState>,
+}
+impl ValueProducer for DeltaProducer {
+    type Output = Number;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let input = value_store.get(&self.input, context)?.0;
+        let now = Instant::now();
+        self.states.with_state(context, |state| {
+            // Only changes of the input are recorded. This keeps the rate stable
+            // when the producer is asked for a value multiple times per frame.
+            match (state.value, state.changed_at) {
+                (Some(value), Some(changed_at)) if value != input => {
+                    let seconds = now.duration_since(changed_at).as_secs_f32();
+                    state.rate = if seconds > 0.0 {
+                        (input - value) / seconds
+                    } else {
+                        0.0
+                    };
+                    state.previous_value = Some(value);
+                    state.value = Some(input);
+                    state.changed_at = Some(now);
+                }
+                (None, _) | (_, None) => {
+                    state.value = Some(input);
+                    state.changed_at = Some(now);
+                }
+                _ => (),
+            }
+
+            let output = match self.mode {
+                DeltaMode::PreviousValue => state.previous_value.unwrap_or(input),
+                DeltaMode::PerSecond => {
+                    if state
+                        .changed_at
+                        .is_some_and(|changed_at| now.duration_since(changed_at) > RATE_TIMEOUT)
+                    {
+                        0.0
+                    } else {
+                        state.rate
+                    }
+                }
+            };
+            Some(Number(output))
+        })
+    }
+}
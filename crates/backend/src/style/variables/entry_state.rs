@@ -0,0 +1,93 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use unified_sim_model::model::{EntryId, Session, SessionId};
+
+use crate::value_store::{ModelContext, RepeatKey};
+
+/// States that were not used for this long are removed.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(30);
+
+type StateKey = (Option<EntryId>, Option<RepeatKey>);
+
+/// Holds the state of a stateful value producer for each entry.
+///
+/// Value producers only have shared access to themself when producing a value.
+/// This storage allows them to keep state between frames. The state is kept
+/// separately for each entry and for the session itself when there is no entry.
+/// Inside a repeater the state is also kept separately for each repeated item.
+///
+/// A state only advances when its producer is evaluated for that entry. A producer
+/// that is not drawn, for example in a hidden cell, keeps its old state until it
+/// is evaluated again.
+///
+/// All states are cleared when the session changes. States of entries that left
+/// the session and states that were not used for a while are pruned.
+pub struct EntryStateStorage<T> {
+    inner: Mutex<Inner<T>>,
+}
+struct Inner<T> {
+    session: Option<SessionId>,
+    states: HashMap<StateKey, T>,
+    used: HashSet<StateKey>,
+    last_prune: Instant,
+}
+impl<T> Default for EntryStateStorage<T> {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                session: None,
+                states: HashMap::new(),
+                used: HashSet::new(),
+                last_prune: Instant::now(),
+            }),
+        }
+    }
+}
+impl<T: Default> EntryStateStorage<T> {
+    /// Run a closure with the state for the entry and repeated item in the context.
+    /// If no state exists for this entry yet, a default state is created.
+    pub fn with_state<R>(&self, context: ModelContext<'_>, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("Entry state lock should not be poisoned");
+        if let Some(session) = context.session {
+            inner.update_session(session);
+        }
+        let key = (
+            context.entry.map(|e| e.id),
+            context.repeat.map(|repeat| repeat.key),
+        );
+        inner.used.insert(key);
+        f(inner.states.entry(key).or_default())
+    }
+}
+impl<T> Inner<T> {
+    fn update_session(&mut self, session: &Session) {
+        if self.session != Some(session.id) {
+            self.session = Some(session.id);
+            self.states.clear();
+            self.used.clear();
+            self.last_prune = Instant::now();
+            return;
+        }
+        if self.last_prune.elapsed() < PRUNE_INTERVAL {
+            return;
+        }
+        let used = std::mem::take(&mut self.used);
+        self.states.retain(|key, _| {
+            let (entry, repeat) = key;
+            let entry_exists = entry.map_or(true, |id| session.entries.contains_key(&id));
+            let repeat_entry_exists = match repeat {
+                Some(RepeatKey::Entry(id)) => session.entries.contains_key(id),
+                _ => true,
+            };
+            used.contains(key) && entry_exists && repeat_entry_exists
+        });
+        self.last_prune = Instant::now();
+    }
+}
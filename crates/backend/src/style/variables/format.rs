@@ -0,0 +1,364 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{AnyProducerRef, Boolean, Number, ProducerRef, Text, ValueType},
+};
+
+/// Formats a number, boolean or text into text.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Format {
+    pub input: AnyProducerRef,
+    #[serde(flatten)]
+    pub number_format: NumberFormat,
+    /// Text for a boolean input that is true.
+    #[serde(default = "default_true_text")]
+    pub true_text: String,
+    /// Text for a boolean input that is false.
+    #[serde(default = "default_false_text")]
+    pub false_text: String,
+    pub prefix: String,
+    pub suffix: String,
+    pub text_case: TextCase,
+    /// Reduce the text to the first letter of every word.
+    pub initials: bool,
+    /// Maximum number of characters before prefix and suffix. 0 for no limit.
+    pub max_length: usize,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            input: AnyProducerRef::default(),
+            number_format: NumberFormat::default(),
+            true_text: default_true_text(),
+            false_text: default_false_text(),
+            prefix: String::new(),
+            suffix: String::new(),
+            text_case: TextCase::Unchanged,
+            initials: false,
+            max_length: 0,
+        }
+    }
+}
+
+fn default_true_text() -> String {
+    String::from("Yes")
+}
+
+fn default_false_text() -> String {
+    String::from("No")
+}
+
+/// How a number is turned into text.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "number_format")]
+pub enum NumberFormat {
+    /// A decimal number like `+1,234.50`.
+    Decimal {
+        decimals: u32,
+        always_sign: bool,
+        /// Minimum number of digits before the decimal point. Padded with zeros.
+        min_digits: u32,
+        thousands_separator: bool,
+    },
+    /// A time in seconds formatted like `1:23.456` or `+2.4`.
+    Time {
+        decimals: u32,
+        always_sign: bool,
+        always_minutes: bool,
+    },
+    /// A number of laps formatted like `+1 LAP` or `+3 LAPS`.
+    Laps {
+        always_sign: bool,
+        singular: String,
+        plural: String,
+    },
+    /// A printf-like template like `%+.3f s` or `%05.1f`.
+    ///
+    /// A directive has the form `%[flags][width][.precision]conversion`.
+    /// The flags are `+` to always show the sign, `0` to pad with zeros instead
+    /// of spaces and `,` to separate thousands. The conversions are `f` for a
+    /// decimal number, `d` for a whole number, `t` for a time like `1:23.456`
+    /// and `T` for a time that always shows minutes. `%%` is a literal `%`.
+    /// Every directive formats the same input value.
+    Template { template: String },
+}
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::Decimal {
+            decimals: 0,
+            always_sign: false,
+            min_digits: 0,
+            thousands_separator: false,
+        }
+    }
+}
+impl NumberFormat {
+    pub fn format(&self, value: f32) -> String {
+        match self {
+            NumberFormat::Decimal {
+                decimals,
+                always_sign,
+                min_digits,
+                thousands_separator,
+            } => format_decimal(
+                value,
+                *decimals,
+                *always_sign,
+                *min_digits,
+                *thousands_separator,
+            ),
+            NumberFormat::Time {
+                decimals,
+                always_sign,
+                always_minutes,
+            } => format_time(value, *decimals, *always_sign, *always_minutes),
+            NumberFormat::Laps {
+                always_sign,
+                singular,
+                plural,
+            } => {
+                let laps = value.round() as i64;
+                let sign = sign_prefix(laps < 0, laps > 0 && *always_sign);
+                let label = if laps.abs() == 1 { singular } else { plural };
+                format!("{sign}{} {label}", laps.abs())
+            }
+            NumberFormat::Template { template } => format_template(template, value),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub enum TextCase {
+    #[default]
+    Unchanged,
+    Uppercase,
+    Lowercase,
+}
+
+impl Format {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Text
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        FormatProducer {
+            input: match self.input.ty() {
+                ValueType::Number => self.input.to_typed().map(FormatInput::Number),
+                ValueType::Boolean => self.input.to_typed().map(FormatInput::Boolean),
+                ValueType::Text => self.input.to_typed().map(FormatInput::Text),
+                _ => None,
+            },
+            format: self.clone(),
+        }
+        .into()
+    }
+
+    pub fn format_text(&self, text: &str) -> String {
+        let mut text = if self.initials {
+            text.split_whitespace()
+                .filter_map(|word| word.chars().next())
+                .collect()
+        } else {
+            text.to_string()
+        };
+        text = match self.text_case {
+            TextCase::Unchanged => text,
+            TextCase::Uppercase => text.to_uppercase(),
+            TextCase::Lowercase => text.to_lowercase(),
+        };
+        if self.max_length > 0 {
+            text = text.chars().take(self.max_length).collect();
+        }
+        format!("{}{}{}", self.prefix, text, self.suffix)
+    }
+}
+
+enum FormatInput {
+    Number(ProducerRef<Number>),
+    Boolean(ProducerRef<Boolean>),
+    Text(ProducerRef<Text>),
+}
+
+struct FormatProducer {
+    input: Option<FormatInput>,
+    format: Format,
+}
+impl ValueProducer for FormatProducer {
+    type Output = Text;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let text = match self.input.as_ref()? {
+            FormatInput::Number(input) => self
+                .format
+                .number_format
+                .format(value_store.get(input, context)?.0),
+            FormatInput::Boolean(input) => {
+                if value_store.get(input, context)?.0 {
+                    self.format.true_text.clone()
+                } else {
+                    self.format.false_text.clone()
+                }
+            }
+            FormatInput::Text(input) => value_store.get(input, context)?.0,
+        };
+        Some(Text(self.format.format_text(&text)))
+    }
+}
+
+fn sign_prefix(negative: bool, positive_sign: bool) -> &'static str {
+    if negative {
+        "-"
+    } else if positive_sign {
+        "+"
+    } else {
+        ""
+    }
+}
+
+fn format_decimal(
+    value: f32,
+    decimals: u32,
+    always_sign: bool,
+    min_digits: u32,
+    thousands_separator: bool,
+) -> String {
+    let formatted = format!("{:.*}", decimals as usize, value.abs());
+    let (integer, fraction) = match formatted.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (formatted.as_str(), None),
+    };
+
+    let mut integer = format!("{:0>width$}", integer, width = min_digits as usize);
+    if thousands_separator {
+        integer = group_thousands(&integer);
+    }
+
+    let is_zero = formatted.chars().all(|c| c == '0' || c == '.');
+    let sign = sign_prefix(
+        value < 0.0 && !is_zero,
+        value > 0.0 && !is_zero && always_sign,
+    );
+    match fraction {
+        Some(fraction) => format!("{sign}{integer}.{fraction}"),
+        None => format!("{sign}{integer}"),
+    }
+}
+
+fn group_thousands(integer: &str) -> String {
+    let digits: Vec<char> = integer.chars().collect();
+    let mut grouped = String::new();
+    for (index, digit) in digits.iter().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(*digit);
+    }
+    grouped
+}
+
+fn format_time(seconds: f32, decimals: u32, always_sign: bool, always_minutes: bool) -> String {
+    let decimals = decimals.min(9);
+    let scale = 10_u64.pow(decimals);
+    let units = (seconds.abs() as f64 * scale as f64).round() as u64;
+    let fraction = units % scale;
+    let whole_seconds = units / scale;
+    let hours = whole_seconds / 3600;
+    let minutes = whole_seconds / 60 % 60;
+    let seconds_part = whole_seconds % 60;
+
+    let mut text = if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds_part:02}")
+    } else if minutes > 0 || always_minutes {
+        format!("{minutes}:{seconds_part:02}")
+    } else {
+        format!("{seconds_part}")
+    };
+    if decimals > 0 {
+        text = format!("{text}.{fraction:0width$}", width = decimals as usize);
+    }
+
+    let sign = sign_prefix(seconds < 0.0 && units > 0, units > 0 && always_sign);
+    format!("{sign}{text}")
+}
+
+fn format_template(template: &str, value: f32) -> String {
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            text.push(c);
+            continue;
+        }
+        let mut directive = String::from("%");
+        let mut always_sign = false;
+        let mut zero_pad = false;
+        let mut thousands_separator = false;
+        while let Some(flag @ ('+' | '0' | ',')) = chars.peek().copied() {
+            match flag {
+                '+' => always_sign = true,
+                '0' => zero_pad = true,
+                _ => thousands_separator = true,
+            }
+            directive.push(flag);
+            chars.next();
+        }
+        let mut width = 0;
+        while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+            width = width * 10 + digit as usize;
+            directive.push(chars.next().unwrap());
+        }
+        let mut precision = None;
+        if chars.peek() == Some(&'.') {
+            directive.push(chars.next().unwrap());
+            let mut digits = 0;
+            while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                digits = digits * 10 + digit;
+                directive.push(chars.next().unwrap());
+            }
+            precision = Some(digits);
+        }
+        let formatted = match chars.next() {
+            Some('%') if directive.len() == 1 => {
+                text.push('%');
+                continue;
+            }
+            Some('f') => format_decimal(
+                value,
+                precision.unwrap_or(6),
+                always_sign,
+                0,
+                thousands_separator,
+            ),
+            Some('d') => format_decimal(value, 0, always_sign, 0, thousands_separator),
+            Some(c @ ('t' | 'T')) => {
+                format_time(value, precision.unwrap_or(3), always_sign, c == 'T')
+            }
+            // Not a directive, keep the text as it was written.
+            other => {
+                text.push_str(&directive);
+                text.extend(other);
+                continue;
+            }
+        };
+        text.push_str(&pad(formatted, width, zero_pad));
+    }
+    text
+}
+
+/// Pad the text to the width. Zeros are inserted after the sign.
+fn pad(text: String, width: usize, zero_pad: bool) -> String {
+    let length = text.chars().count();
+    if length >= width {
+        return text;
+    }
+    let padding = width - length;
+    if zero_pad {
+        let sign_length = if text.starts_with(['+', '-']) { 1 } else { 0 };
+        let (sign, rest) = text.split_at(sign_length);
+        format!("{sign}{}{rest}", "0".repeat(padding))
+    } else {
+        format!("{}{text}", " ".repeat(padding))
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use unified_sim_model::model::Entry;
+
+use crate::{
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{Text, ValueType},
+};
+
+use super::format::NumberFormat;
+
+/// Produces the gap of an entry as text.
+///
+/// Entries in the pits or on an out lap show a pit text instead of a gap.
+/// Entries that are a lap or more behind show the number of laps instead of a time.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Gap {
+    pub reference: GapReference,
+    pub decimals: u32,
+    /// Text for the entry in first position.
+    pub first_text: String,
+    pub pit_text: String,
+    pub out_lap_text: String,
+    pub lap_singular: String,
+    pub lap_plural: String,
+}
+
+impl Default for Gap {
+    fn default() -> Self {
+        Self {
+            reference: GapReference::Leader,
+            decimals: 1,
+            first_text: String::from("Leader"),
+            pit_text: String::from("PIT"),
+            out_lap_text: String::from("OUT"),
+            lap_singular: String::from("LAP"),
+            lap_plural: String::from("LAPS"),
+        }
+    }
+}
+
+/// What the gap is measured to.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub enum GapReference {
+    #[default]
+    Leader,
+    PositionAhead,
+}
+
+impl Gap {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Text
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        GapProducer { gap: self.clone() }.into()
+    }
+}
+
+struct GapProducer {
+    gap: Gap,
+}
+impl ValueProducer for GapProducer {
+    type Output = Text;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let session = context.session?;
+        let entry = context.entry?;
+        let in_pits = *entry.in_pits;
+        let lap_count = *entry.lap_count as i32;
+
+        if in_pits {
+            return Some(Text(self.gap.pit_text.clone()));
+        }
+        if value_store
+            .position_history()
+            .is_out_lap(&entry.id, lap_count, in_pits)
+        {
+            return Some(Text(self.gap.out_lap_text.clone()));
+        }
+
+        let position = *entry.position as i32;
+        if position <= 1 {
+            return Some(Text(self.gap.first_text.clone()));
+        }
+
+        let reference_position = match self.gap.reference {
+            GapReference::Leader => 1,
+            GapReference::PositionAhead => position - 1,
+        };
+        let reference = session
+            .entries
+            .values()
+            .find(|e| *e.position as i32 == reference_position)?;
+
+        let laps_behind = (distance(reference) - distance(entry)).floor() as i32;
+        if laps_behind >= 1 {
+            return Some(Text(
+                NumberFormat::Laps {
+                    always_sign: true,
+                    singular: self.gap.lap_singular.clone(),
+                    plural: self.gap.lap_plural.clone(),
+                }
+                .format(laps_behind as f32),
+            ));
+        }
+
+        let gap_ms = match self.gap.reference {
+            GapReference::Leader => entry.time_behind_leader.ms,
+            GapReference::PositionAhead => entry.time_behind_position_ahead.ms,
+        };
+        Some(Text(
+            NumberFormat::Time {
+                decimals: self.gap.decimals,
+                always_sign: true,
+                always_minutes: false,
+            }
+            .format(gap_ms as f32 / 1000.0),
+        ))
+    }
+}
+
+/// Distance driven by an entry in laps.
+fn distance(entry: &Entry) -> f32 {
+    *entry.lap_count as f32 + *entry.spline_pos as f32
+}
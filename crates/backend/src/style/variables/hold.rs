@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{Boolean, ProducerRef, ValueType},
+};
+
+use super::entry_state::EntryStateStorage;
+
+/// Becomes true when the input becomes true and stays true for
+/// the hold time, regardless of what the input does in the meantime.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Hold {
+    pub input: ProducerRef<Boolean>,
+    /// Hold time in seconds.
+    pub hold_time: f32,
+}
+
+impl Default for Hold {
+    fn default() -> Self {
+        Self {
+            input: ProducerRef::default(),
+            hold_time: 5.0,
+        }
+    }
+}
+
+impl Hold {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Boolean
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        HoldProducer {
+            input: self.input.clone(),
+            hold_time: Duration::from_secs_f32(self.hold_time.max(0.0)),
+            states: EntryStateStorage::default(),
+        }
+        .into()
+    }
+}
+
+#[derive(Default)]
+struct HoldState {
+    last_input: bool,
+    became_true: Option<Instant>,
+}
+
+struct HoldProducer {
+    input: ProducerRef<Boolean>,
+    hold_time: Duration,
+    states: EntryStateStorage<HoldState>,
+}
+impl ValueProducer for HoldProducer {
+    type Output = Boolean;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let input = value_store.get(&self.input, context)?.0;
+        let now = Instant::now();
+        self.states.with_state(context, |state| {
+            if input && !state.last_input {
+                state.became_true = Some(now);
+            }
+            state.last_input = input;
+            Some(Boolean(state.became_true.is_some_and(|became_true| {
+                now.duration_since(became_true) < self.hold_time
+            })))
+        })
+    }
+}
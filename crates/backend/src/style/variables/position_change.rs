@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    position_history::{PositionReference, PositionTrend},
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{Number, ValueType},
+};
+
+/// Produces how the position of an entry changed since a reference point.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct PositionChange {
+    pub reference: PositionReference,
+    pub output: PositionChangeOutput,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub enum PositionChangeOutput {
+    /// Positions gained since the reference. Negative if positions were lost.
+    #[default]
+    Delta,
+    /// 1 if positions were gained, -1 if positions were lost and 0 otherwise.
+    Trend,
+}
+
+impl PositionChange {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Number
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        PositionChangeProducer {
+            position_change: self.clone(),
+        }
+        .into()
+    }
+}
+
+struct PositionChangeProducer {
+    position_change: PositionChange,
+}
+impl ValueProducer for PositionChangeProducer {
+    type Output = Number;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let entry = context.entry?;
+        let history = value_store.position_history();
+        let position = *entry.position as i32;
+        let reference = self.position_change.reference;
+        let value = match self.position_change.output {
+            PositionChangeOutput::Delta => {
+                history.positions_gained(&entry.id, position, reference)? as f32
+            }
+            PositionChangeOutput::Trend => match history.trend(&entry.id, position, reference)? {
+                PositionTrend::Up => 1.0,
+                PositionTrend::Down => -1.0,
+                PositionTrend::Same => 0.0,
+            },
+        };
+        Some(Number(value))
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{Number, ProducerRef, ValueType},
+};
+
+use super::entry_state::EntryStateStorage;
+
+/// Smooths a number to remove jitter from the input.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Smooth {
+    pub input: ProducerRef<Number>,
+    #[serde(flatten)]
+    pub method: SmoothingMethod,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "smoothing")]
+pub enum SmoothingMethod {
+    /// Exponential moving average. The time constant is the time in seconds
+    /// it takes for the output to cover ~63% of a step in the input.
+    Average { time_constant: f32 },
+    /// The output follows the input with a maximum change per second.
+    RateLimit { max_rate: f32 },
+    /// The output is rounded to the step size and only changes when the input
+    /// moves more than the threshold past the rounding boundary.
+    Hysteresis { step: f32, threshold: f32 },
+}
+impl Default for SmoothingMethod {
+    fn default() -> Self {
+        SmoothingMethod::Average { time_constant: 0.5 }
+    }
+}
+
+impl Smooth {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Number
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        SmoothProducer {
+            input: self.input.clone(),
+            method: self.method.clone(),
+            states: EntryStateStorage::default(),
+        }
+        .into()
+    }
+}
+
+#[derive(Default)]
+struct SmoothState {
+    output: Option<f32>,
+    updated_at: Option<Instant>,
+}
+
+struct SmoothProducer {
+    input: ProducerRef<Number>,
+    method: SmoothingMethod,
+    states: EntryStateStorage<SmoothState>,
+}
+impl ValueProducer for SmoothProducer {
+    type Output = Number;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let input = value_store.get(&self.input, context)?.0;
+        let now = Instant::now();
+        self.states.with_state(context, |state| {
+            let (Some(output), Some(updated_at)) = (state.output, state.updated_at) else {
+                state.output = Some(match self.method {
+                    SmoothingMethod::Hysteresis { step, .. } => round_to_step(input, step),
+                    _ => input,
+                });
+                state.updated_at = Some(now);
+                return state.output.map(Number);
+            };
+
+            // The change is based on the time since the last update so that
+            // asking for the value multiple times per frame does not change it.
+            let seconds = now.duration_since(updated_at).as_secs_f32();
+            let new_output = match self.method {
+                SmoothingMethod::Average { time_constant } => {
+                    if time_constant <= 0.0 {
+                        input
+                    } else {
+                        let alpha = 1.0 - (-seconds / time_constant).exp();
+                        output + (input - output) * alpha
+                    }
+                }
+                SmoothingMethod::RateLimit { max_rate } => {
+                    let max_change = max_rate.abs() * seconds;
+                    output + (input - output).clamp(-max_change, max_change)
+                }
+                SmoothingMethod::Hysteresis { step, threshold } => {
+                    if (input - output).abs() > step.abs() / 2.0 + threshold.abs() {
+                        round_to_step(input, step)
+                    } else {
+                        output
+                    }
+                }
+            };
+            state.output = Some(new_output);
+            state.updated_at = Some(now);
+            Some(Number(new_output))
+        })
+    }
+}
+
+fn round_to_step(value: f32, step: f32) -> f32 {
+    if step == 0.0 {
+        value
+    } else {
+        (value / step).round() * step
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{Boolean, Number, ProducerRef, ValueType},
+};
+
+use super::entry_state::EntryStateStorage;
+
+/// Measures the time in seconds since the input became true.
+/// When the input becomes false the last measured time is kept until
+/// the input becomes true again.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Stopwatch {
+    pub input: ProducerRef<Boolean>,
+}
+
+impl Stopwatch {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Number
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        StopwatchProducer {
+            input: self.input.clone(),
+            states: EntryStateStorage::default(),
+        }
+        .into()
+    }
+}
+
+#[derive(Default)]
+struct StopwatchState {
+    started: Option<Instant>,
+    elapsed: Duration,
+}
+
+struct StopwatchProducer {
+    input: ProducerRef<Boolean>,
+    states: EntryStateStorage<StopwatchState>,
+}
+impl ValueProducer for StopwatchProducer {
+    type Output = Number;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let input = value_store.get(&self.input, context)?.0;
+        let now = Instant::now();
+        self.states.with_state(context, |state| {
+            if input {
+                let started = *state.started.get_or_insert(now);
+                state.elapsed = now.duration_since(started);
+            } else {
+                state.started = None;
+            }
+            Some(Number(state.elapsed.as_secs_f32()))
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value_store::{AnyValueProducer, ModelContext, ValueProducer, ValueStore},
+    value_types::{Font, Number, ProducerRef, Property, Text, ValueType},
+};
+
+/// Produces the width of a text when rendered with a font and text size.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TextWidth {
+    pub input: ProducerRef<Text>,
+    pub font: Property<Font>,
+    pub size: Property<Number>,
+}
+impl Default for TextWidth {
+    fn default() -> Self {
+        Self {
+            input: ProducerRef::default(),
+            font: Property::Fixed(Font::Default),
+            size: Property::Fixed(Number(20.0)),
+        }
+    }
+}
+
+impl TextWidth {
+    pub fn output_type(&self) -> ValueType {
+        ValueType::Number
+    }
+
+    pub fn as_typed_producer(&self) -> AnyValueProducer {
+        TextWidthProducer {
+            input: self.input.clone(),
+            font: self.font.clone(),
+            size: self.size.clone(),
+        }
+        .into()
+    }
+}
+
+struct TextWidthProducer {
+    input: ProducerRef<Text>,
+    font: Property<Font>,
+    size: Property<Number>,
+}
+impl ValueProducer for TextWidthProducer {
+    type Output = Number;
+
+    fn get(&self, value_store: &ValueStore, context: ModelContext<'_>) -> Option<Self::Output> {
+        let text = value_store.get(&self.input, context)?.0;
+        let font = value_store
+            .get_property(&self.font, context)
+            .unwrap_or_default();
+        let size = value_store.get_property(&self.size, context)?.0;
+        Some(Number(
+            value_store
+                .text_measurement()
+                .text_width(&text, &font, size),
+        ))
+    }
+}
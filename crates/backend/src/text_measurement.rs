@@ -0,0 +1,159 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use ab_glyph::{Font as _, FontVec, PxScale, ScaleFont};
+use bevy::{
+    app::{First, Plugin},
+    asset::io::file::FileAssetReader,
+    ecs::{
+        event::EventReader,
+        system::{Res, ResMut},
+    },
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    savefile::{Savefile, SavefileChanged},
+    value_store::ValueStore,
+    value_types::{Font, ValueType},
+};
+
+/// Advance of a glyph in the default font relative to the font size.
+/// The default font is a monospace font.
+const DEFAULT_FONT_ADVANCE: f32 = 0.6;
+
+pub struct TextMeasurementPlugin;
+impl Plugin for TextMeasurementPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(First, savefile_changed);
+    }
+}
+
+struct LoadedFont {
+    path: PathBuf,
+    font: FontVec,
+}
+
+/// Measures the size of text using the metrics of the font assets in the style.
+#[derive(Default)]
+pub struct TextMeasurement {
+    fonts: HashMap<Uuid, LoadedFont>,
+}
+impl TextMeasurement {
+    /// The width of a single line of text when rendered with the font and size.
+    pub fn text_width(&self, text: &str, font: &Font, size: f32) -> f32 {
+        let loaded_font = match font {
+            Font::Default => None,
+            Font::Handle(id) => self.fonts.get(id),
+        };
+        let Some(loaded_font) = loaded_font else {
+            return text.chars().count() as f32 * size * DEFAULT_FONT_ADVANCE;
+        };
+
+        let font = loaded_font.font.as_scaled(PxScale::from(size));
+        let mut width = 0.0;
+        let mut previous = None;
+        for c in text.chars() {
+            let glyph_id = font.glyph_id(c);
+            if let Some(previous) = previous {
+                width += font.kern(previous, glyph_id);
+            }
+            width += font.h_advance(glyph_id);
+            previous = Some(glyph_id);
+        }
+        width
+    }
+
+    /// Break text into lines that are no wider than the maximum width.
+    /// Lines are only broken between words. A single word that is wider than
+    /// the maximum width is placed on its own line.
+    pub fn wrap(&self, text: &str, font: &Font, size: f32, max_width: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                if line.is_empty() {
+                    line.push_str(word);
+                    continue;
+                }
+                let candidate = format!("{line} {word}");
+                if self.text_width(&candidate, font, size) <= max_width {
+                    line = candidate;
+                } else {
+                    lines.push(std::mem::replace(&mut line, word.to_owned()));
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Cut a line of text so that it is no wider than the maximum width.
+    /// If a suffix is given, it is appended to the line when the line was cut or
+    /// when `force_suffix` is set. The suffix counts towards the width.
+    pub fn truncate(
+        &self,
+        line: &str,
+        font: &Font,
+        size: f32,
+        max_width: f32,
+        suffix: &str,
+        force_suffix: bool,
+    ) -> String {
+        if !force_suffix && self.text_width(line, font, size) <= max_width {
+            return line.to_owned();
+        }
+        let mut truncated = String::new();
+        for c in line.chars() {
+            let candidate = format!("{truncated}{c}{suffix}");
+            if self.text_width(&candidate, font, size) > max_width {
+                break;
+            }
+            truncated.push(c);
+        }
+        truncated + suffix
+    }
+}
+
+fn savefile_changed(
+    savefile: Res<Savefile>,
+    mut value_store: ResMut<ValueStore>,
+    mut savefile_changed_event: EventReader<SavefileChanged>,
+) {
+    if savefile_changed_event.is_empty() {
+        return;
+    }
+    savefile_changed_event.clear();
+
+    let text_measurement = value_store.text_measurement_mut();
+    let mut fonts = HashMap::new();
+    for asset in savefile.style().assets.contained_assets() {
+        if asset.value_type != ValueType::Font {
+            continue;
+        }
+        // The savefile path is relative to the asset base path, not the working directory.
+        let path = FileAssetReader::get_base_path()
+            .join(savefile.base_path())
+            .join(&asset.path);
+        // Only load fonts that are new or have changed.
+        if let Some(loaded_font) = text_measurement
+            .fonts
+            .remove(&asset.id.0)
+            .filter(|loaded_font| loaded_font.path == path)
+        {
+            fonts.insert(asset.id.0, loaded_font);
+            continue;
+        }
+        info!("Load font metrics for {}", path.display());
+        let font = std::fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| FontVec::try_from_vec(data).map_err(|e| e.to_string()));
+        match font {
+            Ok(font) => {
+                fonts.insert(asset.id.0, LoadedFont { path, font });
+            }
+            Err(e) => warn!("Cannot load font {}: {e}", path.display()),
+        }
+    }
+    text_measurement.fonts = fonts;
+}
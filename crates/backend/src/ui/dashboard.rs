@@ -1,10 +1,11 @@
 use bevy_egui::egui::{Direction, Label, Layout, Sense, Ui};
 use egui_ltreeview::{Action, TreeView};
-use unified_sim_model::{model::Entry, Adapter, AdapterCommand};
+use unified_sim_model::{model::Entry, AdapterCommand};
 
 use crate::{
     graphic::GraphicStates,
     style::graphic::{GraphicDefinition, GRAPHIC_STATE_HIDDEN},
+    GameAdapterResource,
 };
 
 pub fn show_graphic(ui: &mut Ui, graphic: &GraphicDefinition, graphic_states: &mut GraphicStates) {
@@ -33,13 +34,11 @@ pub fn show_graphic(ui: &mut Ui, graphic: &GraphicDefinition, graphic_states: &m
     });
 }
 
-pub fn show_entry_table(ui: &mut Ui, adapter: Option<&Adapter>) {
-    let Some(adapter) = adapter else {
+pub fn show_entry_table(ui: &mut Ui, game_adapter: &GameAdapterResource) {
+    let (Some(adapter), Some(model)) = (game_adapter.adapter(), game_adapter.model()) else {
         return;
     };
 
-    let model = adapter.model.read_raw();
-
     // Get entries sorted by position
     let mut entries: Vec<&Entry> = model
         .current_session()
@@ -2,7 +2,10 @@ use std::{any::Any, collections::HashMap};
 
 use crate::{
     game_sources,
+    position_history::PositionHistory,
+    race_events::RaceEventLog,
     savefile::{Savefile, SavefileChanged},
+    text_measurement::TextMeasurement,
     value_types::{ProducerRef, Property},
 };
 use bevy::{
@@ -15,7 +18,7 @@ use bevy::{
 };
 use serde::{Deserialize, Serialize};
 use tracing::info;
-use unified_sim_model::model::{Entry, Session};
+use unified_sim_model::model::{Entry, EntryId, Lap, Session, Time};
 use uuid::Uuid;
 
 use self::private::PrivateValueResolver;
@@ -80,6 +83,52 @@ where
 pub struct ModelContext<'a> {
     pub session: Option<&'a Session>,
     pub entry: Option<&'a Entry>,
+    pub lap: Option<&'a Lap>,
+    pub sector: Option<&'a Time>,
+    pub repeat: Option<RepeatIndex>,
+    pub parameters: Option<&'a ParameterScope<'a>>,
+}
+
+/// The index of an item in a repeater.
+#[derive(Copy, Clone)]
+pub struct RepeatIndex {
+    pub index: usize,
+    pub count: usize,
+    /// Identifies the item across updates even if its index changes.
+    pub key: RepeatKey,
+}
+
+/// Identifies an element of a repeater across updates.
+#[derive(Hash, PartialEq, Eq, Clone, Copy)]
+pub enum RepeatKey {
+    Index(usize),
+    Entry(EntryId),
+    /// The lap number, starting at 1.
+    Lap(usize),
+    /// The sector number, starting at 1.
+    Sector(usize),
+}
+
+/// The parameter values of a component instance.
+///
+/// Parameters are resolved before any other producer. The value of a parameter
+/// is resolved in the scope the instance was placed in.
+pub struct ParameterScope<'a> {
+    values: HashMap<ProducerId, AnyValueProducer>,
+    parent: Option<&'a ParameterScope<'a>>,
+}
+impl<'a> ParameterScope<'a> {
+    pub fn new(
+        values: HashMap<ProducerId, AnyValueProducer>,
+        parent: Option<&'a ParameterScope<'a>>,
+    ) -> Self {
+        Self { values, parent }
+    }
+
+    /// The number of component instances this scope is nested in, including its own.
+    pub fn depth(&self) -> usize {
+        self.parent.map_or(1, |parent| parent.depth() + 1)
+    }
 }
 
 /// The value store that holds all [`ValueProducer`]s and can resolve
@@ -87,15 +136,43 @@ pub struct ModelContext<'a> {
 #[derive(Resource, Default)]
 pub struct ValueStore {
     values: HashMap<ProducerId, AnyValueProducer>,
+    race_event_log: RaceEventLog,
+    position_history: PositionHistory,
+    text_measurement: TextMeasurement,
 }
 impl ValueStore {
+    /// The log of the most recent race events.
+    pub fn race_event_log(&self) -> &RaceEventLog {
+        &self.race_event_log
+    }
+
+    pub(crate) fn race_event_log_mut(&mut self) -> &mut RaceEventLog {
+        &mut self.race_event_log
+    }
+
+    /// The positions entries had at reference points during the session.
+    pub fn position_history(&self) -> &PositionHistory {
+        &self.position_history
+    }
+
+    pub(crate) fn position_history_mut(&mut self) -> &mut PositionHistory {
+        &mut self.position_history
+    }
+
+    /// Measures text using the fonts of the style.
+    pub fn text_measurement(&self) -> &TextMeasurement {
+        &self.text_measurement
+    }
+
+    pub(crate) fn text_measurement_mut(&mut self) -> &mut TextMeasurement {
+        &mut self.text_measurement
+    }
+
     pub fn get<T>(&self, value_ref: &ProducerRef<T>, context: ModelContext<'_>) -> Option<T>
     where
         Self: ValueResolver<T>,
     {
-        self.values
-            .get(&value_ref.id())
-            .and_then(|p| self.get_typed(p, context))
+        self.get_by_id(&value_ref.id(), context)
     }
 
     pub fn get_property<T>(&self, property: &Property<T>, context: ModelContext<'_>) -> Option<T>
@@ -105,12 +182,27 @@ impl ValueStore {
     {
         match property {
             Property::Fixed(v) => Some(v.clone()),
-            Property::Producer(producer_id) => self
-                .values
-                .get(&producer_id)
-                .and_then(|p| self.get_typed(p, context)),
+            Property::Producer(producer_id) => self.get_by_id(producer_id, context),
         }
     }
+
+    fn get_by_id<T>(&self, producer_id: &ProducerId, context: ModelContext<'_>) -> Option<T>
+    where
+        Self: ValueResolver<T>,
+    {
+        if let Some(parameters) = context.parameters {
+            if let Some(parameter) = parameters.values.get(producer_id) {
+                let context = ModelContext {
+                    parameters: parameters.parent,
+                    ..context
+                };
+                return self.get_typed(parameter, context);
+            }
+        }
+        self.values
+            .get(producer_id)
+            .and_then(|p| self.get_typed(p, context))
+    }
 }
 
 /// This trait signals that the [`ValueStore`] can resolve values of type T.
@@ -30,17 +30,27 @@ pub enum StyleCommand {
     Remove { id: Uuid },
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct CellStyle {
     pub text: String,
     pub text_color: Color,
     pub text_size: f32,
     pub text_alignment: TextAlignment,
     pub text_position: Vec2,
+    /// Distance between two lines of text relative to the text size.
+    pub line_height: f32,
+    pub text_outline: TextOutlineStyle,
+    pub text_shadow: TextShadowStyle,
     pub font: Option<Uuid>,
     pub color: Color,
+    pub gradient: Gradient,
+    pub border: BorderStyle,
+    pub shadow: ShadowStyle,
     pub texture: Option<Uuid>,
     pub pos: Vec3,
+    /// Rotation around the position in radians counter clockwise.
+    pub rotation: f32,
+    pub scale: Vec2,
     pub size: Vec2,
     // Order top left, top right, bottom left, bottom right,
     pub corner_offsets: [Vec2; 4],
@@ -48,6 +58,124 @@ pub struct CellStyle {
     // Order top left, top right, bottom left, bottom right,
     pub rounding: [f32; 4],
     pub render_layer: u8,
+    /// Opacity of the cell and its text.
+    pub opacity: f32,
+}
+impl Default for CellStyle {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            text_color: Color::default(),
+            text_size: 0.0,
+            text_alignment: TextAlignment::default(),
+            text_position: Vec2::ZERO,
+            line_height: 0.0,
+            text_outline: TextOutlineStyle::default(),
+            text_shadow: TextShadowStyle::default(),
+            font: None,
+            color: Color::default(),
+            gradient: Gradient::default(),
+            border: BorderStyle::default(),
+            shadow: ShadowStyle::default(),
+            texture: None,
+            pos: Vec3::ZERO,
+            rotation: 0.0,
+            scale: Vec2::ONE,
+            size: Vec2::ZERO,
+            corner_offsets: [Vec2::ZERO; 4],
+            visible: false,
+            rounding: [0.0; 4],
+            render_layer: 0,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// A gradient from the color of a cell to a second color.
+/// Positions are relative to the top left corner of the cell and angles are in degrees.
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub enum Gradient {
+    #[default]
+    None,
+    Linear {
+        color: Color,
+        position: Vec2,
+        spread: f32,
+        angle: f32,
+    },
+    Radial {
+        color: Color,
+        position: Vec2,
+        spread: f32,
+        distance: f32,
+    },
+    Conical {
+        color: Color,
+        position: Vec2,
+        angle: f32,
+    },
+}
+
+/// A border along the inside edge of a cell.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct BorderStyle {
+    pub width: f32,
+    pub color: Color,
+}
+impl Default for BorderStyle {
+    fn default() -> Self {
+        Self {
+            width: 0.0,
+            color: Color::NONE,
+        }
+    }
+}
+
+/// A shadow that is drawn behind a cell.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct ShadowStyle {
+    pub offset: Vec2,
+    pub blur: f32,
+    pub color: Color,
+}
+impl Default for ShadowStyle {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            blur: 0.0,
+            color: Color::NONE,
+        }
+    }
+}
+
+/// An outline around the text of a cell.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct TextOutlineStyle {
+    pub width: f32,
+    pub color: Color,
+}
+impl Default for TextOutlineStyle {
+    fn default() -> Self {
+        Self {
+            width: 0.0,
+            color: Color::NONE,
+        }
+    }
+}
+
+/// A shadow that is drawn behind the text of a cell.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct TextShadowStyle {
+    pub offset: Vec2,
+    pub color: Color,
+}
+impl Default for TextShadowStyle {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            color: Color::NONE,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -61,10 +189,15 @@ pub enum TextAlignment {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ClipAreaStyle {
     pub pos: Vec3,
+    /// Rotation around the position in radians counter clockwise.
+    pub rotation: f32,
+    pub scale: Vec2,
     pub size: Vec2,
     // Order top left, top right, bottom left, bottom right,
     pub corner_offsets: [Vec2; 4],
     // Order top left, top right, bottom left, bottom right,
     pub rounding: [f32; 4],
     pub render_layer: u8,
+    /// Opacity of the clip area and all its content.
+    pub opacity: f32,
 }
@@ -12,7 +12,9 @@ use bevy::{
     render::color::Color,
     transform::components::Transform,
 };
-use common::communication::{CellStyle, TextAlignment};
+use common::communication::{
+    BorderStyle, CellStyle, Gradient, ShadowStyle, TextAlignment, TextOutlineStyle, TextShadowStyle,
+};
 
 pub struct BallPlugin;
 impl Plugin for BallPlugin {
@@ -89,15 +91,24 @@ fn update(
                 text_size: 40.0,
                 text_alignment: TextAlignment::Center,
                 text_position: Vec2::ZERO,
+                line_height: 1.0,
+                text_outline: TextOutlineStyle::default(),
+                text_shadow: TextShadowStyle::default(),
                 font: None,
                 color: ball.color.clone(),
+                gradient: Gradient::None,
+                border: BorderStyle::default(),
+                shadow: ShadowStyle::default(),
                 texture: None,
                 pos: transform.translation,
+                rotation: 0.0,
+                scale: Vec2::ONE,
                 size: vec2(30.0, 30.0),
                 corner_offsets: [Vec2::ZERO, Vec2::ZERO, Vec2::ZERO, Vec2::ZERO],
                 visible: true,
                 rounding: [15.0; 4],
                 render_layer: 0,
+                opacity: 1.0,
             },
         );
     }
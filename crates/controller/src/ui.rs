@@ -60,7 +60,7 @@ fn ui(
         }
     });
     egui::SidePanel::left("Side panel").show(ctx.ctx_mut(), |ui| {
-        backend::ui::dashboard::show_entry_table(ui, game_adapter.adapter());
+        backend::ui::dashboard::show_entry_table(ui, &game_adapter);
         ui.allocate_space(ui.available_size_before_wrap());
     });
     egui::CentralPanel::default().show(ctx.ctx_mut(), |ui| {
@@ -10,7 +10,7 @@ use bevy::{
         system::{Commands, Query, Res},
     },
     input::{
-        mouse::{MouseButtonInput, MouseWheel},
+        mouse::{MouseButton, MouseButtonInput, MouseWheel},
         ButtonState,
     },
     math::{vec2, vec3, UVec2, Vec2},
@@ -128,7 +128,12 @@ fn camera_drag(
         return;
     }
 
+    // The left mouse button is handled by the scene view. It selects and moves cells
+    // and pans the camera when it is dragged on empty space.
     for ev in mouse_events.read() {
+        if ev.button == MouseButton::Left {
+            continue;
+        }
         match ev.state {
             ButtonState::Pressed => {
                 camera_drag.drag_position = window.cursor_position();
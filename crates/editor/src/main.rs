@@ -8,7 +8,7 @@ use bevy::{
     ecs::system::{EntityCommand, ResMut},
     prelude::{
         App, AssetServer, ClearColor, Color, Commands, Component, EntityWorldMut, EventWriter,
-        Handle, PreStartup, Res, Resource, Startup, Vec2, Vec3, World,
+        Handle, PreStartup, Res, Resource, Startup, World,
     },
     text::Font,
     time::{Timer, TimerMode},
@@ -17,11 +17,8 @@ use bevy::{
 use bevy_egui::EguiPlugin;
 use camera::EditorCameraPlugin;
 use cell_manager::CellManagerPlugin;
-use common::communication::TextAlignment;
-use frontend::{
-    cell::{CreateCell, SetStyle},
-    FrontendPlugin,
-};
+use frontend::FrontendPlugin;
+use reference_images::ReferenceImagePlugin;
 use reference_store::ReferenceStorePlugin;
 use std::env;
 use ui::EditorUiPlugin;
@@ -29,6 +26,7 @@ use ui::EditorUiPlugin;
 mod asset_path_store;
 mod camera;
 mod cell_manager;
+mod reference_images;
 mod reference_store;
 mod ui;
 
@@ -44,6 +42,7 @@ fn main() {
             EditorUiPlugin,
             EditorCameraPlugin,
             ReferenceStorePlugin,
+            ReferenceImagePlugin,
         ))
         .add_plugins(DefaultPlugins)
         .add_plugins(FrameTimeDiagnosticsPlugin)
@@ -64,9 +63,6 @@ pub struct DefaultFont(pub Handle<Font>);
 #[derive(Component)]
 pub struct MainCamera;
 
-#[derive(Component)]
-struct BackgroundImage;
-
 fn load(asset_server: Res<AssetServer>, mut commands: Commands) {
     //let font: Handle<Font> = asset_server.load("fonts/FiraSans-bold.ttf");
     //let font: Handle<Font> = asset_server.load("fonts/Heebo-Regular.ttf");
@@ -77,10 +73,7 @@ fn load(asset_server: Res<AssetServer>, mut commands: Commands) {
 
 fn setup(
     savefile_changed_event: EventWriter<SavefileChanged>,
-    mut commands: Commands,
-    mut set_style_event: EventWriter<SetStyle>,
     mut savefile: ResMut<Savefile>,
-    asset_server: Res<AssetServer>,
     mut game_adapter: ResMut<GameAdapterResource>,
 ) {
     let adapter = unified_sim_model::Adapter::new_dummy();
@@ -92,32 +85,6 @@ fn setup(
     game_adapter.set(adapter);
 
     savefile.load("../../savefiles/f1/style.json", savefile_changed_event);
-
-    let background_id = commands
-        .spawn_empty()
-        .add(CreateCell)
-        .insert(BackgroundImage)
-        .id();
-    set_style_event.send(SetStyle {
-        entity: background_id,
-        style: frontend::cell::CellStyle {
-            text: "".to_string(),
-            text_color: Color::BLACK,
-            text_size: 20.0,
-            text_alignment: TextAlignment::Center,
-            text_position: Vec2::ZERO,
-            font: None,
-            color: Color::WHITE,
-            //texture: Some(asset_server.load("../../../savefile/acc6.PNG")),
-            texture: Some(asset_server.load("../../../reference/F1.png")),
-            pos: Vec3::new(0.0, 0.0, -100.0),
-            size: Vec2::new(1920.0, 1080.0),
-            corner_offsets: [Vec2::ZERO, Vec2::ZERO, Vec2::ZERO, Vec2::ZERO],
-            visible: true,
-            rounding: [0.0, 0.0, 0.0, 0.0],
-            render_layer: 0,
-        },
-    });
 }
 
 pub trait SpawnAndInitWorld {
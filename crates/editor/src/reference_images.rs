@@ -0,0 +1,293 @@
+//! Reference images are shown in the scene to recreate a graphic from a screenshot.
+//!
+//! Reference images are part of the editor workspace and not of the style.
+//! The workspace keeps a separate list of reference images for each style file.
+
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use backend::savefile::{Savefile, SavefileChanged};
+use bevy::{
+    app::{First, Plugin, Update},
+    asset::{io::file::FileAssetReader, AssetPath, AssetServer, Assets, Handle},
+    ecs::{
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        system::{Commands, Local, Res, ResMut, Resource},
+    },
+    hierarchy::DespawnRecursiveExt,
+    math::{vec2, vec3, Vec2},
+    render::{color::Color, render_resource::TextureFormat, texture::Image},
+};
+use frontend::cell::{CellStyle, CreateCell, SetStyle};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use uuid::Uuid;
+
+/// File of the editor workspace. Relative to the base path of the asset reader.
+const WORKSPACE_FILE: &str = "editor_workspace.json";
+/// Folder that contains the reference images. Relative to the base path of the asset reader.
+const REFERENCE_FOLDER: &str = "../../reference";
+/// Reference image that is shown for styles that have no reference images in the workspace.
+const DEFAULT_REFERENCE_IMAGE: &str = "F1.png";
+
+pub struct ReferenceImagePlugin;
+impl Plugin for ReferenceImagePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.insert_resource(ReferenceImages::default())
+            .add_systems(First, savefile_changed)
+            .add_systems(Update, update_reference_images);
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Workspace {
+    /// Reference images for each style file.
+    #[serde(default)]
+    reference_images: HashMap<String, Vec<ReferenceImage>>,
+}
+impl Workspace {
+    fn load() -> Self {
+        let path = FileAssetReader::get_base_path().join(WORKSPACE_FILE);
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let path = FileAssetReader::get_base_path().join(WORKSPACE_FILE);
+        let s = match serde_json::to_string_pretty(self) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Error turning workspace into string: {e}");
+                return;
+            }
+        };
+        if let Err(e) = fs::write(path, s) {
+            error!("Cannot write workspace file: {e}");
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReferenceImage {
+    pub id: Uuid,
+    pub enabled: bool,
+    /// File name of the image in the reference folder.
+    pub file: String,
+    pub opacity: f32,
+    pub blend: ReferenceBlend,
+    /// Position of the top left corner in the scene.
+    pub position: Vec2,
+    pub scale: f32,
+}
+impl ReferenceImage {
+    pub fn new(file: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            enabled: true,
+            file,
+            opacity: 1.0,
+            blend: ReferenceBlend::Background,
+            position: Vec2::ZERO,
+            scale: 1.0,
+        }
+    }
+}
+
+/// How a reference image is combined with the scene.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ReferenceBlend {
+    /// Drawn behind the scene.
+    Background,
+    /// Drawn on top of the scene.
+    OnionSkin,
+    /// The inverted image is drawn on top of the scene at half opacity.
+    /// Areas where the scene matches the reference turn into a flat gray.
+    ///
+    /// This is not a true difference blend. Differences show as a shift away
+    /// from gray and not as the absolute difference of the two images.
+    InvertedOverlay,
+}
+
+/// The reference images of the style that is currently edited.
+#[derive(Resource, Default)]
+pub struct ReferenceImages {
+    /// The style file the images belong to.
+    style_path: String,
+    pub images: Vec<ReferenceImage>,
+    /// If the images were changed since they were last stored in the workspace.
+    pub unsaved: bool,
+    /// Reference images that cannot be shown and why.
+    errors: HashMap<Uuid, String>,
+}
+impl ReferenceImages {
+    /// The reason why a reference image cannot be shown.
+    pub fn error(&self, id: &Uuid) -> Option<&str> {
+        self.errors.get(id).map(String::as_str)
+    }
+
+    /// Store the reference images of the current style in the workspace.
+    pub fn save(&mut self) {
+        self.unsaved = false;
+        let mut workspace = Workspace::load();
+        workspace
+            .reference_images
+            .insert(self.style_path.clone(), self.images.clone());
+        workspace.save();
+    }
+
+    /// The files in the reference folder that can be used as reference images.
+    pub fn available_files() -> Vec<String> {
+        let folder = FileAssetReader::get_base_path().join(REFERENCE_FOLDER);
+        let Ok(entries) = fs::read_dir(folder) else {
+            return Vec::new();
+        };
+        let mut files: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| {
+                        ["png", "jpg", "jpeg"].contains(&extension.to_lowercase().as_str())
+                    })
+            })
+            .filter_map(|path| Some(path.file_name()?.to_str()?.to_owned()))
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+fn savefile_changed(
+    savefile: Res<Savefile>,
+    mut reference_images: ResMut<ReferenceImages>,
+    mut savefile_changed_event: EventReader<SavefileChanged>,
+) {
+    if !savefile_changed_event.read().any(|event| event.replace) {
+        return;
+    }
+    // Keep changes to the images of the previous style.
+    if reference_images.unsaved {
+        reference_images.save();
+    }
+    let style_path = savefile.path().to_string_lossy().into_owned();
+    reference_images.images = Workspace::load()
+        .reference_images
+        .remove(&style_path)
+        .unwrap_or_else(|| vec![ReferenceImage::new(DEFAULT_REFERENCE_IMAGE.to_owned())]);
+    reference_images.style_path = style_path;
+    reference_images.errors.clear();
+}
+
+/// The cell that draws a reference image.
+struct ReferenceCell {
+    entity: Entity,
+    file: String,
+    image: Handle<Image>,
+    inverted: Option<Handle<Image>>,
+}
+
+fn update_reference_images(
+    mut commands: Commands,
+    mut reference_images: ResMut<ReferenceImages>,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut set_style_event: EventWriter<SetStyle>,
+    mut cells: Local<HashMap<Uuid, ReferenceCell>>,
+) {
+    // Remove the cells of reference images that no longer exist.
+    cells.retain(|id, cell| {
+        let exists = reference_images.images.iter().any(|image| image.id == *id);
+        if !exists {
+            commands.entity(cell.entity).despawn_recursive();
+        }
+        exists
+    });
+
+    let reference_images = &mut *reference_images;
+    for reference_image in reference_images.images.iter() {
+        let cell = cells
+            .entry(reference_image.id)
+            .or_insert_with(|| ReferenceCell {
+                entity: commands.spawn_empty().add(CreateCell).id(),
+                file: String::new(),
+                image: Handle::default(),
+                inverted: None,
+            });
+        if cell.file != reference_image.file {
+            let path = PathBuf::from(REFERENCE_FOLDER).join(&reference_image.file);
+            cell.file = reference_image.file.clone();
+            cell.image = asset_server.load(
+                AssetPath::from_path(&path)
+                    .clone_owned()
+                    .with_source("savefile"),
+            );
+            cell.inverted = None;
+            reference_images.errors.remove(&reference_image.id);
+        }
+
+        // The image has to be loaded to know its size.
+        let Some(image) = images.get(&cell.image) else {
+            continue;
+        };
+        let size = vec2(
+            image.texture_descriptor.size.width as f32,
+            image.texture_descriptor.size.height as f32,
+        );
+        let (texture, z, opacity) = match reference_image.blend {
+            ReferenceBlend::Background => {
+                (Some(cell.image.clone()), -100.0, reference_image.opacity)
+            }
+            ReferenceBlend::OnionSkin => (Some(cell.image.clone()), 100.0, reference_image.opacity),
+            ReferenceBlend::InvertedOverlay => {
+                // Images that cannot be inverted are not tried again until the file changes.
+                if cell.inverted.is_none()
+                    && !reference_images.errors.contains_key(&reference_image.id)
+                {
+                    match inverted(image) {
+                        Some(image) => cell.inverted = Some(images.add(image)),
+                        None => {
+                            reference_images.errors.insert(
+                                reference_image.id,
+                                format!(
+                                    "The image format {:?} cannot be inverted.",
+                                    image.texture_descriptor.format
+                                ),
+                            );
+                        }
+                    }
+                }
+                (cell.inverted.clone(), 100.0, reference_image.opacity * 0.5)
+            }
+        };
+        set_style_event.send(SetStyle {
+            entity: cell.entity,
+            style: CellStyle {
+                color: Color::WHITE,
+                visible: reference_image.enabled && texture.is_some(),
+                texture,
+                pos: vec3(reference_image.position.x, -reference_image.position.y, z),
+                size: size * reference_image.scale,
+                opacity: opacity.clamp(0.0, 1.0),
+                ..Default::default()
+            },
+        });
+    }
+}
+
+/// Create a copy of the image with inverted colors.
+/// Images are converted to 8 bit rgba first if they use a different format.
+fn inverted(image: &Image) -> Option<Image> {
+    let mut inverted = match image.texture_descriptor.format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => image.clone(),
+        _ => image.convert(TextureFormat::Rgba8UnormSrgb)?,
+    };
+    for pixel in inverted.data.chunks_exact_mut(4) {
+        for channel in pixel[0..3].iter_mut() {
+            *channel = 255 - *channel;
+        }
+    }
+    Some(inverted)
+}
@@ -14,7 +14,12 @@ use tracing::info;
 use backend::{
     game_sources,
     savefile::{Savefile, SavefileChanged},
-    style::{assets::AssetFolder, variables::VariableFolder, StyleDefinition},
+    style::{
+        assets::AssetFolder,
+        graphic::{ComponentParameter, GraphicFolder},
+        variables::VariableFolder,
+        StyleDefinition, StyleId,
+    },
     value_store::ProducerId,
     value_types::AnyProducerRef,
 };
@@ -42,6 +47,17 @@ fn savefile_changed(
     info!("Reload reference store");
     *reference_store = ReferenceStore {
         entries: Entry::from_style_definition(savefile.style()),
+        components: savefile
+            .style()
+            .components
+            .contained_graphics()
+            .into_iter()
+            .map(|component| ComponentData {
+                id: component.id,
+                name: component.name.clone(),
+                parameters: component.parameters.clone(),
+            })
+            .collect(),
     };
 }
 
@@ -51,12 +67,29 @@ pub struct ProducerData {
     pub producer_ref: AnyProducerRef,
 }
 
+/// A component that can be placed in a graphic.
+pub struct ComponentData {
+    pub id: StyleId,
+    pub name: String,
+    pub parameters: Vec<ComponentParameter>,
+}
+
 #[derive(Resource, Default)]
 pub struct ReferenceStore {
     entries: Vec<Entry>,
+    components: Vec<ComponentData>,
 }
 
 impl ReferenceStore {
+    /// All components of the style.
+    pub fn components(&self) -> &[ComponentData] {
+        &self.components
+    }
+
+    pub fn component(&self, id: &StyleId) -> Option<&ComponentData> {
+        self.components.iter().find(|component| &component.id == id)
+    }
+
     fn get(&self, id: &ProducerId) -> Option<&ProducerData> {
         self.entries.iter().find_map(|e| e.get(id))
     }
@@ -108,6 +141,7 @@ impl Entry {
         result.push(Self::from_assets(&style.assets));
         result.push(Self::from_game());
         result.push(Self::from_vars(&style.vars));
+        result.push(Self::from_components(&style.components));
         result
     }
     fn from_vars(vars: &VariableFolder) -> Self {
@@ -128,6 +162,28 @@ impl Entry {
                 .collect(),
         }
     }
+    fn from_components(components: &GraphicFolder) -> Self {
+        Self::Folder {
+            name: String::from("Parameters"),
+            entries: components
+                .contained_graphics()
+                .into_iter()
+                .map(|component| Self::Folder {
+                    name: component.name.clone(),
+                    entries: component
+                        .parameters
+                        .iter()
+                        .map(|parameter| {
+                            Self::Producer(ProducerData {
+                                name: parameter.name.clone(),
+                                producer_ref: parameter.producer_ref(),
+                            })
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
     fn from_game() -> Self {
         Self::Folder {
             name: "Game".to_string(),
@@ -276,3 +332,35 @@ pub fn any_producer_ref_editor(
 
     res.response
 }
+
+/// Editor for a producer reference that can be unset.
+/// Shows the `none_text` when no producer is referenced.
+pub fn optional_producer_ref_editor(
+    ui: &mut Ui,
+    reference_store: &ReferenceStore,
+    producer_ref: &mut Option<AnyProducerRef>,
+    none_text: &str,
+    is_type_allowed: impl Fn(&ProducerData) -> bool,
+) -> Response {
+    ui.horizontal(|ui| {
+        let button_name = match producer_ref {
+            Some(producer_ref) => reference_store
+                .get(&producer_ref.id())
+                .map(|id| id.name.as_str())
+                .unwrap_or("- Invalud Ref -"),
+            None => none_text,
+        };
+
+        let mut res = reference_store.show_popup(ui, button_name, is_type_allowed);
+        if let Some(selected_producer) = res.inner {
+            *producer_ref = Some(selected_producer);
+            res.response.mark_changed();
+        }
+        if producer_ref.is_some() && ui.small_button("x").clicked() {
+            *producer_ref = None;
+            res.response.mark_changed();
+        }
+        res.response
+    })
+    .inner
+}
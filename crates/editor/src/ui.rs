@@ -1,3 +1,4 @@
+mod clipboard;
 pub mod combo_box;
 mod panels;
 pub mod popup;
@@ -8,6 +9,7 @@ use std::{collections::HashMap, fs::File, io::Write, ops::BitOrAssign, time::Ins
 use backend::{
     exact_variant::ExactVariant,
     savefile::{Savefile, SavefileChanged},
+    scenario::Scenario,
     style::{
         graphic::{graphic_items::GraphicItemId, GraphicStateId},
         StyleDefinition, StyleId, StyleItem, TreePosition,
@@ -19,24 +21,29 @@ use bevy::{
     app::{First, Update},
     ecs::{
         event::{EventReader, EventWriter},
+        query::With,
         schedule::{IntoSystemConfigs, SystemSet},
         system::{Local, Res, SystemState},
         world::World,
     },
+    math::Vec2,
     prelude::{Plugin, ResMut, Resource, Startup},
+    transform::components::Transform,
 };
 use bevy_egui::{
     egui::{self, Rect, Response},
-    EguiContexts,
+    EguiClipboard, EguiContexts,
 };
 
-use egui_ltreeview::TreeViewState;
+use egui_ltreeview::{DropPosition, TreeViewState};
 use rand::{seq::IteratorRandom, thread_rng};
 use tracing::error;
 use unified_sim_model::Adapter;
 
 use crate::camera::{AlignCamera, EditorCamera, ResetCamera};
 
+use self::clipboard::ClipboardContent;
+
 pub struct EditorUiPlugin;
 impl Plugin for EditorUiPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
@@ -97,8 +104,23 @@ impl EditorState {
 #[derive(Default)]
 struct StyleItemSelection {
     graphic_item_tree_state: TreeViewState<GraphicItemId>,
+    /// Graphic items that are selected together with the item selected in the tree.
+    graphic_item_multi_selection: Vec<GraphicItemId>,
     graphic_state_tree_state: TreeViewState<GraphicStateId>,
 }
+impl StyleItemSelection {
+    /// All selected graphic items.
+    /// The item that is selected in the tree is always part of the selection.
+    fn selected_graphic_items(&self) -> Vec<GraphicItemId> {
+        match self.graphic_item_tree_state.selected() {
+            Some(id) if self.graphic_item_multi_selection.contains(&id) => {
+                self.graphic_item_multi_selection.clone()
+            }
+            Some(id) => vec![id],
+            None => Vec::new(),
+        }
+    }
+}
 
 #[derive(Resource)]
 struct UiMessages(Vec<UiMessage>);
@@ -151,6 +173,8 @@ enum UiMessage {
     Undo,
     Redo,
     SceneViewport(Rect),
+    /// Move the scene camera by an offset in world space.
+    ScenePan(Vec2),
     SaveStyleDefinition,
     GameAdapterClose,
     GameAdapterConnectDummy,
@@ -158,6 +182,7 @@ enum UiMessage {
     GameAdapterSelectRandomEntry,
     GameAdapterDummySetSessionType(unified_sim_model::model::SessionType),
     GameAdapterDummySetEntryAmount(usize),
+    GameAdapterSetScenario(Option<Scenario>),
     CameraReset,
     CameraAlign,
     StyleItemSelect(StyleId),
@@ -177,6 +202,31 @@ enum UiMessage {
         widget_id: egui::Id,
         item: StyleItem,
     },
+    StyleItemCopy(StyleId),
+    StyleItemPaste {
+        target: StyleId,
+        position: TreePosition<StyleId>,
+    },
+    StyleItemDuplicate {
+        id: StyleId,
+        target: StyleId,
+        position: TreePosition<StyleId>,
+    },
+    GraphicItemCopy {
+        graphic_id: StyleId,
+        item_id: GraphicItemId,
+    },
+    GraphicItemPaste {
+        graphic_id: StyleId,
+        target: GraphicItemId,
+        position: DropPosition<GraphicItemId>,
+    },
+    GraphicItemDuplicate {
+        graphic_id: StyleId,
+        item_id: GraphicItemId,
+        target: GraphicItemId,
+        position: DropPosition<GraphicItemId>,
+    },
 }
 
 fn process_ui_messages(
@@ -259,6 +309,12 @@ fn process_message(message: UiMessage, world: &mut World) -> Option<UiMessage> {
                 .single_mut(world)
                 .raw_viewport = viewport_rect;
         }
+        UiMessage::ScenePan(offset) => {
+            world
+                .query_filtered::<&mut Transform, With<EditorCamera>>()
+                .single_mut(world)
+                .translation += offset.extend(0.0);
+        }
         UiMessage::SaveStyleDefinition => {
             let savefile = world.resource::<Savefile>();
             let s = match serde_json::to_string_pretty(savefile.style()) {
@@ -330,6 +386,11 @@ fn process_message(message: UiMessage, world: &mut World) -> Option<UiMessage> {
                 ));
             }
         }
+        UiMessage::GameAdapterSetScenario(scenario) => {
+            world
+                .resource_mut::<GameAdapterResource>()
+                .set_scenario(scenario);
+        }
         UiMessage::CameraReset => {
             world.send_event(ResetCamera);
         }
@@ -445,6 +506,128 @@ fn process_message(message: UiMessage, world: &mut World) -> Option<UiMessage> {
                 });
             }
         }
+        UiMessage::StyleItemCopy(id) => {
+            let content =
+                ClipboardContent::copy_style_item(world.resource::<Savefile>().style(), id);
+            set_clipboard(content, world);
+        }
+        UiMessage::StyleItemPaste { target, position } => {
+            let content = get_clipboard(world)?;
+            return paste_style_item(content, target, position, world);
+        }
+        UiMessage::StyleItemDuplicate {
+            id,
+            target,
+            position,
+        } => {
+            let content =
+                ClipboardContent::copy_style_item(world.resource::<Savefile>().style(), id)?;
+            return paste_style_item(content, target, position, world);
+        }
+        UiMessage::GraphicItemCopy {
+            graphic_id,
+            item_id,
+        } => {
+            let content = ClipboardContent::copy_graphic_item(
+                world.resource::<Savefile>().style(),
+                graphic_id,
+                item_id,
+            );
+            set_clipboard(content, world);
+        }
+        UiMessage::GraphicItemPaste {
+            graphic_id,
+            target,
+            position,
+        } => {
+            let content = get_clipboard(world)?;
+            return paste_graphic_item(content, graphic_id, target, position, world);
+        }
+        UiMessage::GraphicItemDuplicate {
+            graphic_id,
+            item_id,
+            target,
+            position,
+        } => {
+            let content = ClipboardContent::copy_graphic_item(
+                world.resource::<Savefile>().style(),
+                graphic_id,
+                item_id,
+            )?;
+            return paste_graphic_item(content, graphic_id, target, position, world);
+        }
     }
     None
 }
+
+fn set_clipboard(content: Option<ClipboardContent>, world: &mut World) {
+    let Some(json) = content.and_then(|content| content.to_json()) else {
+        error!("Cannot copy item to the clipboard");
+        return;
+    };
+    world.resource_mut::<EguiClipboard>().set_contents(&json);
+}
+
+fn get_clipboard(world: &mut World) -> Option<ClipboardContent> {
+    world
+        .resource_mut::<EguiClipboard>()
+        .get_contents()
+        .and_then(|json| ClipboardContent::from_json(&json))
+}
+
+/// Paste a style item into the style.
+/// The whole style is replaced in a single edit so the paste can be undone in one step.
+fn paste_style_item(
+    content: ClipboardContent,
+    target: StyleId,
+    position: TreePosition<StyleId>,
+    world: &mut World,
+) -> Option<UiMessage> {
+    let mut style = world.resource::<Savefile>().style().clone();
+    let pasted_id = content.paste_style_item(&mut style, target, position)?;
+
+    let mut editor_state = world.resource_mut::<EditorState>();
+    editor_state
+        .style_item_tree_state
+        .set_selected(Some(pasted_id));
+    editor_state
+        .style_item_tree_state
+        .expand_parents_of(target, true);
+
+    process_message(
+        UiMessage::StyleItemEdit {
+            widget_id: egui::Id::new(("Paste", pasted_id)),
+            item: style.to_enum(),
+        },
+        world,
+    )
+}
+
+/// Paste a graphic item into a graphic.
+/// The whole style is replaced in a single edit so the paste can be undone in one step.
+fn paste_graphic_item(
+    content: ClipboardContent,
+    graphic_id: StyleId,
+    target: GraphicItemId,
+    position: DropPosition<GraphicItemId>,
+    world: &mut World,
+) -> Option<UiMessage> {
+    let mut style = world.resource::<Savefile>().style().clone();
+    let pasted_id = content.paste_graphic_item(&mut style, graphic_id, target, position)?;
+
+    world
+        .resource_mut::<EditorState>()
+        .style_item_selection_data
+        .entry(graphic_id)
+        .or_default()
+        .graphic_item_tree_state
+        .set_selected(Some(pasted_id));
+
+    process_message(
+        UiMessage::StyleItemEdit {
+            widget_id: egui::Id::new(("Paste", pasted_id)),
+            item: style.to_enum(),
+        },
+        world,
+    )
+}
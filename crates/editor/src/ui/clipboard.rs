@@ -0,0 +1,470 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::ControlFlow,
+};
+
+use backend::{
+    exact_variant::ExactVariant,
+    style::{
+        graphic::{
+            graphic_items::{GraphicItem, GraphicItemId},
+            GraphicDefinition, GraphicOrFolder, GraphicStateId,
+        },
+        variables::{VariableDefinition, VariableFolder, VariableOrFolder},
+        StyleDefinition, StyleId, StyleItem, TreePosition,
+    },
+    tree_iterator::{Method, TreeItem, TreeIterator, TreeIteratorMut},
+};
+use egui_ltreeview::DropPosition;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::tabs::style_item::graphic::insert_element;
+
+/// Content of the clipboard when graphic items or style items are copied.
+///
+/// The variables and components that the copied item references are copied
+/// along so that the item can be pasted into a different style.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "clipboard_type")]
+pub enum ClipboardContent {
+    GraphicItem {
+        item: GraphicItem,
+        variables: Vec<CopiedVariable>,
+        components: Vec<GraphicDefinition>,
+    },
+    StyleItem {
+        item: StyleItem,
+        variables: Vec<CopiedVariable>,
+        components: Vec<GraphicDefinition>,
+    },
+}
+
+/// A copied variable and the names of the folders it was in.
+#[derive(Serialize, Deserialize)]
+pub struct CopiedVariable {
+    folders: Vec<String>,
+    variable: VariableDefinition,
+}
+impl ClipboardContent {
+    pub fn to_json(&self) -> Option<String> {
+        serde_json::to_string_pretty(self).ok()
+    }
+
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str(json).ok()
+    }
+
+    /// Copy a graphic item and all its children.
+    pub fn copy_graphic_item(
+        style: &ExactVariant<StyleItem, StyleDefinition>,
+        graphic_id: StyleId,
+        item_id: GraphicItemId,
+    ) -> Option<Self> {
+        let item = style
+            .search(graphic_id, |node| match node {
+                StyleItem::Graphic(graphic) => graphic
+                    .items
+                    .as_enum_ref()
+                    .search(item_id, |item| item.clone()),
+                _ => None,
+            })
+            .flatten()?;
+        let (variables, components) = references(style, serde_json::to_value(&item).ok()?, &[])?;
+        Some(Self::GraphicItem {
+            item,
+            variables,
+            components,
+        })
+    }
+
+    /// Copy a style item and all its children.
+    pub fn copy_style_item(
+        style: &ExactVariant<StyleItem, StyleDefinition>,
+        id: StyleId,
+    ) -> Option<Self> {
+        let item = style.search(id, |node| node.clone())?;
+        if let StyleItem::Style(_) = item {
+            return None;
+        }
+        let (variables, components) = references(
+            style,
+            serde_json::to_value(&item).ok()?,
+            &defined_style_item_ids(&item),
+        )?;
+        Some(Self::StyleItem {
+            item,
+            variables,
+            components,
+        })
+    }
+
+    /// Paste the content as a graphic item into a graphic.
+    /// Returns the id of the pasted item.
+    pub fn paste_graphic_item(
+        self,
+        style: &mut ExactVariant<StyleItem, StyleDefinition>,
+        graphic_id: StyleId,
+        target: GraphicItemId,
+        position: DropPosition<GraphicItemId>,
+    ) -> Option<GraphicItemId> {
+        if !matches!(self, Self::GraphicItem { .. }) {
+            return None;
+        }
+        // States of the graphic the item was copied from do not exist in the target.
+        let target_states: HashSet<GraphicStateId> = style
+            .search(graphic_id, |node| match node {
+                StyleItem::Graphic(graphic) => {
+                    Some(graphic.states.iter().map(|state| state.id).collect())
+                }
+                _ => None,
+            })
+            .flatten()?;
+        let Self::GraphicItem {
+            item,
+            variables,
+            components,
+        } = self.prepare(style, Some(&target_states))?
+        else {
+            return None;
+        };
+
+        let item_id = item.id();
+        style
+            .as_enum_mut()
+            .search_mut(graphic_id, |node| match node {
+                StyleItem::Graphic(graphic) => {
+                    insert_element(graphic, target, position, item);
+                    Some(())
+                }
+                _ => None,
+            })??;
+        add_variables(style, variables);
+        add_components(style, components);
+        Some(item_id)
+    }
+
+    /// Paste the content as a style item into a folder.
+    /// Returns the id of the pasted item.
+    pub fn paste_style_item(
+        self,
+        style: &mut ExactVariant<StyleItem, StyleDefinition>,
+        target: StyleId,
+        position: TreePosition<StyleId>,
+    ) -> Option<StyleId> {
+        let Self::StyleItem { item, .. } = &self else {
+            return None;
+        };
+        let insert_allowed = style
+            .search(target, |node| insert_allowed(node, item))
+            .unwrap_or(false);
+        if !insert_allowed {
+            return None;
+        }
+        let Self::StyleItem {
+            item,
+            variables,
+            components,
+        } = self.prepare(style, None)?
+        else {
+            return None;
+        };
+
+        let item_id = item.id();
+        style.as_enum_mut().insert(item, &target, position);
+        add_variables(style, variables);
+        add_components(style, components);
+        Some(item_id)
+    }
+
+    /// Prepare the content to be pasted into a style.
+    ///
+    /// Variables and components that already exist in the style are used instead
+    /// of their copy. Every item, variable and component that is pasted gets a new id.
+    /// References to those ids are changed to the new ids.
+    /// A graphic item keeps only the attribute states that exist in the target states.
+    fn prepare(
+        self,
+        style: &StyleDefinition,
+        target_states: Option<&HashSet<GraphicStateId>>,
+    ) -> Option<Self> {
+        let existing_variables: HashSet<StyleId> = style
+            .vars
+            .contained_variables()
+            .into_iter()
+            .map(|variable| variable.id)
+            .collect();
+        let existing_components: HashSet<StyleId> = style
+            .components
+            .contained_graphics()
+            .into_iter()
+            .map(|component| component.id)
+            .collect();
+
+        let mut content = self;
+        let mut defined_ids = Vec::new();
+        match &mut content {
+            ClipboardContent::GraphicItem { item, .. } => {
+                defined_ids.extend(defined_graphic_item_ids(item));
+                if let Some(target_states) = target_states {
+                    let mut value = serde_json::to_value(&*item).ok()?;
+                    let target_states = target_states
+                        .iter()
+                        .map(|state| state.0.to_string())
+                        .collect();
+                    retain_states(&mut value, &target_states);
+                    *item = serde_json::from_value(value).ok()?;
+                }
+            }
+            ClipboardContent::StyleItem { item, .. } => {
+                defined_ids.extend(defined_style_item_ids(item));
+            }
+        }
+        let (ClipboardContent::GraphicItem {
+            variables,
+            components,
+            ..
+        }
+        | ClipboardContent::StyleItem {
+            variables,
+            components,
+            ..
+        }) = &mut content;
+        variables.retain(|copied| !existing_variables.contains(&copied.variable.id));
+        components.retain(|component| !existing_components.contains(&component.id));
+        defined_ids.extend(variables.iter().map(|copied| copied.variable.id.0));
+        defined_ids.extend(components.iter().flat_map(defined_graphic_ids));
+
+        let new_ids: HashMap<String, String> = defined_ids
+            .into_iter()
+            .map(|id| (id.to_string(), Uuid::new_v4().to_string()))
+            .collect();
+        let mut value = serde_json::to_value(&content).ok()?;
+        replace_ids(&mut value, &new_ids);
+        serde_json::from_value(value).ok()
+    }
+}
+
+/// Test if a style item can be inserted into the target.
+fn insert_allowed(target: &StyleItem, item: &StyleItem) -> bool {
+    match (target, item) {
+        (StyleItem::VariableFolder(_), StyleItem::VariableFolder(_)) => true,
+        (StyleItem::VariableFolder(_), StyleItem::Variable(_)) => true,
+
+        (StyleItem::AssetFolder(_), StyleItem::AssetFolder(_)) => true,
+        (StyleItem::AssetFolder(_), StyleItem::Asset(_)) => true,
+
+        (StyleItem::GraphicFolder(_), StyleItem::GraphicFolder(_)) => true,
+        (StyleItem::GraphicFolder(_), StyleItem::Graphic(_)) => true,
+
+        _ => false,
+    }
+}
+
+/// Add pasted variables to the style.
+/// Variables are placed in folders with the same names as the folders they were
+/// copied from. Missing folders are created.
+fn add_variables(style: &mut StyleDefinition, variables: Vec<CopiedVariable>) {
+    for CopiedVariable { folders, variable } in variables {
+        let mut folder: &mut VariableFolder = &mut style.vars;
+        for name in folders {
+            let index = folder
+                .content
+                .iter()
+                .position(|item| matches!(item, VariableOrFolder::Folder(f) if f.name == name))
+                .unwrap_or_else(|| {
+                    let mut new_folder = VariableFolder::new();
+                    new_folder.name = name;
+                    folder
+                        .content
+                        .push(VariableOrFolder::Folder(new_folder.into()));
+                    folder.content.len() - 1
+                });
+            let VariableOrFolder::Folder(next) = &mut folder.content[index] else {
+                unreachable!("The index was found for a folder");
+            };
+            folder = &mut **next;
+        }
+        folder
+            .content
+            .push(VariableOrFolder::Variable(variable.into()));
+    }
+}
+
+/// Add pasted components to the style.
+fn add_components(style: &mut StyleDefinition, components: Vec<GraphicDefinition>) {
+    style.components.content.extend(
+        components
+            .into_iter()
+            .map(|component| GraphicOrFolder::Graphic(component.into())),
+    );
+}
+
+/// The names of the folders that lead to a variable, not counting the root folder.
+fn variable_folders(folder: &VariableFolder, id: &StyleId) -> Option<Vec<String>> {
+    folder.content.iter().find_map(|item| match item {
+        VariableOrFolder::Variable(variable) => (variable.id == *id).then(Vec::new),
+        VariableOrFolder::Folder(sub_folder) => {
+            variable_folders(sub_folder, id).map(|mut folders| {
+                folders.insert(0, sub_folder.name.clone());
+                folders
+            })
+        }
+    })
+}
+
+/// The ids of all graphic items in the item and its children.
+fn defined_graphic_item_ids(item: &GraphicItem) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    item.walk(&mut |item, method| {
+        if method == Method::Visit {
+            ids.push(item.id().0);
+        }
+        ControlFlow::Continue::<()>(())
+    });
+    ids
+}
+
+/// The ids of everything that is defined in the style item and its children.
+fn defined_style_item_ids(item: &StyleItem) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    item.walk(&mut |item, method| {
+        if method == Method::Visit {
+            match item {
+                StyleItem::Graphic(graphic) => ids.extend(defined_graphic_ids(graphic)),
+                _ => ids.push(item.id().0),
+            }
+        }
+        ControlFlow::Continue::<()>(())
+    });
+    ids
+}
+
+/// The ids of the graphic and everything that is defined in it.
+fn defined_graphic_ids(graphic: &GraphicDefinition) -> Vec<Uuid> {
+    let mut ids = vec![graphic.id.0];
+    ids.extend(defined_graphic_item_ids(graphic.items.as_enum_ref()));
+    ids.extend(graphic.states.iter().map(|state| state.id.0));
+    ids.extend(graphic.parameters.iter().map(|parameter| parameter.id.0));
+    ids
+}
+
+/// Find the variables and components that are referenced by the value.
+/// Variables that are referenced by the components are included.
+fn references(
+    style: &StyleDefinition,
+    value: Value,
+    defined_ids: &[Uuid],
+) -> Option<(Vec<CopiedVariable>, Vec<GraphicDefinition>)> {
+    let components: Vec<GraphicDefinition> = referenced(
+        style.components.contained_graphics(),
+        |component| component.id,
+        &value,
+        defined_ids,
+    )
+    .into_iter()
+    .cloned()
+    .collect();
+    let value = Value::Array(vec![value, serde_json::to_value(&components).ok()?]);
+    let variables = referenced(
+        style.vars.contained_variables(),
+        |variable| variable.id,
+        &value,
+        defined_ids,
+    )
+    .into_iter()
+    .map(|variable| CopiedVariable {
+        folders: variable_folders(&style.vars, &variable.id).unwrap_or_default(),
+        variable: variable.clone(),
+    })
+    .collect();
+    Some((variables, components))
+}
+
+/// Find all candidates that are referenced by the value, directly or through
+/// other candidates. Candidates that are defined in the value itself are skipped.
+fn referenced<'a, T: Serialize>(
+    candidates: Vec<&'a T>,
+    id: impl Fn(&T) -> StyleId,
+    value: &Value,
+    defined_ids: &[Uuid],
+) -> Vec<&'a T> {
+    let mut referenced: Vec<&'a T> = Vec::new();
+    let mut pending = vec![value.clone()];
+    while let Some(value) = pending.pop() {
+        let mut strings = HashSet::new();
+        collect_strings(&value, &mut strings);
+        for candidate in candidates.iter() {
+            let candidate_id = id(*candidate);
+            let is_new = strings.contains(candidate_id.0.to_string().as_str())
+                && !defined_ids.contains(&candidate_id.0)
+                && !referenced.iter().any(|r| id(*r) == candidate_id);
+            if !is_new {
+                continue;
+            }
+            if let Ok(value) = serde_json::to_value(*candidate) {
+                pending.push(value);
+            }
+            referenced.push(*candidate);
+        }
+    }
+    referenced
+}
+
+/// Remove the values of attribute states that are not in the list of states.
+fn retain_states(value: &mut Value, states: &HashSet<String>) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(|v| retain_states(v, states)),
+        Value::Object(map) => {
+            let is_attribute = map.len() == 2 && map.contains_key("template");
+            if let (true, Some(Value::Object(attribute_states))) =
+                (is_attribute, map.get_mut("states"))
+            {
+                attribute_states.retain(|state, _| states.contains(state));
+            }
+            map.values_mut().for_each(|v| retain_states(v, states));
+        }
+        _ => (),
+    }
+}
+
+/// Collect all strings and object keys in a value.
+fn collect_strings<'a>(value: &'a Value, strings: &mut HashSet<&'a str>) {
+    match value {
+        Value::String(s) => {
+            strings.insert(s);
+        }
+        Value::Array(values) => values.iter().for_each(|v| collect_strings(v, strings)),
+        Value::Object(map) => {
+            for (key, value) in map.iter() {
+                strings.insert(key);
+                collect_strings(value, strings);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Replace every occurrence of an id in a value. Ids are replaced in strings
+/// and in object keys.
+fn replace_ids(value: &mut Value, new_ids: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(new_id) = new_ids.get(s.as_str()) {
+                *s = new_id.clone();
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(|v| replace_ids(v, new_ids)),
+        Value::Object(map) => {
+            *map = std::mem::take(map)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    replace_ids(&mut value, new_ids);
+                    (new_ids.get(&key).cloned().unwrap_or(key), value)
+                })
+                .collect();
+        }
+        _ => (),
+    }
+}
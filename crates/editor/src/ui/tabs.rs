@@ -1,17 +1,34 @@
 mod dashboard;
+mod reference_images;
+mod scenario;
+mod scene_view;
 mod secondary_editor;
-mod style_item;
+pub(super) mod style_item;
 mod style_item_tree;
 
-use backend::{graphic::GraphicStates, savefile::Savefile, GameAdapterResource};
-use bevy::ecs::system::{Res, ResMut, Resource};
-use bevy_egui::{egui, EguiContexts};
+use backend::{
+    graphic::{DrawnCells, GraphicStates},
+    savefile::Savefile,
+    GameAdapterResource,
+};
+use bevy::{
+    ecs::{
+        query::With,
+        system::{Query, Res, ResMut, Resource},
+    },
+    transform::components::Transform,
+};
+use bevy_egui::{egui, EguiContexts, EguiSettings};
 use egui_dock::{DockArea, DockState, NodeIndex, TabViewer};
-use unified_sim_model::Adapter;
 
-use crate::reference_store::ReferenceStore;
+use crate::{
+    camera::EditorCamera, reference_images::ReferenceImages, reference_store::ReferenceStore,
+    MainCamera,
+};
 
-use super::{EditorState, EditorStyle, UiMessage, UiMessages};
+use self::scene_view::SceneCamera;
+
+use super::{EditorState, EditorStyle, UiMessages};
 
 #[derive(Resource)]
 pub struct TabArea {
@@ -24,7 +41,11 @@ impl TabArea {
         let [scene, _tree_view] = tree.split_left(NodeIndex::root(), 0.15, vec![Tab::StyleItems]);
         let [scene, _component_editor] = tree.split_right(scene, 0.75, vec![Tab::StyleItemEditor]);
 
-        let [_scene, _element_editor] = tree.split_right(scene, 0.7, vec![Tab::GraphicItemEditor]);
+        let [_scene, _element_editor] = tree.split_right(
+            scene,
+            0.7,
+            vec![Tab::GraphicItemEditor, Tab::ReferenceImages, Tab::Scenarios],
+        );
 
         Self { dock_state: state }
     }
@@ -40,7 +61,16 @@ pub(super) fn tab_area(
     game_adapter: Res<GameAdapterResource>,
     mut graphic_states: ResMut<GraphicStates>,
     savefile: ResMut<Savefile>,
+    drawn_cells: Res<DrawnCells>,
+    camera: Query<(&Transform, &EditorCamera), With<MainCamera>>,
+    egui_settings: Res<EguiSettings>,
+    mut reference_images: ResMut<ReferenceImages>,
 ) {
+    let (camera_transform, editor_camera) = camera.single();
+    let scene_camera = SceneCamera {
+        position: camera_transform.translation.truncate(),
+        units_per_point: editor_camera.scale * egui_settings.scale_factor as f32,
+    };
     DockArea::new(&mut tab_area.dock_state)
         .style({
             let mut style = egui_dock::Style::from_egui(ctx.ctx_mut().style().as_ref());
@@ -64,9 +94,12 @@ pub(super) fn tab_area(
                 editor_style: &mut editor_style,
                 editor_state: &mut editor_state,
                 reference_store: &reference_store,
-                game_adapter: game_adapter.adapter(),
+                game_adapter: &game_adapter,
                 graphic_states: &mut graphic_states,
                 savefile: &savefile,
+                drawn_cells: &drawn_cells,
+                scene_camera: &scene_camera,
+                reference_images: &mut reference_images,
             },
         );
 }
@@ -77,6 +110,8 @@ enum Tab {
     StyleItems,
     StyleItemEditor,
     GraphicItemEditor,
+    ReferenceImages,
+    Scenarios,
 }
 
 struct EditorTabViewer<'a> {
@@ -84,9 +119,12 @@ struct EditorTabViewer<'a> {
     editor_style: &'a mut EditorStyle,
     editor_state: &'a mut EditorState,
     reference_store: &'a ReferenceStore,
-    game_adapter: Option<&'a Adapter>,
+    game_adapter: &'a GameAdapterResource,
     graphic_states: &'a mut GraphicStates,
     savefile: &'a Savefile,
+    drawn_cells: &'a DrawnCells,
+    scene_camera: &'a SceneCamera,
+    reference_images: &'a mut ReferenceImages,
 }
 impl<'a> TabViewer for EditorTabViewer<'a> {
     type Tab = Tab;
@@ -98,13 +136,22 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
             Tab::StyleItems => "Style".into(),
             Tab::StyleItemEditor => "Component".into(),
             Tab::GraphicItemEditor => "Element".into(),
+            Tab::ReferenceImages => "Reference images".into(),
+            Tab::Scenarios => "Scenarios".into(),
         }
     }
 
     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
         match tab {
             Tab::SceneView => {
-                self.messages.push(UiMessage::SceneViewport(ui.clip_rect()));
+                scene_view::scene_view(
+                    ui,
+                    self.messages,
+                    self.editor_style,
+                    self.editor_state,
+                    self.drawn_cells,
+                    self.scene_camera,
+                );
             }
             Tab::Dashboard => {
                 dashboard::dashboard(
@@ -124,7 +171,7 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                     self.editor_style,
                     self.editor_state,
                     self.reference_store,
-                    self.game_adapter,
+                    self.game_adapter.adapter(),
                 );
             }
             Tab::GraphicItemEditor => {
@@ -136,6 +183,12 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                     self.reference_store,
                 );
             }
+            Tab::ReferenceImages => {
+                reference_images::reference_images(ui, self.reference_images);
+            }
+            Tab::Scenarios => {
+                scenario::scenarios(ui, self.messages, self.savefile, self.game_adapter);
+            }
         }
     }
 
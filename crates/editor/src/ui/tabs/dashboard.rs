@@ -1,10 +1,9 @@
-use backend::{graphic::GraphicStates, style::StyleDefinition};
+use backend::{graphic::GraphicStates, style::StyleDefinition, GameAdapterResource};
 use bevy_egui::egui::{self, pos2, vec2, Align, Layout, Rect, Sense, Ui};
-use unified_sim_model::Adapter;
 
 pub fn dashboard(
     ui: &mut Ui,
-    adapter: Option<&Adapter>,
+    game_adapter: &GameAdapterResource,
     style: &StyleDefinition,
     graphic_states: &mut GraphicStates,
 ) {
@@ -12,7 +11,7 @@ pub fn dashboard(
         ui,
         300.0,
         |ui| {
-            backend::ui::dashboard::show_entry_table(ui, adapter);
+            backend::ui::dashboard::show_entry_table(ui, game_adapter);
         },
         |ui| {
             for graphic in style.graphics.contained_graphics() {
@@ -0,0 +1,112 @@
+use bevy_egui::egui::{self, DragValue, Slider, Ui};
+
+use crate::{
+    reference_images::{ReferenceBlend, ReferenceImage, ReferenceImages},
+    ui::combo_box::LComboBox,
+};
+
+use super::secondary_editor::ui_split;
+
+pub(super) fn reference_images(ui: &mut Ui, reference_images: &mut ReferenceImages) {
+    let mut changed = false;
+    let mut remove = None;
+
+    let errors: Vec<Option<String>> = reference_images
+        .images
+        .iter()
+        .map(|image| reference_images.error(&image.id).map(str::to_owned))
+        .collect();
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for (index, (image, error)) in reference_images
+                .images
+                .iter_mut()
+                .zip(errors.iter())
+                .enumerate()
+            {
+                ui.push_id(image.id, |ui| {
+                    changed |= reference_image_editor(ui, image);
+                    if let Some(error) = error {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    if ui.button("Remove").clicked() {
+                        remove = Some(index);
+                    }
+                });
+                ui.separator();
+            }
+
+            if ui.button("Add reference image").clicked() {
+                let file = ReferenceImages::available_files()
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                reference_images.images.push(ReferenceImage::new(file));
+                changed = true;
+            }
+        });
+
+    if let Some(index) = remove {
+        reference_images.images.remove(index);
+        changed = true;
+    }
+    reference_images.unsaved |= changed;
+    // Only write the workspace once the user has stopped dragging or typing.
+    let editing = ui.memory(|m| m.is_anything_being_dragged() || m.focus().is_some());
+    if reference_images.unsaved && !editing {
+        reference_images.save();
+    }
+}
+
+fn reference_image_editor(ui: &mut Ui, image: &mut ReferenceImage) -> bool {
+    let mut changed = false;
+    ui_split(ui, "Show", |ui| {
+        changed |= ui.checkbox(&mut image.enabled, "").changed();
+    });
+    ui_split(ui, "File", |ui| {
+        egui::ComboBox::from_id_source(ui.next_auto_id())
+            .selected_text(&image.file)
+            .width(ui.available_width())
+            .show_ui(ui, |ui| {
+                for file in ReferenceImages::available_files() {
+                    let label = file.clone();
+                    changed |= ui.selectable_value(&mut image.file, file, label).changed();
+                }
+            });
+    });
+    ui_split(ui, "Opacity", |ui| {
+        changed |= ui.add(Slider::new(&mut image.opacity, 0.0..=1.0)).changed();
+    });
+    ui_split(ui, "Blend", |ui| {
+        changed |= ui
+            .add(
+                LComboBox::new(&mut image.blend)
+                    .with_id(ui.make_persistent_id("Reference image blend"))
+                    .add_option(ReferenceBlend::Background, "Background")
+                    .add_option(ReferenceBlend::OnionSkin, "Onion skin")
+                    .add_option(ReferenceBlend::InvertedOverlay, "Inverted overlay"),
+            )
+            .on_hover_text(
+                "Inverted overlay approximates a difference view. Matching areas turn gray, \
+                but differences are not shown as the absolute difference of the images.",
+            )
+            .changed();
+    });
+    ui_split(ui, "Position X", |ui| {
+        changed |= ui.add(DragValue::new(&mut image.position.x)).changed();
+    });
+    ui_split(ui, "Y", |ui| {
+        changed |= ui.add(DragValue::new(&mut image.position.y)).changed();
+    });
+    ui_split(ui, "Scale", |ui| {
+        changed |= ui
+            .add(
+                DragValue::new(&mut image.scale)
+                    .clamp_range(0.01..=f32::MAX)
+                    .speed(0.01),
+            )
+            .changed();
+    });
+    changed
+}
@@ -0,0 +1,392 @@
+//! Scenarios put the game adapter in a known state to test a style against.
+//!
+//! Scenarios are stored in a file next to the style.
+//! The dummy adapter creates the entries of a scenario. The values set by the
+//! scenario are then applied on top of the model of the adapter.
+
+use std::{fs, path::PathBuf};
+
+use backend::{
+    savefile::Savefile,
+    scenario::{EntryOverride, Scenario, ScenarioSessionPhase, ScenarioSessionType},
+    GameAdapterResource,
+};
+use bevy::asset::io::file::FileAssetReader;
+use bevy_egui::egui::{self, DragValue, Ui};
+use tracing::error;
+
+use crate::ui::{combo_box::LComboBox, UiMessage, UiMessages};
+
+use super::secondary_editor::ui_split;
+
+/// File that stores the scenarios. Relative to the folder of the style.
+const SCENARIO_FILE: &str = "scenarios.json";
+
+/// The scenarios of a style file.
+#[derive(Clone, Default)]
+struct Scenarios {
+    file: PathBuf,
+    scenarios: Vec<Scenario>,
+    /// If the scenarios were changed since they were last written to the file.
+    unsaved: bool,
+}
+impl Scenarios {
+    fn load(file: PathBuf) -> Self {
+        let scenarios = fs::read_to_string(&file)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self {
+            file,
+            scenarios,
+            unsaved: false,
+        }
+    }
+
+    fn save(&mut self) {
+        self.unsaved = false;
+        let s = match serde_json::to_string_pretty(&self.scenarios) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Error turning scenarios into string: {e}");
+                return;
+            }
+        };
+        if let Err(e) = fs::write(&self.file, s) {
+            error!("Cannot write scenario file: {e}");
+        }
+    }
+}
+
+pub(super) fn scenarios(
+    ui: &mut Ui,
+    messages: &mut UiMessages,
+    savefile: &Savefile,
+    game_adapter: &GameAdapterResource,
+) {
+    let file = FileAssetReader::get_base_path()
+        .join(savefile.base_path())
+        .join(SCENARIO_FILE);
+
+    // The scenarios are kept in memory and only read again when the style changes.
+    let data_id = ui.make_persistent_id("Scenarios");
+    let mut scenarios = ui
+        .data(|d| d.get_temp::<Scenarios>(data_id))
+        .filter(|scenarios| scenarios.file == file)
+        .unwrap_or_else(|| Scenarios::load(file));
+
+    let connected = game_adapter
+        .adapter()
+        .is_some_and(|adapter| !adapter.is_finished());
+    if !connected {
+        ui.label("Connect the dummy adapter to apply a scenario.");
+    }
+    let active_id = game_adapter.scenario().map(|scenario| scenario.id);
+    let session = SessionInfo::new(game_adapter);
+
+    let mut changed = false;
+    let mut remove = None;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for (index, scenario) in scenarios.scenarios.iter_mut().enumerate() {
+                let active = active_id == Some(scenario.id);
+                ui.push_id(scenario.id, |ui| {
+                    let scenario_changed = scenario_editor(ui, scenario, &session);
+                    if scenario_changed && active {
+                        messages.push(UiMessage::GameAdapterSetScenario(Some(scenario.clone())));
+                    }
+                    changed |= scenario_changed;
+                    ui.horizontal(|ui| {
+                        ui.add_enabled_ui(connected, |ui| {
+                            if ui.button("Apply").clicked() {
+                                apply(scenario, messages);
+                            }
+                        });
+                        if active && ui.button("Stop").clicked() {
+                            messages.push(UiMessage::GameAdapterSetScenario(None));
+                        }
+                        if ui.button("Remove").clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                });
+                ui.separator();
+            }
+
+            if ui.button("Add scenario").clicked() {
+                scenarios.scenarios.push(Scenario::new());
+                changed = true;
+            }
+        });
+
+    if let Some(index) = remove {
+        let scenario = scenarios.scenarios.remove(index);
+        if active_id == Some(scenario.id) {
+            messages.push(UiMessage::GameAdapterSetScenario(None));
+        }
+        changed = true;
+    }
+    scenarios.unsaved |= changed;
+    // Only write the file once the user has stopped dragging or typing.
+    let editing = ui.memory(|m| m.is_anything_being_dragged() || m.focus().is_some());
+    if scenarios.unsaved && !editing {
+        scenarios.save();
+    }
+    ui.data_mut(|d| d.insert_temp(data_id, scenarios));
+}
+
+fn apply(scenario: &Scenario, messages: &mut UiMessages) {
+    // The dummy adapter generates the rest of the session to match the session type.
+    if let Some(session_type) = scenario.session.session_type {
+        messages.push(UiMessage::GameAdapterDummySetSessionType(
+            session_type.into(),
+        ));
+    }
+    messages.push(UiMessage::GameAdapterDummySetEntryAmount(
+        scenario.entry_amount,
+    ));
+    messages.push(UiMessage::GameAdapterSetScenario(Some(scenario.clone())));
+}
+
+/// The entries and classes of the current session to choose from.
+struct SessionInfo {
+    /// The id of each entry and a label to show for it. Ordered by position.
+    entries: Vec<(i32, String)>,
+    classes: Vec<String>,
+}
+impl SessionInfo {
+    fn new(game_adapter: &GameAdapterResource) -> Self {
+        let mut info = Self {
+            entries: Vec::new(),
+            classes: Vec::new(),
+        };
+        let Some(model) = game_adapter.model() else {
+            return info;
+        };
+        let Some(session) = model.current_session() else {
+            return info;
+        };
+
+        let mut entries: Vec<_> = session.entries.values().collect();
+        entries.sort_by_key(|entry| *entry.position);
+        info.entries = entries
+            .into_iter()
+            .map(|entry| {
+                let name = entry
+                    .drivers
+                    .get(&entry.current_driver)
+                    .map(|driver| format!("{} {}", *driver.first_name, *driver.last_name))
+                    .unwrap_or_default();
+                (entry.id.0, format!("#{} {}", *entry.car_number, name))
+            })
+            .collect();
+
+        info.classes = session
+            .entries
+            .values()
+            .map(|entry| entry.car.category().name.to_string())
+            .collect();
+        info.classes.sort();
+        info.classes.dedup();
+        info
+    }
+}
+
+fn scenario_editor(ui: &mut Ui, scenario: &mut Scenario, session: &SessionInfo) -> bool {
+    let mut changed = false;
+    ui_split(ui, "Name", |ui| {
+        changed |= ui.text_edit_singleline(&mut scenario.name).changed();
+    });
+    ui_split(ui, "Entries", |ui| {
+        changed |= ui
+            .add(DragValue::new(&mut scenario.entry_amount).clamp_range(1..=usize::MAX))
+            .changed();
+    });
+    ui_split(ui, "Session", |ui| {
+        changed |= optional(
+            ui,
+            &mut scenario.session.session_type,
+            ScenarioSessionType::Race,
+            |ui, session_type| {
+                ui.add(
+                    LComboBox::new(session_type)
+                        .with_id(ui.make_persistent_id("Scenario session type"))
+                        .add_option(ScenarioSessionType::Race, "Race")
+                        .add_option(ScenarioSessionType::Qualifying, "Qualifying")
+                        .add_option(ScenarioSessionType::Practice, "Practice"),
+                )
+                .changed()
+            },
+        );
+    });
+    ui_split(ui, "Phase", |ui| {
+        changed |= optional(
+            ui,
+            &mut scenario.session.phase,
+            ScenarioSessionPhase::Active,
+            |ui, phase| {
+                ui.add(
+                    LComboBox::new(phase)
+                        .with_id(ui.make_persistent_id("Scenario session phase"))
+                        .add_option(ScenarioSessionPhase::Waiting, "Waiting")
+                        .add_option(ScenarioSessionPhase::Preparing, "Preparing")
+                        .add_option(ScenarioSessionPhase::Formation, "Formation")
+                        .add_option(ScenarioSessionPhase::Active, "Active")
+                        .add_option(ScenarioSessionPhase::Ending, "Ending")
+                        .add_option(ScenarioSessionPhase::Finished, "Finished"),
+                )
+                .changed()
+            },
+        );
+    });
+    ui_split(ui, "Time remaining", |ui| {
+        changed |= optional(
+            ui,
+            &mut scenario.session.time_remaining,
+            600.0,
+            seconds_edit,
+        );
+    });
+
+    ui.label("Entry overrides:");
+    let mut remove = None;
+    for (index, entry) in scenario.entries.iter_mut().enumerate() {
+        ui.push_id(index, |ui| {
+            changed |= entry_editor(ui, entry, session);
+            if ui.button("Remove entry").clicked() {
+                remove = Some(index);
+            }
+        });
+        ui.separator();
+    }
+    if let Some(index) = remove {
+        scenario.entries.remove(index);
+        changed = true;
+    }
+    if ui.button("Add entry").clicked() {
+        scenario.entries.push(EntryOverride::default());
+        changed = true;
+    }
+    changed
+}
+
+fn entry_editor(ui: &mut Ui, entry: &mut EntryOverride, session: &SessionInfo) -> bool {
+    let mut changed = false;
+    ui_split(ui, "Entry", |ui| {
+        let selected_text = session
+            .entries
+            .iter()
+            .find(|(id, _)| *id == entry.entry_id)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_else(|| format!("Entry {}", entry.entry_id));
+        egui::ComboBox::from_id_source(ui.next_auto_id())
+            .width(ui.available_width())
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for (id, label) in session.entries.iter() {
+                    let is_selected = entry.entry_id == *id;
+                    if ui.selectable_label(is_selected, label).clicked() && !is_selected {
+                        entry.entry_id = *id;
+                        changed = true;
+                    }
+                }
+            });
+    });
+    ui_split(ui, "First name", |ui| {
+        changed |= optional(ui, &mut entry.first_name, String::new(), |ui, name| {
+            ui.text_edit_singleline(name).changed()
+        });
+    });
+    ui_split(ui, "Last name", |ui| {
+        changed |= optional(ui, &mut entry.last_name, String::new(), |ui, name| {
+            ui.text_edit_singleline(name).changed()
+        });
+    });
+    ui_split(ui, "Number", |ui| {
+        changed |= optional(ui, &mut entry.car_number, 1, |ui, number| {
+            ui.add(DragValue::new(number).clamp_range(0..=999))
+                .changed()
+        });
+    });
+    ui_split(ui, "Class", |ui| {
+        let default_class = session.classes.first().cloned().unwrap_or_default();
+        changed |= optional(ui, &mut entry.class, default_class, |ui, class| {
+            let mut class_changed = false;
+            egui::ComboBox::from_id_source(ui.next_auto_id())
+                .width(ui.available_width())
+                .selected_text(class.as_str())
+                .show_ui(ui, |ui| {
+                    for name in session.classes.iter() {
+                        let is_selected = *class == *name;
+                        if ui.selectable_label(is_selected, name).clicked() && !is_selected {
+                            *class = name.clone();
+                            class_changed = true;
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "The entry takes the car of another entry in this class. \
+                    Only classes that are already in the session can be used.",
+                );
+            class_changed
+        });
+    });
+    ui_split(ui, "Position", |ui| {
+        changed |= optional(ui, &mut entry.position, 1, |ui, position| {
+            ui.add(DragValue::new(position).clamp_range(1..=i32::MAX))
+                .changed()
+        });
+    });
+    ui_split(ui, "Gap to leader", |ui| {
+        changed |= optional(ui, &mut entry.gap_to_leader, 0.0, seconds_edit);
+    });
+    ui_split(ui, "In pits", |ui| {
+        changed |= optional(ui, &mut entry.in_pits, true, |ui, in_pits| {
+            ui.checkbox(in_pits, "").changed()
+        });
+    });
+    ui_split(ui, "Laps", |ui| {
+        changed |= optional(ui, &mut entry.lap_count, 0, |ui, laps| {
+            ui.add(DragValue::new(laps).clamp_range(0..=i32::MAX))
+                .changed()
+        });
+    });
+    ui_split(ui, "Best lap", |ui| {
+        changed |= optional(ui, &mut entry.best_lap, 90.0, seconds_edit);
+    });
+    ui_split(ui, "Current lap", |ui| {
+        changed |= optional(ui, &mut entry.current_lap, 0.0, seconds_edit);
+    });
+    changed
+}
+
+/// Edit a value that is only set by the scenario if the checkbox is ticked.
+fn optional<T>(
+    ui: &mut Ui,
+    value: &mut Option<T>,
+    default: T,
+    add_contents: impl FnOnce(&mut Ui, &mut T) -> bool,
+) -> bool {
+    let mut changed = false;
+    let mut enabled = value.is_some();
+    if ui.checkbox(&mut enabled, "").changed() {
+        *value = enabled.then_some(default);
+        changed = true;
+    }
+    if let Some(value) = value {
+        changed |= add_contents(ui, value);
+    }
+    changed
+}
+
+fn seconds_edit(ui: &mut Ui, seconds: &mut f64) -> bool {
+    ui.add(
+        DragValue::new(seconds)
+            .clamp_range(0.0..=f64::MAX)
+            .speed(0.1)
+            .suffix("s"),
+    )
+    .changed()
+}
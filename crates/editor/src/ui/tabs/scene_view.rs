@@ -0,0 +1,627 @@
+use backend::{
+    graphic::{DrawnCell, DrawnCells},
+    style::{
+        graphic::{
+            graphic_items::{cell::Cell, GraphicItem, GraphicItemId},
+            GraphicStateId, TEMPLATE_ID,
+        },
+        StyleId, StyleItem,
+    },
+    tree_iterator::{TreeIterator, TreeIteratorMut},
+};
+use bevy::math::{vec2, Vec2};
+use bevy_egui::egui::{
+    self, pos2, Color32, DragValue, Id, PointerButton, Pos2, Rect, Sense, Shape, Stroke, Ui,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::ui::{EditorState, EditorStyle, UiMessage, UiMessages};
+
+use super::secondary_editor::{editable_attribute, fixed, set_fixed};
+
+/// Distance in points in which a cell snaps to a target.
+const SNAP_DISTANCE: f32 = 6.0;
+/// Size of a resize handle in points.
+const HANDLE_SIZE: f32 = 8.0;
+
+const SELECTION_COLOR: Color32 = Color32::from_rgb(0, 160, 255);
+const GUIDE_COLOR: Color32 = Color32::from_rgb(255, 0, 200);
+
+/// The position and zoom of the scene camera.
+pub(super) struct SceneCamera {
+    /// Position of the camera in world space.
+    pub position: Vec2,
+    /// Number of world units in one point of the ui.
+    pub units_per_point: f32,
+}
+
+/// Transforms between world space and screen space of the scene view.
+struct SceneTransform<'a> {
+    camera: &'a SceneCamera,
+    center: Pos2,
+}
+impl<'a> SceneTransform<'a> {
+    fn to_screen(&self, world: Vec2) -> Pos2 {
+        let offset = (world - self.camera.position) / self.camera.units_per_point;
+        self.center + egui::vec2(offset.x, -offset.y)
+    }
+
+    fn to_world(&self, screen: Pos2) -> Vec2 {
+        let offset = (screen - self.center) * self.camera.units_per_point;
+        self.camera.position + vec2(offset.x, -offset.y)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SceneViewSettings {
+    snap_to_cells: bool,
+    snap_to_grid: bool,
+    grid_size: f32,
+}
+impl Default for SceneViewSettings {
+    fn default() -> Self {
+        Self {
+            snap_to_cells: true,
+            snap_to_grid: false,
+            grid_size: 10.0,
+        }
+    }
+}
+
+/// A cell that is currently being dragged.
+#[derive(Clone)]
+struct Drag {
+    graphic_id: StyleId,
+    cell: DrawnCell,
+    /// The handle that is dragged. Each axis is 0 for the start of the cell,
+    /// 1 for the end of the cell and 0.5 if the axis is not resized.
+    /// Moves the cell if no handle is dragged.
+    handle: Option<Vec2>,
+    /// Position of the pointer in world space when the drag started.
+    start_pointer: Vec2,
+    start_position: Vec2,
+    start_size: Vec2,
+    /// The rounded position and size that were last written to the cell.
+    applied: (Vec2, Vec2),
+}
+
+/// A line the dragged cell was snapped to.
+enum Guide {
+    Vertical(f32),
+    Horizontal(f32),
+}
+
+pub(super) fn scene_view(
+    ui: &mut Ui,
+    messages: &mut UiMessages,
+    editor_style: &mut EditorStyle,
+    editor_state: &mut EditorState,
+    drawn_cells: &DrawnCells,
+    camera: &SceneCamera,
+) {
+    let rect = ui.clip_rect();
+    messages.push(UiMessage::SceneViewport(rect));
+
+    let transform = SceneTransform {
+        camera,
+        center: rect.center(),
+    };
+    let settings_id = ui.make_persistent_id("Scene view settings");
+    let mut settings: SceneViewSettings = ui
+        .data_mut(|d| d.get_persisted(settings_id))
+        .unwrap_or_default();
+    let drag_id = ui.make_persistent_id("Scene view drag");
+    let mut drag: Option<Drag> = ui.data_mut(|d| d.get_temp(drag_id));
+    let pan_id = ui.make_persistent_id("Scene view pan");
+    let mut pan: bool = ui.data_mut(|d| d.get_temp(pan_id)).unwrap_or(false);
+
+    let selected_graphic = editor_state.style_item_tree_state.selected();
+    let selected_cell = selected_graphic.and_then(|graphic_id| {
+        let item_id = editor_state
+            .style_item_selection_data
+            .get(&graphic_id)?
+            .graphic_item_tree_state
+            .selected()?;
+        let cell = drawn_cells
+            .graphics
+            .get(&graphic_id)?
+            .iter()
+            .find(|cell| cell.id == item_id)?;
+        Some((graphic_id, cell))
+    });
+
+    let response = ui.interact(rect, ui.id().with("Scene view"), Sense::click_and_drag());
+    let pointer = response.interact_pointer_pos().or(response.hover_pos());
+
+    if response.clicked() {
+        let toggle = ui.input(|i| i.modifiers.command);
+        match pointer.and_then(|pointer| cell_at(drawn_cells, transform.to_world(pointer))) {
+            Some((graphic_id, cell)) if toggle && selected_graphic == Some(graphic_id) => {
+                toggle_selection(editor_state, graphic_id, cell.id)
+            }
+            Some((graphic_id, cell)) => select(editor_state, graphic_id, cell.id),
+            None => deselect(editor_state),
+        }
+    }
+
+    if response.drag_started_by(PointerButton::Primary) {
+        drag = pointer.and_then(|pointer| {
+            let handle = selected_cell.and_then(|(graphic_id, cell)| {
+                handle_at(cell, &transform, pointer).map(|handle| (graphic_id, cell, handle))
+            });
+            let (graphic_id, cell, handle) = match handle {
+                Some((graphic_id, cell, handle)) => (graphic_id, cell, Some(handle)),
+                None => {
+                    let (graphic_id, cell) = cell_at(drawn_cells, transform.to_world(pointer))?;
+                    select(editor_state, graphic_id, cell.id);
+                    (graphic_id, cell, None)
+                }
+            };
+            let state_id = selected_state(editor_state, graphic_id);
+            let (start_position, start_size) =
+                find_cell(editor_style, graphic_id, cell.id, |cell| {
+                    let position = editable_attribute(&mut cell.pos, state_id)?;
+                    let position = vec2(fixed(&position.x)?, fixed(&position.y)?);
+                    let size = editable_attribute(&mut cell.size, state_id)?;
+                    let size = vec2(fixed(&size.x)?, fixed(&size.y)?);
+                    Some((position, size))
+                })?;
+            Some(Drag {
+                graphic_id,
+                cell: cell.clone(),
+                handle,
+                start_pointer: transform.to_world(pointer),
+                start_position,
+                start_size,
+                applied: (start_position.round(), start_size.round()),
+            })
+        });
+        // Dragging empty space pans the camera.
+        pan = drag.is_none()
+            && pointer
+                .and_then(|pointer| cell_at(drawn_cells, transform.to_world(pointer)))
+                .is_none();
+    }
+    if pan && response.dragged_by(PointerButton::Primary) {
+        let delta = response.drag_delta() * camera.units_per_point;
+        messages.push(UiMessage::ScenePan(vec2(-delta.x, delta.y)));
+    }
+
+    let mut guides = Vec::new();
+    if let Some((drag, pointer)) = drag.as_mut().zip(pointer) {
+        if response.dragged_by(PointerButton::Primary) {
+            let snap_targets = SnapTargets::new(drawn_cells, drag.cell.id, &settings);
+            let snap_distance = SNAP_DISTANCE * camera.units_per_point;
+            let pointer = transform.to_world(pointer);
+            let (position, size) = match drag.handle {
+                None => move_cell(drag, pointer, &snap_targets, snap_distance, &mut guides),
+                Some(handle) => resize_cell(
+                    drag,
+                    handle,
+                    pointer,
+                    &snap_targets,
+                    snap_distance,
+                    &mut guides,
+                ),
+            };
+            // Only edit the style when the cell moved by at least one unit.
+            let rounded = (position.round(), size.round());
+            let changed = rounded != drag.applied;
+            drag.applied = rounded;
+            let state_id = selected_state(editor_state, drag.graphic_id);
+            let graphic = if changed {
+                find_cell(editor_style, drag.graphic_id, drag.cell.id, |cell| {
+                    if let Some(attr) = editable_attribute(&mut cell.pos, state_id) {
+                        set_fixed(&mut attr.x, position.x);
+                        set_fixed(&mut attr.y, position.y);
+                    }
+                    if let Some(attr) = editable_attribute(&mut cell.size, state_id) {
+                        set_fixed(&mut attr.x, size.x);
+                        set_fixed(&mut attr.y, size.y);
+                    }
+                    Some(())
+                })
+                .and_then(|_| editor_style.0.search(drag.graphic_id, |item| item.clone()))
+            } else {
+                None
+            };
+            if let Some(graphic) = graphic {
+                messages.push(UiMessage::StyleItemEdit {
+                    widget_id: Id::new(("Scene view drag", drag.cell.id)),
+                    item: graphic,
+                });
+            }
+        }
+    }
+    if !response.dragged_by(PointerButton::Primary) {
+        drag = None;
+        pan = false;
+    }
+
+    let painter = ui.painter_at(rect);
+    if settings.snap_to_grid {
+        paint_grid(&painter, &transform, rect, settings.grid_size);
+    }
+    if drag.is_none() {
+        if let Some((_, cell)) =
+            pointer.and_then(|pointer| cell_at(drawn_cells, transform.to_world(pointer)))
+        {
+            painter.add(cell_outline(
+                cell,
+                &transform,
+                Stroke::new(1.0, SELECTION_COLOR.linear_multiply(0.5)),
+            ));
+        }
+    }
+    if let Some(graphic_id) = editor_state.style_item_tree_state.selected() {
+        let selection = editor_state
+            .style_item_selection_data
+            .get(&graphic_id)
+            .map(|selection_data| selection_data.selected_graphic_items())
+            .unwrap_or_default();
+        for cell in drawn_cells
+            .graphics
+            .get(&graphic_id)
+            .into_iter()
+            .flatten()
+            .filter(|cell| selection.len() > 1 && selection.contains(&cell.id))
+        {
+            painter.add(cell_outline(
+                cell,
+                &transform,
+                Stroke::new(1.5, SELECTION_COLOR),
+            ));
+        }
+    }
+    let selected_cell = drag
+        .as_ref()
+        .and_then(|drag| {
+            drawn_cells
+                .graphics
+                .get(&drag.graphic_id)?
+                .iter()
+                .find(|cell| cell.id == drag.cell.id)
+        })
+        .or(selected_cell.map(|(_, cell)| cell));
+    if let Some(cell) = selected_cell {
+        painter.add(cell_outline(
+            cell,
+            &transform,
+            Stroke::new(1.5, SELECTION_COLOR),
+        ));
+        for handle in HANDLES {
+            painter.rect(
+                handle_rect(cell, &transform, handle),
+                0.0,
+                Color32::WHITE,
+                Stroke::new(1.0, SELECTION_COLOR),
+            );
+        }
+    }
+    for guide in guides {
+        let (start, end) = match guide {
+            Guide::Vertical(x) => {
+                let x = transform.to_screen(vec2(x, 0.0)).x;
+                (pos2(x, rect.top()), pos2(x, rect.bottom()))
+            }
+            Guide::Horizontal(y) => {
+                let y = transform.to_screen(vec2(0.0, y)).y;
+                (pos2(rect.left(), y), pos2(rect.right(), y))
+            }
+        };
+        painter.line_segment([start, end], Stroke::new(1.0, GUIDE_COLOR));
+    }
+
+    ui.allocate_ui_at_rect(rect.shrink(5.0), |ui| {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut settings.snap_to_cells, "Snap to cells");
+            ui.checkbox(&mut settings.snap_to_grid, "Snap to grid");
+            ui.add_enabled(
+                settings.snap_to_grid,
+                DragValue::new(&mut settings.grid_size)
+                    .clamp_range(1.0..=f32::MAX)
+                    .speed(0.1),
+            );
+        });
+    });
+
+    ui.data_mut(|d| {
+        d.insert_persisted(settings_id, settings);
+        match drag {
+            Some(drag) => d.insert_temp(drag_id, drag),
+            None => d.remove::<Drag>(drag_id),
+        }
+        d.insert_temp(pan_id, pan);
+    });
+}
+
+/// The handles of a cell in the local space of the cell relative to its size.
+const HANDLES: [Vec2; 8] = [
+    Vec2::new(0.0, 0.0),
+    Vec2::new(0.5, 0.0),
+    Vec2::new(1.0, 0.0),
+    Vec2::new(1.0, 0.5),
+    Vec2::new(1.0, 1.0),
+    Vec2::new(0.5, 1.0),
+    Vec2::new(0.0, 1.0),
+    Vec2::new(0.0, 0.5),
+];
+
+fn handle_rect(cell: &DrawnCell, transform: &SceneTransform, handle: Vec2) -> Rect {
+    Rect::from_center_size(
+        transform.to_screen(cell.to_world(handle * cell.size)),
+        egui::vec2(HANDLE_SIZE, HANDLE_SIZE),
+    )
+}
+
+/// Find the handle of a cell at a position on screen.
+fn handle_at(cell: &DrawnCell, transform: &SceneTransform, pointer: Pos2) -> Option<Vec2> {
+    HANDLES
+        .into_iter()
+        .find(|handle| handle_rect(cell, transform, *handle).contains(pointer))
+}
+
+/// Find the top most cell at a position in world space.
+fn cell_at(drawn_cells: &DrawnCells, point: Vec2) -> Option<(StyleId, &DrawnCell)> {
+    drawn_cells
+        .graphics
+        .iter()
+        .flat_map(|(graphic_id, cells)| cells.iter().map(|cell| (*graphic_id, cell)))
+        .filter(|(_, cell)| cell.contains(point))
+        .max_by(|(_, a), (_, b)| a.position.z.total_cmp(&b.position.z))
+}
+
+fn cell_outline(cell: &DrawnCell, transform: &SceneTransform, stroke: Stroke) -> Shape {
+    Shape::closed_line(
+        corners(cell)
+            .into_iter()
+            .map(|corner| transform.to_screen(corner))
+            .collect(),
+        stroke,
+    )
+}
+
+/// The corners of a cell in world space.
+fn corners(cell: &DrawnCell) -> [Vec2; 4] {
+    [
+        cell.to_world(Vec2::ZERO),
+        cell.to_world(vec2(cell.size.x, 0.0)),
+        cell.to_world(cell.size),
+        cell.to_world(vec2(0.0, cell.size.y)),
+    ]
+}
+
+fn paint_grid(painter: &egui::Painter, transform: &SceneTransform, rect: Rect, grid_size: f32) {
+    // Skip the grid when the lines would be too close to each other.
+    if grid_size / transform.camera.units_per_point < 8.0 {
+        return;
+    }
+    let stroke = Stroke::new(1.0, Color32::from_white_alpha(15));
+    let min = transform.to_world(rect.left_bottom());
+    let max = transform.to_world(rect.right_top());
+    let mut x = (min.x / grid_size).ceil() * grid_size;
+    while x <= max.x {
+        let screen_x = transform.to_screen(vec2(x, 0.0)).x;
+        painter.vline(screen_x, rect.y_range(), stroke);
+        x += grid_size;
+    }
+    let mut y = (min.y / grid_size).ceil() * grid_size;
+    while y <= max.y {
+        let screen_y = transform.to_screen(vec2(0.0, y)).y;
+        painter.hline(rect.x_range(), screen_y, stroke);
+        y += grid_size;
+    }
+}
+
+fn select(editor_state: &mut EditorState, graphic_id: StyleId, item_id: GraphicItemId) {
+    editor_state
+        .style_item_tree_state
+        .set_selected(Some(graphic_id));
+    editor_state
+        .style_item_tree_state
+        .expand_parents_of(graphic_id, false);
+    let selection_data = editor_state
+        .style_item_selection_data
+        .entry(graphic_id)
+        .or_default();
+    selection_data.graphic_item_multi_selection.clear();
+    selection_data
+        .graphic_item_tree_state
+        .set_selected(Some(item_id));
+    selection_data
+        .graphic_item_tree_state
+        .expand_parents_of(item_id, false);
+}
+
+/// Add an item to the selection or remove it if it is already selected.
+fn toggle_selection(editor_state: &mut EditorState, graphic_id: StyleId, item_id: GraphicItemId) {
+    let selection_data = editor_state
+        .style_item_selection_data
+        .entry(graphic_id)
+        .or_default();
+    let mut selection = selection_data.selected_graphic_items();
+    match selection.iter().position(|selected| *selected == item_id) {
+        Some(index) => {
+            selection.remove(index);
+        }
+        None => selection.push(item_id),
+    }
+    selection_data
+        .graphic_item_tree_state
+        .set_selected(selection.last().copied());
+    selection_data.graphic_item_multi_selection = selection;
+}
+
+fn deselect(editor_state: &mut EditorState) {
+    if let Some(selection_data) = editor_state
+        .style_item_tree_state
+        .selected()
+        .and_then(|graphic_id| editor_state.style_item_selection_data.get_mut(&graphic_id))
+    {
+        selection_data.graphic_item_tree_state.set_selected(None);
+    }
+}
+
+fn selected_state(editor_state: &EditorState, graphic_id: StyleId) -> GraphicStateId {
+    editor_state
+        .style_item_selection_data
+        .get(&graphic_id)
+        .and_then(|selection_data| selection_data.graphic_state_tree_state.selected())
+        .unwrap_or(TEMPLATE_ID)
+}
+
+fn find_cell<R>(
+    editor_style: &mut EditorStyle,
+    graphic_id: StyleId,
+    cell_id: GraphicItemId,
+    action: impl FnOnce(&mut Cell) -> Option<R>,
+) -> Option<R> {
+    editor_style
+        .0
+        .search_mut(graphic_id, |item| match item {
+            StyleItem::Graphic(graphic) => graphic
+                .items
+                .search_mut(cell_id, |item| match item {
+                    GraphicItem::Cell(cell) => action(cell),
+                    _ => None,
+                })
+                .flatten(),
+            _ => None,
+        })
+        .flatten()
+}
+
+/// The values in world space a dragged cell can snap to.
+struct SnapTargets {
+    x: Vec<f32>,
+    y: Vec<f32>,
+    grid_size: Option<f32>,
+}
+impl SnapTargets {
+    fn new(drawn_cells: &DrawnCells, dragged: GraphicItemId, settings: &SceneViewSettings) -> Self {
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+        if settings.snap_to_cells {
+            for cell in drawn_cells.graphics.values().flatten() {
+                if cell.id == dragged {
+                    continue;
+                }
+                let (min, max) = bounds(&corners(cell));
+                x.extend([min.x, (min.x + max.x) * 0.5, max.x]);
+                y.extend([min.y, (min.y + max.y) * 0.5, max.y]);
+            }
+        }
+        Self {
+            x,
+            y,
+            grid_size: settings.snap_to_grid.then_some(settings.grid_size),
+        }
+    }
+
+    /// Find the offset that moves one of the values onto the closest target.
+    /// Returns the offset and the target if it is a guide.
+    fn snap(&self, values: &[f32], targets: &[f32], distance: f32) -> Option<(f32, Option<f32>)> {
+        let mut best: Option<(f32, Option<f32>)> = None;
+        for value in values {
+            let guides = targets.iter().map(|target| (target - value, Some(*target)));
+            let grid = self
+                .grid_size
+                .map(|size| ((value / size).round() * size - value, None));
+            for (offset, guide) in guides.chain(grid) {
+                if offset.abs() <= distance
+                    && best.map_or(true, |(best, _)| offset.abs() < best.abs())
+                {
+                    best = Some((offset, guide));
+                }
+            }
+        }
+        best
+    }
+}
+
+/// The axis aligned bounds of a set of points.
+fn bounds(points: &[Vec2]) -> (Vec2, Vec2) {
+    points.iter().fold(
+        (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+        |(min, max), point| (min.min(*point), max.max(*point)),
+    )
+}
+
+/// Calculate the position and size of a cell that is moved.
+fn move_cell(
+    drag: &Drag,
+    pointer: Vec2,
+    snap_targets: &SnapTargets,
+    snap_distance: f32,
+    guides: &mut Vec<Guide>,
+) -> (Vec2, Vec2) {
+    let mut delta = pointer - drag.start_pointer;
+
+    let (min, max) = bounds(&corners(&drag.cell));
+    let (min, max) = (min + delta, max + delta);
+    let center = (min + max) * 0.5;
+    if let Some((offset, guide)) =
+        snap_targets.snap(&[min.x, center.x, max.x], &snap_targets.x, snap_distance)
+    {
+        delta.x += offset;
+        guides.extend(guide.map(Guide::Vertical));
+    }
+    if let Some((offset, guide)) =
+        snap_targets.snap(&[min.y, center.y, max.y], &snap_targets.y, snap_distance)
+    {
+        delta.y += offset;
+        guides.extend(guide.map(Guide::Horizontal));
+    }
+
+    (
+        drag.start_position + drag.cell.to_parent_vector(delta),
+        drag.start_size,
+    )
+}
+
+/// Calculate the position and size of a cell that is resized by a handle.
+fn resize_cell(
+    drag: &Drag,
+    handle: Vec2,
+    pointer: Vec2,
+    snap_targets: &SnapTargets,
+    snap_distance: f32,
+    guides: &mut Vec<Guide>,
+) -> (Vec2, Vec2) {
+    let start_handle = drag.cell.to_world(handle * drag.cell.size);
+    let mut point = start_handle + pointer - drag.start_pointer;
+    if handle.x != 0.5 {
+        if let Some((offset, guide)) = snap_targets.snap(&[point.x], &snap_targets.x, snap_distance)
+        {
+            point.x += offset;
+            guides.extend(guide.map(Guide::Vertical));
+        }
+    }
+    if handle.y != 0.5 {
+        if let Some((offset, guide)) = snap_targets.snap(&[point.y], &snap_targets.y, snap_distance)
+        {
+            point.y += offset;
+            guides.extend(guide.map(Guide::Horizontal));
+        }
+    }
+
+    // Resize the cell in its local space and move its top left corner
+    // by the amount the start edges have moved.
+    let local = drag.cell.to_local(point);
+    let mut size = drag.cell.size;
+    let mut offset = Vec2::ZERO;
+    for axis in 0..2 {
+        if handle[axis] == 0.0 {
+            offset[axis] = local[axis].min(size[axis]);
+            size[axis] -= offset[axis];
+        } else if handle[axis] == 1.0 {
+            size[axis] = local[axis].max(0.0);
+        }
+    }
+    let world_offset = drag.cell.to_world(offset) - drag.cell.to_world(Vec2::ZERO);
+
+    (
+        drag.start_position + drag.cell.to_parent_vector(world_offset),
+        drag.start_size + size - drag.cell.size,
+    )
+}
@@ -2,33 +2,44 @@ use backend::{
     style::{
         graphic::{
             graphic_items::{
-                cell::Cell,
+                cell::{Cell, CellGradient, TextFit, TextOverflow},
                 clip_area::ClipArea,
-                driver_table::DriverTable,
+                component_instance::ComponentInstance,
+                driver_table::{DriverTable, Easing, ScrollMode, SortOrder},
                 entry_context::{EntryContext, EntrySelection},
+                group::Group,
+                layout::{Layout, LayoutAlignment, LayoutDirection},
+                repeater::{Repeater, RepeaterLayout, RepeaterSource},
                 root::Root,
                 Attribute, GraphicItem,
             },
-            GraphicDefinition, GraphicStateId, TEMPLATE_ID,
+            GraphicDefinition, GraphicState, GraphicStateId, TEMPLATE_ID,
         },
         StyleItem,
     },
     tree_iterator::TreeIteratorMut,
+    value_types::{Number, ProducerRef, Property, Tint, ValueType, Vec2Property},
 };
+use bevy::render::color::Color;
 use bevy_egui::egui::{
     self, vec2, CollapsingHeader, DragValue, Layout, ScrollArea, Ui, Widget, WidgetText,
 };
 use common::communication::TextAlignment;
 
 use crate::{
-    reference_store::ReferenceStore,
+    reference_store::{optional_producer_ref_editor, ReferenceStore},
     ui::{
         combo_box::LComboBox, EditResult, EditorState, EditorStyle, StyleItemSelection, UiMessage,
         UiMessages,
     },
 };
 
-use super::style_item::property::PropertyEditor;
+use super::style_item::{
+    property::{parameter_value_editor, PropertyEditor},
+    variable::{producer_ref_editor, EguiComboBoxExtension},
+};
+
+mod multi_selection;
 
 pub(super) fn editor(
     ui: &mut Ui,
@@ -68,20 +79,26 @@ fn graphic_editor(
         .entry(graphic.id)
         .or_insert(StyleItemSelection::default());
 
+    let state_id = selection_data
+        .graphic_state_tree_state
+        .selected()
+        .unwrap_or(TEMPLATE_ID);
+    let selection = selection_data.selected_graphic_items();
+
     // Editor for the graphic item
-    if let Some(graphic_item_selection) = selection_data.graphic_item_tree_state.selected() {
+    if selection.len() > 1 {
+        edit_result |= multi_selection::multi_selection_editor(
+            ui,
+            graphic,
+            &selection,
+            state_id,
+            reference_store,
+        );
+    } else if let Some(graphic_item_selection) = selection.first() {
         edit_result |= graphic
             .items
-            .search_mut(graphic_item_selection, |item| {
-                graphic_item_editor(
-                    ui,
-                    item,
-                    reference_store,
-                    selection_data
-                        .graphic_state_tree_state
-                        .selected()
-                        .unwrap_or(TEMPLATE_ID),
-                )
+            .search_mut(*graphic_item_selection, |item| {
+                graphic_item_editor(ui, item, &graphic.states, reference_store, state_id)
             })
             .unwrap_or_default();
     }
@@ -101,6 +118,7 @@ fn graphic_editor(
 fn graphic_item_editor(
     ui: &mut Ui,
     item: &mut GraphicItem,
+    states: &[GraphicState],
     reference_store: &ReferenceStore,
     state_id: GraphicStateId,
 ) -> EditResult {
@@ -142,7 +160,7 @@ fn graphic_item_editor(
                 edit_result |= ui.text_edit_singleline(&mut driver_table.name).into();
             });
             ui.separator();
-            edit_result |= driver_table_editor(ui, driver_table, state_id, reference_store);
+            edit_result |= driver_table_editor(ui, driver_table, states, state_id, reference_store);
             edit_result
         }
         GraphicItem::EntryContext(entry_context) => {
@@ -155,6 +173,46 @@ fn graphic_item_editor(
             edit_result |= entry_context_editor(ui, entry_context, state_id, reference_store);
             edit_result
         }
+        GraphicItem::Repeater(repeater) => {
+            let mut edit_result = EditResult::None;
+
+            ui_split_with_space(ui, "Name", |ui| {
+                edit_result |= ui.text_edit_singleline(&mut repeater.name).into();
+            });
+            ui.separator();
+            edit_result |= repeater_editor(ui, repeater, state_id, reference_store);
+            edit_result
+        }
+        GraphicItem::Layout(layout) => {
+            let mut edit_result = EditResult::None;
+
+            ui_split_with_space(ui, "Name", |ui| {
+                edit_result |= ui.text_edit_singleline(&mut layout.name).into();
+            });
+            ui.separator();
+            edit_result |= layout_editor(ui, layout, state_id, reference_store);
+            edit_result
+        }
+        GraphicItem::Group(group) => {
+            let mut edit_result = EditResult::None;
+
+            ui_split_with_space(ui, "Name", |ui| {
+                edit_result |= ui.text_edit_singleline(&mut group.name).into();
+            });
+            ui.separator();
+            edit_result |= group_editor(ui, group, state_id, reference_store);
+            edit_result
+        }
+        GraphicItem::ComponentInstance(instance) => {
+            let mut edit_result = EditResult::None;
+
+            ui_split_with_space(ui, "Name", |ui| {
+                edit_result |= ui.text_edit_singleline(&mut instance.name).into();
+            });
+            ui.separator();
+            edit_result |= component_instance_editor(ui, instance, state_id, reference_store);
+            edit_result
+        }
     }
 }
 
@@ -220,6 +278,37 @@ fn ui_attribute<T: Clone>(
     });
 }
 
+/// The value of an attribute that is edited in a state.
+/// Attributes that are not part of the state cannot be edited.
+pub(super) fn editable_attribute<T>(
+    attr: &mut Attribute<T>,
+    state_id: GraphicStateId,
+) -> Option<&mut T> {
+    if attr.has_state(&state_id) {
+        attr.get_state(&state_id)
+    } else if state_id == TEMPLATE_ID {
+        Some(attr.template_mut())
+    } else {
+        None
+    }
+}
+
+/// The value of a property if it is a fixed value.
+pub(super) fn fixed(property: &Property<Number>) -> Option<f32> {
+    match property {
+        Property::Fixed(Number(value)) => Some(*value),
+        Property::Producer(_) => None,
+    }
+}
+
+/// Set the value of a property if it is a fixed value.
+/// Properties that reference a producer are left unchanged.
+pub(super) fn set_fixed(property: &mut Property<Number>, value: f32) {
+    if let Property::Fixed(Number(v)) = property {
+        *v = value.round();
+    }
+}
+
 pub fn cell_property_editor(
     ui: &mut Ui,
     cell: &mut Cell,
@@ -234,6 +323,11 @@ pub fn cell_property_editor(
                 edit_result |= ui.add(PropertyEditor::new(visible, reference_store)).into();
             });
         });
+        ui_attribute(ui, &mut cell.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
         CollapsingHeader::new("Text").show_unindented(ui, |ui| {
             ui_attribute(ui, &mut cell.text, state_id, |ui, attr| {
                 ui_split(ui, "Text", |ui| {
@@ -280,6 +374,70 @@ pub fn cell_property_editor(
                         .into();
                 });
             });
+            ui_attribute(ui, &mut cell.text_fit, state_id, |ui, attr| {
+                ui_split(ui, "Fit", |ui| {
+                    edit_result |= LComboBox::new(attr)
+                        .add_option(TextFit::None, "None")
+                        .add_option(TextFit::FitWidth, "Fit width to text")
+                        .add_option(TextFit::ShrinkText, "Shrink text to fit")
+                        .ui(ui)
+                        .into();
+                });
+            });
+            ui_attribute(ui, &mut cell.text_padding, state_id, |ui, attr| {
+                ui_split(ui, "Padding", |ui| {
+                    edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+                });
+            });
+            ui_attribute(ui, &mut cell.text_wrap, state_id, |ui, attr| {
+                ui_split(ui, "Wrap", |ui| {
+                    edit_result |= ui.checkbox(attr, "").into();
+                });
+            });
+            ui_attribute(ui, &mut cell.text_overflow, state_id, |ui, attr| {
+                ui_split(ui, "Overflow", |ui| {
+                    edit_result |= LComboBox::new(attr)
+                        .add_option(TextOverflow::Visible, "Visible")
+                        .add_option(TextOverflow::Truncate, "Truncate")
+                        .add_option(TextOverflow::Ellipsis, "Ellipsis")
+                        .ui(ui)
+                        .into();
+                });
+            });
+            ui_attribute(ui, &mut cell.line_height, state_id, |ui, attr| {
+                ui_split(ui, "Line height", |ui| {
+                    edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+                });
+            });
+            ui_attribute(ui, &mut cell.text_outline, state_id, |ui, attr| {
+                ui_split(ui, "Outline width", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.width, reference_store))
+                        .into();
+                });
+                ui_split(ui, "Outline color", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.color, reference_store))
+                        .into();
+                });
+            });
+            ui_attribute(ui, &mut cell.text_shadow, state_id, |ui, attr| {
+                ui_split(ui, "Shadow X", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.offset.x, reference_store))
+                        .into();
+                });
+                ui_split(ui, "Y", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.offset.y, reference_store))
+                        .into();
+                });
+                ui_split(ui, "Shadow color", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.color, reference_store))
+                        .into();
+                });
+            });
         });
         CollapsingHeader::new("Position").show_unindented(ui, |ui| {
             ui_attribute(ui, &mut cell.pos, state_id, |ui, attr| {
@@ -299,6 +457,14 @@ pub fn cell_property_editor(
                         .into();
                 });
             });
+            edit_result |= transform_editor(
+                ui,
+                &mut cell.rotation,
+                &mut cell.scale,
+                &mut cell.pivot,
+                state_id,
+                reference_store,
+            );
         });
         CollapsingHeader::new("Shape").show_unindented(ui, |ui| {
             ui_attribute(ui, &mut cell.size, state_id, |ui, attr| {
@@ -401,10 +567,158 @@ pub fn cell_property_editor(
                 });
             });
         });
+        CollapsingHeader::new("Gradient").show_unindented(ui, |ui| {
+            ui_attribute(ui, &mut cell.gradient, state_id, |ui, attr| {
+                edit_result |= gradient_editor(ui, attr, reference_store);
+            });
+        });
+        CollapsingHeader::new("Border").show_unindented(ui, |ui| {
+            ui_attribute(ui, &mut cell.border, state_id, |ui, attr| {
+                ui_split(ui, "Width", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.width, reference_store))
+                        .into();
+                });
+                ui_split(ui, "Color", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.color, reference_store))
+                        .into();
+                });
+            });
+        });
+        CollapsingHeader::new("Shadow").show_unindented(ui, |ui| {
+            ui_attribute(ui, &mut cell.shadow, state_id, |ui, attr| {
+                ui_split(ui, "Offset X", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.offset.x, reference_store))
+                        .into();
+                });
+                ui_split(ui, "Y", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.offset.y, reference_store))
+                        .into();
+                });
+                ui_split(ui, "Blur", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.blur, reference_store))
+                        .into();
+                });
+                ui_split(ui, "Color", |ui| {
+                    edit_result |= ui
+                        .add(PropertyEditor::new(&mut attr.color, reference_store))
+                        .into();
+                });
+            });
+        });
     });
     edit_result
 }
 
+fn gradient_editor(
+    ui: &mut Ui,
+    gradient: &mut CellGradient,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+    ui_split(ui, "Gradient", |ui| {
+        let color = || Property::Fixed(Tint(Color::BLACK));
+        let position = || Vec2Property {
+            x: Property::Fixed(Number(0.0)),
+            y: Property::Fixed(Number(0.0)),
+        };
+        edit_result |= egui::ComboBox::from_id_source(ui.next_auto_id())
+            .width(ui.available_width())
+            .choose(
+                ui,
+                gradient,
+                vec![
+                    (CellGradient::None, "None"),
+                    (
+                        CellGradient::Linear {
+                            color: color(),
+                            position: position(),
+                            spread: Property::Fixed(Number(100.0)),
+                            angle: Property::Fixed(Number(0.0)),
+                        },
+                        "Linear",
+                    ),
+                    (
+                        CellGradient::Radial {
+                            color: color(),
+                            position: position(),
+                            spread: Property::Fixed(Number(100.0)),
+                            distance: Property::Fixed(Number(0.0)),
+                        },
+                        "Radial",
+                    ),
+                    (
+                        CellGradient::Conical {
+                            color: color(),
+                            position: position(),
+                            angle: Property::Fixed(Number(0.0)),
+                        },
+                        "Conical",
+                    ),
+                ],
+            )
+            .into();
+    });
+    let (color, position) = match gradient {
+        CellGradient::None => return edit_result,
+        CellGradient::Linear {
+            color, position, ..
+        }
+        | CellGradient::Radial {
+            color, position, ..
+        }
+        | CellGradient::Conical {
+            color, position, ..
+        } => (color, position),
+    };
+    ui_split(ui, "Color", |ui| {
+        edit_result |= ui.add(PropertyEditor::new(color, reference_store)).into();
+    });
+    ui_split(ui, "Position X", |ui| {
+        edit_result |= ui
+            .add(PropertyEditor::new(&mut position.x, reference_store))
+            .into();
+    });
+    ui_split(ui, "Y", |ui| {
+        edit_result |= ui
+            .add(PropertyEditor::new(&mut position.y, reference_store))
+            .into();
+    });
+    match gradient {
+        CellGradient::None => (),
+        CellGradient::Linear { spread, angle, .. } => {
+            ui_split(ui, "Spread", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(spread, reference_store)).into();
+            });
+            ui_split(ui, "Angle", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(angle, reference_store)).into();
+            });
+        }
+        CellGradient::Radial {
+            spread, distance, ..
+        } => {
+            ui_split(ui, "Spread", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(spread, reference_store)).into();
+            });
+            ui_split(ui, "Distance", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(distance, reference_store))
+                    .into();
+            });
+        }
+        CellGradient::Conical { angle, .. } => {
+            ui_split(ui, "Angle", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(angle, reference_store)).into();
+            });
+        }
+    }
+    edit_result
+}
+
 pub fn clip_area_editor(
     ui: &mut Ui,
     clip_area: &mut ClipArea,
@@ -414,6 +728,11 @@ pub fn clip_area_editor(
     let mut edit_result = EditResult::None;
 
     ui.scope(|ui| {
+        ui_attribute(ui, &mut clip_area.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
         ui.add_enabled_ui(state_id == TEMPLATE_ID, |ui| {
             ui.horizontal(|ui| {
                 ui.add_space(ui.spacing().icon_width);
@@ -444,6 +763,14 @@ pub fn clip_area_editor(
                         .into();
                 });
             });
+            edit_result |= transform_editor(
+                ui,
+                &mut clip_area.rotation,
+                &mut clip_area.scale,
+                &mut clip_area.pivot,
+                state_id,
+                reference_store,
+            );
         });
         CollapsingHeader::new("Shape").show_unindented(ui, |ui| {
             ui_attribute(ui, &mut clip_area.size, state_id, |ui, attr| {
@@ -501,6 +828,11 @@ pub fn root_editor(
     let mut edit_result = EditResult::None;
 
     ui.scope(|ui| {
+        ui_attribute(ui, &mut root.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
         ui_attribute(ui, &mut root.position, state_id, |ui, attr| {
             ui_split(ui, "Position X", |ui| {
                 edit_result |= ui
@@ -513,20 +845,75 @@ pub fn root_editor(
                     .into();
             });
         });
+        edit_result |= transform_editor(
+            ui,
+            &mut root.rotation,
+            &mut root.scale,
+            &mut root.pivot,
+            state_id,
+            reference_store,
+        );
     });
 
     edit_result
 }
 
+fn transform_editor(
+    ui: &mut Ui,
+    rotation: &mut Attribute<Property<Number>>,
+    scale: &mut Attribute<Vec2Property>,
+    pivot: &mut Attribute<Vec2Property>,
+    state_id: GraphicStateId,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+    ui_attribute(ui, rotation, state_id, |ui, attr| {
+        ui_split(ui, "Rotation", |ui| {
+            edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+        });
+    });
+    ui_attribute(ui, scale, state_id, |ui, attr| {
+        ui_split(ui, "Scale X", |ui| {
+            edit_result |= ui
+                .add(PropertyEditor::new(&mut attr.x, reference_store))
+                .into();
+        });
+        ui_split(ui, "Y", |ui| {
+            edit_result |= ui
+                .add(PropertyEditor::new(&mut attr.y, reference_store))
+                .into();
+        });
+    });
+    ui_attribute(ui, pivot, state_id, |ui, attr| {
+        ui_split(ui, "Pivot X", |ui| {
+            edit_result |= ui
+                .add(PropertyEditor::new(&mut attr.x, reference_store))
+                .into();
+        });
+        ui_split(ui, "Y", |ui| {
+            edit_result |= ui
+                .add(PropertyEditor::new(&mut attr.y, reference_store))
+                .into();
+        });
+    });
+    edit_result
+}
+
 pub fn driver_table_editor(
     ui: &mut Ui,
     driver_table: &mut DriverTable,
+    states: &[GraphicState],
     state_id: GraphicStateId,
     reference_store: &ReferenceStore,
 ) -> EditResult {
     let mut edit_result = EditResult::None;
 
     ui.scope(|ui| {
+        ui_attribute(ui, &mut driver_table.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
         ui_attribute(ui, &mut driver_table.position, state_id, |ui, attr| {
             ui_split(ui, "Position X", |ui| {
                 edit_result |= ui
@@ -551,6 +938,137 @@ pub fn driver_table_editor(
                     .into();
             });
         });
+        CollapsingHeader::new("Scrolling").show_unindented(ui, |ui| {
+            ui_attribute(ui, &mut driver_table.visible_rows, state_id, |ui, attr| {
+                ui_split(ui, "Visible rows", |ui| {
+                    edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+                });
+            });
+            ui_attribute(ui, &mut driver_table.pinned_rows, state_id, |ui, attr| {
+                ui_split(ui, "Pinned rows", |ui| {
+                    edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+                });
+            });
+            ui_attribute(ui, &mut driver_table.scroll_mode, state_id, |ui, attr| {
+                ui_split(ui, "Scroll mode", |ui| {
+                    edit_result |= LComboBox::new(attr)
+                        .add_option(ScrollMode::FollowFocus, "Follow focus")
+                        .add_option(ScrollMode::Fixed, "Fixed")
+                        .add_option(ScrollMode::Paged, "Paged")
+                        .ui(ui)
+                        .into();
+                });
+            });
+            ui_attribute(ui, &mut driver_table.page_interval, state_id, |ui, attr| {
+                ui_split(ui, "Page interval", |ui| {
+                    edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+                });
+            });
+            ui_attribute(
+                ui,
+                &mut driver_table.scroll_smoothing,
+                state_id,
+                |ui, attr| {
+                    ui_split(ui, "Smoothing", |ui| {
+                        edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+                    });
+                },
+            );
+        });
+        CollapsingHeader::new("Sorting").show_unindented(ui, |ui| {
+            ui_attribute(ui, &mut driver_table.sort_key, state_id, |ui, attr| {
+                ui_split(ui, "Sort by", |ui| {
+                    edit_result |=
+                        optional_producer_ref_editor(ui, reference_store, attr, "Position", |v| {
+                            match v.producer_ref.ty() {
+                                ValueType::Number => true,
+                                ValueType::Text => true,
+                                _ => false,
+                            }
+                        })
+                        .into();
+                });
+            });
+            ui_attribute(ui, &mut driver_table.sort_order, state_id, |ui, attr| {
+                ui_split(ui, "Order", |ui| {
+                    edit_result |= LComboBox::new(attr)
+                        .add_option(SortOrder::Ascending, "Ascending")
+                        .add_option(SortOrder::Descending, "Descending")
+                        .ui(ui)
+                        .into();
+                });
+            });
+            ui_attribute(ui, &mut driver_table.filter, state_id, |ui, attr| {
+                ui_split(ui, "Show only if", |ui| {
+                    let mut any_ref = attr.clone().map(|filter| filter.to_any_producer_ref());
+                    let res = optional_producer_ref_editor(
+                        ui,
+                        reference_store,
+                        &mut any_ref,
+                        "Always",
+                        |v| v.producer_ref.ty() == ValueType::Boolean,
+                    );
+                    if res.changed() {
+                        *attr = any_ref.and_then(|any_ref| any_ref.to_typed());
+                    }
+                    edit_result |= res.into();
+                });
+            });
+        });
+        CollapsingHeader::new("Reorder animation").show_unindented(ui, |ui| {
+            ui_attribute(
+                ui,
+                &mut driver_table.reorder_duration,
+                state_id,
+                |ui, attr| {
+                    ui_split(ui, "Duration", |ui| {
+                        edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+                    });
+                },
+            );
+            ui_attribute(
+                ui,
+                &mut driver_table.reorder_easing,
+                state_id,
+                |ui, attr| {
+                    ui_split(ui, "Easing", |ui| {
+                        edit_result |= LComboBox::new(attr)
+                            .add_option(Easing::Linear, "Linear")
+                            .add_option(Easing::EaseIn, "Ease in")
+                            .add_option(Easing::EaseOut, "Ease out")
+                            .add_option(Easing::EaseInOut, "Ease in out")
+                            .ui(ui)
+                            .into();
+                    });
+                },
+            );
+            ui_split(ui, "Moving state", |ui| {
+                let selected_name = driver_table
+                    .moving_state
+                    .and_then(|id| states.iter().find(|s| s.id == id))
+                    .map(|s| s.name.as_str())
+                    .unwrap_or("None");
+                egui::ComboBox::from_id_source(ui.next_auto_id())
+                    .width(ui.available_width())
+                    .selected_text(selected_name)
+                    .show_ui(ui, |ui| {
+                        let is_selected = driver_table.moving_state.is_none();
+                        if ui.selectable_label(is_selected, "None").clicked() && !is_selected {
+                            driver_table.moving_state = None;
+                            edit_result = EditResult::FromId(ui.id());
+                        }
+                        for state in states.iter() {
+                            let is_selected = driver_table.moving_state == Some(state.id);
+                            if ui.selectable_label(is_selected, &state.name).clicked()
+                                && !is_selected
+                            {
+                                driver_table.moving_state = Some(state.id);
+                                edit_result = EditResult::FromId(ui.id());
+                            }
+                        }
+                    });
+            });
+        });
     });
 
     edit_result
@@ -560,24 +1078,459 @@ pub fn entry_context_editor(
     ui: &mut Ui,
     entry_context: &mut EntryContext,
     state_id: GraphicStateId,
-    _reference_store: &ReferenceStore,
+    reference_store: &ReferenceStore,
 ) -> EditResult {
     let mut edit_result = EditResult::None;
 
     ui.scope(|ui| {
+        ui_attribute(ui, &mut entry_context.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
         ui_attribute(ui, &mut entry_context.selection, state_id, |ui, attr| {
             ui_split(ui, "Selected entry", |ui| {
+                edit_result |= egui::ComboBox::from_id_source(ui.next_auto_id())
+                    .width(ui.available_width())
+                    .choose(
+                        ui,
+                        attr,
+                        vec![
+                            (EntrySelection::First, "First"),
+                            (EntrySelection::Second, "Second"),
+                            (EntrySelection::Third, "Third"),
+                            (EntrySelection::AheadOfFocus, "Ahead of focus"),
+                            (EntrySelection::Focus, "Focus"),
+                            (EntrySelection::BehindFocus, "Behind focus"),
+                            (
+                                EntrySelection::Position(Property::Fixed(Number(1.0))),
+                                "Position",
+                            ),
+                            (
+                                EntrySelection::FocusOffset(Property::Fixed(Number(0.0))),
+                                "Offset from focus",
+                            ),
+                            (
+                                EntrySelection::CarNumber(Property::Fixed(Number(0.0))),
+                                "Car number",
+                            ),
+                            (EntrySelection::EntryId(0), "Entry id"),
+                            (
+                                EntrySelection::Producer(ProducerRef::default()),
+                                "Entry id from variable",
+                            ),
+                        ],
+                    )
+                    .into();
+            });
+            match attr {
+                EntrySelection::Position(position) => {
+                    ui_split(ui, "Position", |ui| {
+                        edit_result |= ui
+                            .add(PropertyEditor::new(position, reference_store))
+                            .into();
+                    });
+                }
+                EntrySelection::FocusOffset(offset) => {
+                    ui_split(ui, "Offset", |ui| {
+                        edit_result |= ui.add(PropertyEditor::new(offset, reference_store)).into();
+                    });
+                }
+                EntrySelection::CarNumber(car_number) => {
+                    ui_split(ui, "Car number", |ui| {
+                        edit_result |= ui
+                            .add(PropertyEditor::new(car_number, reference_store))
+                            .into();
+                    });
+                }
+                EntrySelection::EntryId(entry_id) => {
+                    ui_split(ui, "Entry id", |ui| {
+                        edit_result |= ui.add(DragValue::new(entry_id)).into();
+                    });
+                }
+                EntrySelection::Producer(producer) => {
+                    ui_split(ui, "Entry id", |ui| {
+                        edit_result |= producer_ref_editor(ui, reference_store, producer).into();
+                    });
+                }
+                _ => (),
+            }
+        });
+    });
+
+    edit_result
+}
+
+pub fn repeater_editor(
+    ui: &mut Ui,
+    repeater: &mut Repeater,
+    state_id: GraphicStateId,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui.scope(|ui| {
+        ui_attribute(ui, &mut repeater.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
+        ui_attribute(ui, &mut repeater.position, state_id, |ui, attr| {
+            ui_split(ui, "Position X", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.x, reference_store))
+                    .into();
+            });
+            ui_split(ui, "Y", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.y, reference_store))
+                    .into();
+            });
+        });
+        ui_attribute(ui, &mut repeater.source, state_id, |ui, attr| {
+            ui_split(ui, "Repeat for", |ui| {
+                edit_result |= egui::ComboBox::from_id_source(ui.next_auto_id())
+                    .width(ui.available_width())
+                    .choose(
+                        ui,
+                        attr,
+                        vec![
+                            (RepeaterSource::Count(Property::Fixed(Number(3.0))), "Count"),
+                            (RepeaterSource::Entries(None), "Entries"),
+                            (RepeaterSource::Laps(Property::Fixed(Number(5.0))), "Laps"),
+                            (RepeaterSource::Sectors, "Sectors"),
+                        ],
+                    )
+                    .into();
+            });
+            match attr {
+                RepeaterSource::Count(count) => {
+                    ui_split(ui, "Count", |ui| {
+                        edit_result |= ui.add(PropertyEditor::new(count, reference_store)).into();
+                    });
+                }
+                RepeaterSource::Entries(filter) => {
+                    ui_split(ui, "Show only if", |ui| {
+                        let mut any_ref = filter.clone().map(|filter| filter.to_any_producer_ref());
+                        let res = optional_producer_ref_editor(
+                            ui,
+                            reference_store,
+                            &mut any_ref,
+                            "Always",
+                            |v| v.producer_ref.ty() == ValueType::Boolean,
+                        );
+                        if res.changed() {
+                            *filter = any_ref.and_then(|any_ref| any_ref.to_typed());
+                        }
+                        edit_result |= res.into();
+                    });
+                }
+                RepeaterSource::Laps(count) => {
+                    ui_split(ui, "Last laps", |ui| {
+                        edit_result |= ui.add(PropertyEditor::new(count, reference_store)).into();
+                    });
+                }
+                RepeaterSource::Sectors => (),
+            }
+        });
+        ui_attribute(ui, &mut repeater.layout, state_id, |ui, attr| {
+            ui_split(ui, "Layout", |ui| {
+                edit_result |= egui::ComboBox::from_id_source(ui.next_auto_id())
+                    .width(ui.available_width())
+                    .choose(
+                        ui,
+                        attr,
+                        vec![
+                            (
+                                RepeaterLayout::Grid {
+                                    columns: Property::Fixed(Number(1.0)),
+                                },
+                                "Grid",
+                            ),
+                            (
+                                RepeaterLayout::Flow {
+                                    item_width: Property::Fixed(Number(100.0)),
+                                    max_width: Property::Fixed(Number(500.0)),
+                                },
+                                "Flow",
+                            ),
+                        ],
+                    )
+                    .into();
+            });
+            match attr {
+                RepeaterLayout::Grid { columns } => {
+                    ui_split(ui, "Columns", |ui| {
+                        edit_result |= ui.add(PropertyEditor::new(columns, reference_store)).into();
+                    });
+                }
+                RepeaterLayout::Flow {
+                    item_width,
+                    max_width,
+                } => {
+                    ui_split(ui, "Item width", |ui| {
+                        edit_result |= ui
+                            .add(PropertyEditor::new(item_width, reference_store))
+                            .into();
+                    });
+                    ui_split(ui, "Max width", |ui| {
+                        edit_result |= ui
+                            .add(PropertyEditor::new(max_width, reference_store))
+                            .into();
+                    });
+                }
+            }
+        });
+        ui_attribute(ui, &mut repeater.item_offset, state_id, |ui, attr| {
+            ui_split(ui, "Item offset X", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.x, reference_store))
+                    .into();
+            });
+            ui_split(ui, "Y", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.y, reference_store))
+                    .into();
+            });
+        });
+    });
+
+    edit_result
+}
+
+pub fn group_editor(
+    ui: &mut Ui,
+    group: &mut Group,
+    state_id: GraphicStateId,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui.scope(|ui| {
+        ui_attribute(ui, &mut group.visible, state_id, |ui, visible| {
+            ui_split(ui, "Visible", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(visible, reference_store)).into();
+            });
+        });
+        ui_attribute(ui, &mut group.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
+        ui_attribute(ui, &mut group.position, state_id, |ui, attr| {
+            ui_split(ui, "Position X", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.x, reference_store))
+                    .into();
+            });
+            ui_split(ui, "Y", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.y, reference_store))
+                    .into();
+            });
+        });
+        edit_result |= transform_editor(
+            ui,
+            &mut group.rotation,
+            &mut group.scale,
+            &mut group.pivot,
+            state_id,
+            reference_store,
+        );
+    });
+
+    edit_result
+}
+
+pub fn component_instance_editor(
+    ui: &mut Ui,
+    instance: &mut ComponentInstance,
+    state_id: GraphicStateId,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui.scope(|ui| {
+        ui.add_enabled_ui(state_id == TEMPLATE_ID, |ui| {
+            ui_split_with_space(ui, "Component", |ui| {
+                let selected_name = instance
+                    .component
+                    .and_then(|id| reference_store.component(&id))
+                    .map(|component| component.name.as_str())
+                    .unwrap_or("-");
+                egui::ComboBox::from_id_source(ui.next_auto_id())
+                    .width(ui.available_width())
+                    .selected_text(selected_name)
+                    .show_ui(ui, |ui| {
+                        for component in reference_store.components() {
+                            let is_selected = instance.component == Some(component.id);
+                            if ui.selectable_label(is_selected, &component.name).clicked()
+                                && !is_selected
+                            {
+                                instance.component = Some(component.id);
+                                instance.parameters.clear();
+                                edit_result = EditResult::FromId(ui.id());
+                            }
+                        }
+                    });
+            });
+        });
+        ui_attribute(ui, &mut instance.visible, state_id, |ui, visible| {
+            ui_split(ui, "Visible", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(visible, reference_store)).into();
+            });
+        });
+        ui_attribute(ui, &mut instance.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
+        ui_attribute(ui, &mut instance.position, state_id, |ui, attr| {
+            ui_split(ui, "Position X", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.x, reference_store))
+                    .into();
+            });
+            ui_split(ui, "Y", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.y, reference_store))
+                    .into();
+            });
+        });
+        edit_result |= transform_editor(
+            ui,
+            &mut instance.rotation,
+            &mut instance.scale,
+            &mut instance.pivot,
+            state_id,
+            reference_store,
+        );
+
+        let Some(component) = instance
+            .component
+            .and_then(|id| reference_store.component(&id))
+        else {
+            return;
+        };
+        CollapsingHeader::new("Parameters").show_unindented(ui, |ui| {
+            for parameter in component.parameters.iter() {
+                match instance.parameters.get_mut(&parameter.id) {
+                    // The override is only used if it still has the type of the parameter.
+                    Some(value)
+                        if value.template().value_type() == parameter.default.value_type() =>
+                    {
+                        ui_attribute(ui, value, state_id, |ui, value| {
+                            ui_split(ui, parameter.name.as_str(), |ui| {
+                                edit_result |=
+                                    parameter_value_editor(ui, value, reference_store).into();
+                            });
+                        });
+                        ui.add_enabled_ui(state_id == TEMPLATE_ID, |ui| {
+                            ui_split_with_space(ui, "", |ui| {
+                                if ui.button("Use default").clicked() {
+                                    instance.parameters.remove(&parameter.id);
+                                    edit_result = EditResult::FromId(ui.id());
+                                }
+                            });
+                        });
+                    }
+                    _ => {
+                        ui.add_enabled_ui(state_id == TEMPLATE_ID, |ui| {
+                            ui_split_with_space(ui, parameter.name.as_str(), |ui| {
+                                if ui.button("Override").clicked() {
+                                    instance
+                                        .parameters
+                                        .insert(parameter.id, parameter.default.clone().into());
+                                    edit_result = EditResult::FromId(ui.id());
+                                }
+                            });
+                        });
+                    }
+                }
+            }
+        });
+    });
+
+    edit_result
+}
+
+pub fn layout_editor(
+    ui: &mut Ui,
+    layout: &mut Layout,
+    state_id: GraphicStateId,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui.scope(|ui| {
+        ui_attribute(ui, &mut layout.opacity, state_id, |ui, attr| {
+            ui_split(ui, "Opacity", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
+        ui_attribute(ui, &mut layout.position, state_id, |ui, attr| {
+            ui_split(ui, "Position X", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.x, reference_store))
+                    .into();
+            });
+            ui_split(ui, "Y", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.y, reference_store))
+                    .into();
+            });
+        });
+        ui_attribute(ui, &mut layout.direction, state_id, |ui, attr| {
+            ui_split(ui, "Direction", |ui| {
+                edit_result |= LComboBox::new(attr)
+                    .add_option(LayoutDirection::Horizontal, "Horizontal")
+                    .add_option(LayoutDirection::Vertical, "Vertical")
+                    .ui(ui)
+                    .into();
+            });
+        });
+        ui_attribute(ui, &mut layout.spacing, state_id, |ui, attr| {
+            ui_split(ui, "Spacing", |ui| {
+                edit_result |= ui.add(PropertyEditor::new(attr, reference_store)).into();
+            });
+        });
+        ui_attribute(ui, &mut layout.alignment, state_id, |ui, attr| {
+            ui_split(ui, "Alignment", |ui| {
                 edit_result |= LComboBox::new(attr)
-                    .add_option(EntrySelection::First, "First")
-                    .add_option(EntrySelection::Second, "Second")
-                    .add_option(EntrySelection::Third, "Third")
-                    .add_option(EntrySelection::AheadOfFocus, "Ahead of focus")
-                    .add_option(EntrySelection::Focus, "Focus")
-                    .add_option(EntrySelection::BehindFocus, "Behind focus")
+                    .add_option(LayoutAlignment::Start, "Start")
+                    .add_option(LayoutAlignment::Center, "Center")
+                    .add_option(LayoutAlignment::End, "End")
                     .ui(ui)
                     .into();
             });
         });
+        ui_attribute(ui, &mut layout.justify, state_id, |ui, attr| {
+            ui_split(ui, "Justify", |ui| {
+                edit_result |= LComboBox::new(attr)
+                    .add_option(LayoutAlignment::Start, "Start")
+                    .add_option(LayoutAlignment::Center, "Center")
+                    .add_option(LayoutAlignment::End, "End")
+                    .ui(ui)
+                    .into();
+            });
+        });
+        ui_attribute(ui, &mut layout.auto_size, state_id, |ui, attr| {
+            ui_split(ui, "Auto size", |ui| {
+                edit_result |= ui.checkbox(attr, "").into();
+            });
+        });
+        ui_attribute(ui, &mut layout.size, state_id, |ui, attr| {
+            ui_split(ui, "Size X", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.x, reference_store))
+                    .into();
+            });
+            ui_split(ui, "Y", |ui| {
+                edit_result |= ui
+                    .add(PropertyEditor::new(&mut attr.y, reference_store))
+                    .into();
+            });
+        });
     });
 
     edit_result
@@ -0,0 +1,520 @@
+use std::{collections::HashMap, ops::ControlFlow};
+
+use backend::{
+    style::graphic::{
+        graphic_items::{cell::Cell, GraphicItem, GraphicItemId},
+        GraphicDefinition, GraphicStateId, TEMPLATE_ID,
+    },
+    tree_iterator::{Method, TreeItem, TreeIterator, TreeIteratorMut},
+};
+use bevy::math::{vec2, Vec2};
+use bevy_egui::egui::{Id, Ui};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::{reference_store::ReferenceStore, ui::EditResult};
+
+use super::{cell_property_editor, editable_attribute, fixed, set_fixed, ui_split};
+
+/// Fields of a cell that are unique to each cell and never edited together.
+const UNIQUE_FIELDS: [&str; 2] = ["id", "name"];
+
+/// Position and size of a cell in the space of its parent.
+struct CellRect {
+    id: GraphicItemId,
+    position: Vec2,
+    size: Vec2,
+}
+impl CellRect {
+    fn end(&self) -> Vec2 {
+        self.position + self.size
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+impl Axis {
+    fn index(&self) -> usize {
+        match self {
+            Axis::Horizontal => 0,
+            Axis::Vertical => 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Alignment {
+    Start,
+    Center,
+    End,
+}
+
+#[derive(Clone, Copy)]
+enum Arrangement {
+    Align(Axis, Alignment),
+    Distribute(Axis),
+    MatchSize(Option<Axis>),
+}
+
+/// Editor for multiple selected graphic items.
+///
+/// The last item in the selection is the item that is selected in the tree.
+/// It is used as the reference when sizes are matched and when attributes are
+/// edited together.
+/// Only cells that share the parent of the reference are arranged because
+/// their positions are in the same space.
+pub(super) fn multi_selection_editor(
+    ui: &mut Ui,
+    graphic: &mut GraphicDefinition,
+    selection: &[GraphicItemId],
+    state_id: GraphicStateId,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    let siblings = arrangeable_siblings(graphic, selection);
+    let cells = cell_rects(graphic, &siblings, state_id);
+    ui.label(format!(
+        "{} items selected, {} cells can be arranged.",
+        selection.len(),
+        cells.len()
+    ));
+    if cells.len() < selection.len() {
+        ui.label(
+            "Only cells with the same parent as the item selected in the tree can be arranged.",
+        );
+    }
+    ui.separator();
+
+    let mut arrangement = None;
+    ui.add_enabled_ui(cells.len() > 1, |ui| {
+        ui_split(ui, "Align", |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (label, alignment) in [
+                    ("Left", Alignment::Start),
+                    ("Center", Alignment::Center),
+                    ("Right", Alignment::End),
+                ] {
+                    if ui.button(label).clicked() {
+                        arrangement = Some(Arrangement::Align(Axis::Horizontal, alignment));
+                    }
+                }
+            });
+        });
+        ui_split(ui, "", |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (label, alignment) in [
+                    ("Top", Alignment::Start),
+                    ("Middle", Alignment::Center),
+                    ("Bottom", Alignment::End),
+                ] {
+                    if ui.button(label).clicked() {
+                        arrangement = Some(Arrangement::Align(Axis::Vertical, alignment));
+                    }
+                }
+            });
+        });
+        ui_split(ui, "Match size", |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (label, axis) in [
+                    ("Width", Some(Axis::Horizontal)),
+                    ("Height", Some(Axis::Vertical)),
+                    ("Both", None),
+                ] {
+                    if ui.button(label).clicked() {
+                        arrangement = Some(Arrangement::MatchSize(axis));
+                    }
+                }
+            });
+        });
+    });
+    ui.add_enabled_ui(cells.len() > 2, |ui| {
+        ui_split(ui, "Distribute", |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (label, axis) in [
+                    ("Horizontally", Axis::Horizontal),
+                    ("Vertically", Axis::Vertical),
+                ] {
+                    if ui.button(label).clicked() {
+                        arrangement = Some(Arrangement::Distribute(axis));
+                    }
+                }
+            });
+        });
+    });
+
+    if let Some(arrangement) = arrangement {
+        let reference = selection.last().copied();
+        for cell in arrange(cells, arrangement, reference) {
+            set_cell_rect(graphic, &cell, state_id);
+        }
+        // Each arrangement is its own edit and is never merged with the previous one.
+        edit_result = EditResult::FromId(Id::new(("Arrange cells", Uuid::new_v4())));
+    }
+
+    ui.separator();
+    ui.label("Shared attributes:");
+    edit_result |= shared_attribute_editor(ui, graphic, selection, state_id, reference_store);
+
+    edit_result
+}
+
+/// Edit the attributes of the reference cell and copy every changed value
+/// to all other selected cells.
+///
+/// Only the values that changed in the edited state are copied. Other values
+/// of the same attribute and the values of other states are left unchanged.
+fn shared_attribute_editor(
+    ui: &mut Ui,
+    graphic: &mut GraphicDefinition,
+    selection: &[GraphicItemId],
+    state_id: GraphicStateId,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let Some(mut reference_cell) = selection
+        .last()
+        .and_then(|id| find_cell(graphic, *id, |cell| Some(cell.clone())))
+    else {
+        ui.label("The item selected in the tree is not a cell.");
+        return EditResult::None;
+    };
+
+    let before = serde_json::to_value(&reference_cell).ok();
+    let edit_result = cell_property_editor(ui, &mut reference_cell, state_id, reference_store);
+    let after = serde_json::to_value(&reference_cell).ok();
+    let (Some(Value::Object(before)), Some(Value::Object(after))) = (before, after) else {
+        return EditResult::None;
+    };
+
+    let state_key = (state_id != TEMPLATE_ID).then(|| state_id.0.to_string());
+    let changes: Vec<FieldChange> = after
+        .iter()
+        .filter(|(key, _)| !UNIQUE_FIELDS.contains(&key.as_str()))
+        .flat_map(|(key, after)| {
+            before
+                .get(key)
+                .map(|before| field_changes(key, before, after, state_key.as_deref()))
+                .unwrap_or_default()
+        })
+        .collect();
+    if changes.is_empty() {
+        return EditResult::None;
+    }
+
+    for id in selection {
+        find_cell(graphic, *id, |cell| {
+            let Ok(Value::Object(mut value)) = serde_json::to_value(&*cell) else {
+                return None;
+            };
+            for change in changes.iter() {
+                change.apply(&mut value, state_key.as_deref());
+            }
+            *cell = serde_json::from_value(Value::Object(value)).ok()?;
+            Some(())
+        });
+    }
+    match edit_result {
+        EditResult::FromId(_) => edit_result,
+        // Adding or removing an attribute from a state does not create an edit result.
+        EditResult::None => EditResult::FromId(Id::new(("Shared attributes", Uuid::new_v4()))),
+    }
+}
+
+/// A change to a field of the reference cell.
+enum FieldChange {
+    /// The attribute was added to or removed from the edited state.
+    State { field: String, added: bool },
+    /// A value changed. The path is relative to the edited value of the field.
+    Value {
+        field: String,
+        path: Vec<String>,
+        value: Value,
+    },
+}
+impl FieldChange {
+    fn apply(&self, cell: &mut Map<String, Value>, state_key: Option<&str>) {
+        match self {
+            FieldChange::State { field, added } => {
+                let (Some(state_key), Some(attribute)) = (state_key, cell.get_mut(field)) else {
+                    return;
+                };
+                if *added {
+                    // Getting the value of the state adds the attribute to the state.
+                    edited_value_mut(attribute, Some(state_key));
+                } else if let Some(Value::Object(states)) = attribute.get_mut("states") {
+                    states.remove(state_key);
+                }
+            }
+            FieldChange::Value { field, path, value } => {
+                let Some(edited) = cell
+                    .get_mut(field)
+                    .and_then(|field| edited_value_mut(field, state_key))
+                else {
+                    return;
+                };
+                set_value(edited, path, value.clone());
+            }
+        }
+    }
+}
+
+/// Find the changes of a field between two versions of the reference cell.
+fn field_changes(
+    field: &str,
+    before: &Value,
+    after: &Value,
+    state_key: Option<&str>,
+) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let before_value = edited_value(before, state_key);
+    let after_value = edited_value(after, state_key);
+    if before_value.is_some() != after_value.is_some() {
+        changes.push(FieldChange::State {
+            field: field.to_owned(),
+            added: after_value.is_some(),
+        });
+    }
+    if let (Some(before_value), Some(after_value)) = (before_value, after_value) {
+        let mut leaves = Vec::new();
+        changed_leaves(before_value, after_value, &mut Vec::new(), &mut leaves);
+        changes.extend(leaves.into_iter().map(|(path, value)| FieldChange::Value {
+            field: field.to_owned(),
+            path,
+            value,
+        }));
+    }
+    changes
+}
+
+/// Test if the value of a field is a serialized attribute.
+fn is_attribute(value: &Value) -> bool {
+    matches!(value, Value::Object(map)
+        if map.len() == 2 && map.contains_key("template") && map.contains_key("states"))
+}
+
+/// The value of a field that is edited in a state.
+/// Fields that are not attributes are edited directly.
+fn edited_value<'a>(field: &'a Value, state_key: Option<&str>) -> Option<&'a Value> {
+    if !is_attribute(field) {
+        return Some(field);
+    }
+    match state_key {
+        Some(state_key) => field.get("states")?.get(state_key),
+        None => field.get("template"),
+    }
+}
+
+/// The mutable value of a field that is edited in a state.
+/// If the attribute is not part of the state yet, it is added with the value of the template.
+fn edited_value_mut<'a>(field: &'a mut Value, state_key: Option<&str>) -> Option<&'a mut Value> {
+    if !is_attribute(field) {
+        return Some(field);
+    }
+    let Value::Object(attribute) = field else {
+        return None;
+    };
+    let Some(state_key) = state_key else {
+        return attribute.get_mut("template");
+    };
+    let template = attribute.get("template")?.clone();
+    let Value::Object(states) = attribute.get_mut("states")? else {
+        return None;
+    };
+    Some(states.entry(state_key).or_insert(template))
+}
+
+/// Collect the path and new value of every value that is different.
+/// Objects are compared field by field. Any other value is compared as a whole.
+fn changed_leaves(
+    before: &Value,
+    after: &Value,
+    path: &mut Vec<String>,
+    leaves: &mut Vec<(Vec<String>, Value)>,
+) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, after) in after.iter() {
+                path.push(key.clone());
+                match before.get(key) {
+                    Some(before) => changed_leaves(before, after, path, leaves),
+                    None => leaves.push((path.clone(), after.clone())),
+                }
+                path.pop();
+            }
+        }
+        (before, after) if before != after => leaves.push((path.clone(), after.clone())),
+        _ => (),
+    }
+}
+
+/// Set a value at a path. Missing objects along the path are created.
+fn set_value(target: &mut Value, path: &[String], value: Value) {
+    let Some((key, rest)) = path.split_first() else {
+        *target = value;
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(map) = target {
+        set_value(map.entry(key.as_str()).or_insert(Value::Null), rest, value);
+    }
+}
+
+fn find_cell<R>(
+    graphic: &mut GraphicDefinition,
+    id: GraphicItemId,
+    action: impl FnOnce(&mut Cell) -> Option<R>,
+) -> Option<R> {
+    graphic
+        .items
+        .search_mut(id, |item| match item {
+            GraphicItem::Cell(cell) => action(cell),
+            _ => None,
+        })
+        .flatten()
+}
+
+/// The selected items that have the same parent as the reference item.
+///
+/// Items inside a layout are never arranged because their position is an
+/// offset to their place in the layout.
+fn arrangeable_siblings(
+    graphic: &GraphicDefinition,
+    selection: &[GraphicItemId],
+) -> Vec<GraphicItemId> {
+    // The parent of every item and if that parent is a layout.
+    let mut parents: HashMap<GraphicItemId, (GraphicItemId, bool)> = HashMap::new();
+    let mut stack: Vec<(GraphicItemId, bool)> = Vec::new();
+    graphic.items.as_enum_ref().walk(&mut |item, method| {
+        match method {
+            Method::Visit => {
+                if let Some(parent) = stack.last() {
+                    parents.insert(item.id(), *parent);
+                }
+                stack.push((item.id(), matches!(item, GraphicItem::Layout(_))));
+            }
+            Method::Leave => {
+                stack.pop();
+            }
+        }
+        ControlFlow::Continue::<()>(())
+    });
+
+    let Some((parent_id, parent_is_layout)) = selection.last().and_then(|id| parents.get(id))
+    else {
+        return Vec::new();
+    };
+    if *parent_is_layout {
+        return Vec::new();
+    }
+    selection
+        .iter()
+        .filter(|id| {
+            parents
+                .get(*id)
+                .is_some_and(|(other, _)| other == parent_id)
+        })
+        .copied()
+        .collect()
+}
+
+/// The position and size of all selected cells.
+/// Cells whose position or size is not a fixed value are skipped.
+fn cell_rects(
+    graphic: &mut GraphicDefinition,
+    selection: &[GraphicItemId],
+    state_id: GraphicStateId,
+) -> Vec<CellRect> {
+    selection
+        .iter()
+        .filter_map(|id| {
+            find_cell(graphic, *id, |cell| {
+                let position = editable_attribute(&mut cell.pos, state_id)?;
+                let position = vec2(fixed(&position.x)?, fixed(&position.y)?);
+                let size = editable_attribute(&mut cell.size, state_id)?;
+                let size = vec2(fixed(&size.x)?, fixed(&size.y)?);
+                Some(CellRect {
+                    id: *id,
+                    position,
+                    size,
+                })
+            })
+        })
+        .collect()
+}
+
+fn set_cell_rect(graphic: &mut GraphicDefinition, rect: &CellRect, state_id: GraphicStateId) {
+    find_cell(graphic, rect.id, |cell| {
+        if let Some(position) = editable_attribute(&mut cell.pos, state_id) {
+            set_fixed(&mut position.x, rect.position.x);
+            set_fixed(&mut position.y, rect.position.y);
+        }
+        if let Some(size) = editable_attribute(&mut cell.size, state_id) {
+            set_fixed(&mut size.x, rect.size.x);
+            set_fixed(&mut size.y, rect.size.y);
+        }
+        Some(())
+    });
+}
+
+/// Arrange the cells and return their new position and size.
+fn arrange(
+    mut cells: Vec<CellRect>,
+    arrangement: Arrangement,
+    reference: Option<GraphicItemId>,
+) -> Vec<CellRect> {
+    match arrangement {
+        Arrangement::Align(axis, alignment) => {
+            let i = axis.index();
+            let start = cells
+                .iter()
+                .map(|cell| cell.position[i])
+                .fold(f32::MAX, f32::min);
+            let end = cells
+                .iter()
+                .map(|cell| cell.end()[i])
+                .fold(f32::MIN, f32::max);
+            for cell in cells.iter_mut() {
+                cell.position[i] = match alignment {
+                    Alignment::Start => start,
+                    Alignment::Center => (start + end - cell.size[i]) * 0.5,
+                    Alignment::End => end - cell.size[i],
+                };
+            }
+        }
+        Arrangement::Distribute(axis) => {
+            let i = axis.index();
+            cells.sort_by(|a, b| a.position[i].total_cmp(&b.position[i]));
+            if let (Some(first), Some(last)) = (cells.first(), cells.last()) {
+                let total_size: f32 = cells.iter().map(|cell| cell.size[i]).sum();
+                let gap = (last.end()[i] - first.position[i] - total_size)
+                    / (cells.len() - 1).max(1) as f32;
+                let mut next = first.position[i];
+                for cell in cells.iter_mut() {
+                    cell.position[i] = next;
+                    next += cell.size[i] + gap;
+                }
+            }
+        }
+        Arrangement::MatchSize(axis) => {
+            let Some(size) = cells
+                .iter()
+                .find(|cell| Some(cell.id) == reference)
+                .map(|cell| cell.size)
+            else {
+                return Vec::new();
+            };
+            for cell in cells.iter_mut() {
+                match axis {
+                    Some(axis) => cell.size[axis.index()] = size[axis.index()],
+                    None => cell.size = size,
+                }
+            }
+        }
+    }
+    cells
+}
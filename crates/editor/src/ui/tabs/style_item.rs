@@ -1,6 +1,10 @@
 use backend::{
     style::{
-        variables::{condition::Condition, fixed_value::FixedValue, map::Map, VariableBehavior},
+        variables::{
+            condition::Condition, debounce::Debounce, delta::Delta, fixed_value::FixedValue,
+            format::Format, gap::Gap, hold::Hold, map::Map, position_change::PositionChange,
+            smooth::Smooth, stopwatch::Stopwatch, text_width::TextWidth, VariableBehavior,
+        },
         StyleItem,
     },
     tree_iterator::TreeIteratorMut,
@@ -20,9 +24,9 @@ use self::graphic::graphic_property_editor;
 
 use super::secondary_editor::ui_split;
 
-mod graphic;
+pub(crate) mod graphic;
 pub mod property;
-mod variable;
+pub mod variable;
 
 pub(super) fn editor(
     ui: &mut Ui,
@@ -122,7 +126,25 @@ fn edit_node(
                             VariableBehavior::Condition(Condition::default()),
                             "Condition",
                         )
-                        .add_option(VariableBehavior::Map(Map::default()), "Map"),
+                        .add_option(VariableBehavior::Map(Map::default()), "Map")
+                        .add_option(VariableBehavior::Hold(Hold::default()), "Hold")
+                        .add_option(VariableBehavior::Debounce(Debounce::default()), "Debounce")
+                        .add_option(
+                            VariableBehavior::Stopwatch(Stopwatch::default()),
+                            "Stopwatch",
+                        )
+                        .add_option(VariableBehavior::Delta(Delta::default()), "Delta")
+                        .add_option(VariableBehavior::Smooth(Smooth::default()), "Smooth")
+                        .add_option(VariableBehavior::Format(Format::default()), "Format")
+                        .add_option(VariableBehavior::Gap(Gap::default()), "Gap")
+                        .add_option(
+                            VariableBehavior::TextWidth(TextWidth::default()),
+                            "Text width",
+                        )
+                        .add_option(
+                            VariableBehavior::PositionChange(PositionChange::default()),
+                            "Position change",
+                        ),
                     )
                     .into();
             });
@@ -137,6 +159,33 @@ fn edit_node(
                 VariableBehavior::Map(value) => {
                     variable::map::property_editor(ui, value, reference_store)
                 }
+                VariableBehavior::Hold(value) => {
+                    variable::hold::property_editor(ui, value, reference_store)
+                }
+                VariableBehavior::Debounce(value) => {
+                    variable::debounce::property_editor(ui, value, reference_store)
+                }
+                VariableBehavior::Stopwatch(value) => {
+                    variable::stopwatch::property_editor(ui, value, reference_store)
+                }
+                VariableBehavior::Delta(value) => {
+                    variable::delta::property_editor(ui, value, reference_store)
+                }
+                VariableBehavior::Smooth(value) => {
+                    variable::smooth::property_editor(ui, value, reference_store)
+                }
+                VariableBehavior::Format(value) => {
+                    variable::format::property_editor(ui, value, reference_store)
+                }
+                VariableBehavior::Gap(value) => {
+                    variable::gap::property_editor(ui, value, reference_store)
+                }
+                VariableBehavior::TextWidth(value) => {
+                    variable::text_width::property_editor(ui, value, reference_store)
+                }
+                VariableBehavior::PositionChange(value) => {
+                    variable::position_change::property_editor(ui, value, reference_store)
+                }
             };
 
             if let EditResult::FromId(widget_id) = edit_result {
@@ -223,7 +272,13 @@ fn edit_node(
                 .or_insert(StyleItemSelection::default());
 
             ui.push_id(graphic.id, |ui| {
-                graphic_property_editor(ui, graphic, messages, style_item_selection);
+                graphic_property_editor(
+                    ui,
+                    graphic,
+                    messages,
+                    style_item_selection,
+                    reference_store,
+                );
             });
         }
         StyleItem::GraphicFolder(folder) => {
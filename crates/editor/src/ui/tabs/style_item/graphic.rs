@@ -1,35 +1,46 @@
 use std::ops::ControlFlow;
 
 use backend::{
+    race_events::{GraphicStateTrigger, RaceEventKind},
     style::{
         graphic::{
             self,
             graphic_items::{
-                cell::Cell, clip_area::ClipArea, driver_table::DriverTable,
-                entry_context::EntryContext, root::Root, GraphicItem, GraphicItemId,
+                cell::Cell, clip_area::ClipArea, component_instance::ComponentInstance,
+                driver_table::DriverTable, entry_context::EntryContext, group::Group,
+                layout::Layout, repeater::Repeater, root::Root, GraphicItem, GraphicItemId,
             },
-            GraphicDefinition, GraphicStateId,
+            ComponentParameter, GraphicDefinition, GraphicStateId, ParameterValue,
         },
         StyleItem,
     },
     tree_iterator::{Method, TreeItem, TreeIterator, TreeIteratorMut},
+    value_types::ValueType,
 };
-use bevy_egui::egui::{self, vec2, Color32, Id, Label, RichText, Ui};
+use bevy_egui::egui::{self, vec2, Color32, DragValue, Id, Label, RichText, Ui};
 use egui_ltreeview::{
     node::NodeBuilder, Action, DropPosition, RowLayout, TreeView, TreeViewBuilder, TreeViewState,
 };
 
-use crate::ui::{
-    tabs::secondary_editor::ui_split, EditResult, StyleItemSelection, UiMessage, UiMessages,
+use crate::{
+    reference_store::ReferenceStore,
+    ui::{
+        combo_box::LComboBox, tabs::secondary_editor::ui_split, EditResult, StyleItemSelection,
+        UiMessage, UiMessages,
+    },
 };
 
+use super::property::parameter_value_editor;
+
 pub(super) fn graphic_property_editor(
     ui: &mut Ui,
     graphic: &mut GraphicDefinition,
     messages: &mut UiMessages,
     selection_data: &mut StyleItemSelection,
+    reference_store: &ReferenceStore,
 ) {
     let mut edit_result = EditResult::None;
+    let is_component = reference_store.component(&graphic.id).is_some();
 
     ui_split(ui, "Name", |ui| {
         let res = ui.text_edit_singleline(&mut graphic.name);
@@ -47,8 +58,7 @@ pub(super) fn graphic_property_editor(
         egui::ScrollArea::horizontal()
             .auto_shrink([false, true])
             .show(ui, |ui| {
-                edit_result |=
-                    show_element_tree(ui, graphic, &mut selection_data.graphic_item_tree_state);
+                edit_result |= show_element_tree(ui, graphic, selection_data, messages);
             });
 
         ui.allocate_space(vec2(
@@ -80,24 +90,58 @@ pub(super) fn graphic_property_editor(
                 edit_result = EditResult::FromId(ui.id());
                 ui.close_menu();
             }
+            if ui.selectable_label(false, "Repeater").clicked() {
+                graphic.items.items.push(Repeater::new().into());
+                edit_result = EditResult::FromId(ui.id());
+                ui.close_menu();
+            }
+            if ui.selectable_label(false, "Layout").clicked() {
+                graphic.items.items.push(Layout::new().into());
+                edit_result = EditResult::FromId(ui.id());
+                ui.close_menu();
+            }
+            if ui.selectable_label(false, "Group").clicked() {
+                graphic.items.items.push(Group::new().into());
+                edit_result = EditResult::FromId(ui.id());
+                ui.close_menu();
+            }
+            if ui.selectable_label(false, "Component").clicked() {
+                graphic.items.items.push(ComponentInstance::new().into());
+                edit_result = EditResult::FromId(ui.id());
+                ui.close_menu();
+            }
         });
 
-    ui.add_space(10.0);
-    ui.label("States:");
-    ui.group(|ui| {
-        edit_result |= show_states_tree(ui, graphic, &mut selection_data.graphic_state_tree_state);
+    // Components are always drawn in their template state and cannot be
+    // triggered on their own. They expose parameters instead.
+    if is_component {
+        ui.add_space(10.0);
+        ui.label("Parameters:");
+        edit_result |= show_parameters(ui, graphic, reference_store);
+    } else {
+        ui.add_space(10.0);
+        ui.label("States:");
+        ui.group(|ui| {
+            edit_result |=
+                show_states_tree(ui, graphic, &mut selection_data.graphic_state_tree_state);
 
-        ui.allocate_space(vec2(
-            ui.available_width(),
-            -ui.spacing().item_spacing.y + (100.0 - ui.min_rect().height()),
-        ));
-    });
-    let add_button_res = ui.add_sized(vec2(ui.available_width(), 0.0), egui::Button::new("Add"));
-    if add_button_res.clicked() {
-        graphic.states.push(graphic::GraphicState {
-            id: GraphicStateId::new(),
-            name: String::from("new state"),
+            ui.allocate_space(vec2(
+                ui.available_width(),
+                -ui.spacing().item_spacing.y + (100.0 - ui.min_rect().height()),
+            ));
         });
+        let add_button_res =
+            ui.add_sized(vec2(ui.available_width(), 0.0), egui::Button::new("Add"));
+        if add_button_res.clicked() {
+            graphic.states.push(graphic::GraphicState {
+                id: GraphicStateId::new(),
+                name: String::from("new state"),
+            });
+        }
+
+        ui.add_space(10.0);
+        ui.label("Triggers:");
+        edit_result |= show_triggers(ui, graphic);
     }
 
     if let EditResult::FromId(widget_id) = edit_result {
@@ -117,27 +161,66 @@ enum GraphicItemCommand {
     Remove {
         id: GraphicItemId,
     },
+    Copy {
+        id: GraphicItemId,
+    },
+    Cut {
+        id: GraphicItemId,
+    },
+    Paste {
+        target: GraphicItemId,
+        position: DropPosition<GraphicItemId>,
+    },
+    Duplicate {
+        id: GraphicItemId,
+        target: GraphicItemId,
+        position: DropPosition<GraphicItemId>,
+    },
 }
 
 fn show_element_tree(
     ui: &mut Ui,
     graphic: &mut GraphicDefinition,
-    tree_view_state: &mut TreeViewState<GraphicItemId>,
+    selection_data: &mut StyleItemSelection,
+    messages: &mut UiMessages,
 ) -> EditResult {
     let mut edit_result = EditResult::None;
     let mut commands = Vec::new();
+    let selection = selection_data.selected_graphic_items();
+    // The tree only shows the main selection. Other selected items are highlighted.
+    let highlighted: &[GraphicItemId] = if selection.len() > 1 {
+        &selection[..]
+    } else {
+        &[]
+    };
     let res = TreeView::new(ui.make_persistent_id("Component element tree"))
         .row_layout(RowLayout::AlignedIcons)
-        .show_state(ui, tree_view_state, |mut builder| {
-            graphic.items.walk(&mut |item, method| {
-                element_tree_node(&mut builder, item, method, &mut commands);
-                ControlFlow::Continue::<()>(())
-            });
-        });
+        .show_state(
+            ui,
+            &mut selection_data.graphic_item_tree_state,
+            |mut builder| {
+                graphic.items.walk(&mut |item, method| {
+                    element_tree_node(&mut builder, item, method, &mut commands, highlighted);
+                    ControlFlow::Continue::<()>(())
+                });
+            },
+        );
+    let tree_view_state = &mut selection_data.graphic_item_tree_state;
 
     for action in res.actions.iter() {
         match action {
-            Action::SetSelected(_) => (),
+            Action::SetSelected(Some(id)) if ui.input(|i| i.modifiers.command) => {
+                let mut selection = selection.clone();
+                match selection.iter().position(|selected| selected == id) {
+                    Some(index) => {
+                        selection.remove(index);
+                    }
+                    None => selection.push(*id),
+                }
+                tree_view_state.set_selected(selection.last().copied());
+                selection_data.graphic_item_multi_selection = selection;
+            }
+            Action::SetSelected(_) => selection_data.graphic_item_multi_selection.clear(),
             Action::Move {
                 source,
                 target,
@@ -167,6 +250,40 @@ fn show_element_tree(
                 remove_element(graphic, id);
                 edit_result = EditResult::FromId(Id::new("Component element Tree view edit"));
             }
+            GraphicItemCommand::Copy { id } => {
+                messages.push(UiMessage::GraphicItemCopy {
+                    graphic_id: graphic.id,
+                    item_id: id,
+                });
+            }
+            GraphicItemCommand::Cut { id } => {
+                // The copy reads the saved graphic which still contains the item.
+                messages.push(UiMessage::GraphicItemCopy {
+                    graphic_id: graphic.id,
+                    item_id: id,
+                });
+                remove_element(graphic, id);
+                edit_result = EditResult::FromId(Id::new("Component element Tree view edit"));
+            }
+            GraphicItemCommand::Paste { target, position } => {
+                messages.push(UiMessage::GraphicItemPaste {
+                    graphic_id: graphic.id,
+                    target,
+                    position,
+                });
+            }
+            GraphicItemCommand::Duplicate {
+                id,
+                target,
+                position,
+            } => {
+                messages.push(UiMessage::GraphicItemDuplicate {
+                    graphic_id: graphic.id,
+                    item_id: id,
+                    target,
+                    position,
+                });
+            }
         }
     }
 
@@ -178,6 +295,7 @@ fn element_tree_node(
     element: &GraphicItem,
     method: Method,
     commands: &mut Vec<GraphicItemCommand>,
+    highlighted: &[GraphicItemId],
 ) {
     match (method, element) {
         (Method::Visit, GraphicItem::Root(root)) => {
@@ -192,7 +310,7 @@ fn element_tree_node(
                                 )
                                 .selectable(false),
                             );
-                            ui.add(egui::Label::new(&root.name).selectable(false));
+                            name_label(ui, &root.name, highlighted.contains(&root.id));
                         });
                     })
                     .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
@@ -219,7 +337,7 @@ fn element_tree_node(
                                 )
                                 .selectable(false),
                             );
-                            ui.add(egui::Label::new(&cell.name).selectable(false));
+                            name_label(ui, &cell.name, highlighted.contains(&cell.id));
                         });
                     })
                     .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
@@ -243,7 +361,7 @@ fn element_tree_node(
                                 )
                                 .selectable(false),
                             );
-                            ui.add(egui::Label::new(&clip_area.name).selectable(false));
+                            name_label(ui, &clip_area.name, highlighted.contains(&clip_area.id));
                         });
                     })
                     .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
@@ -271,7 +389,11 @@ fn element_tree_node(
                                 )
                                 .selectable(false),
                             );
-                            ui.add(egui::Label::new(&driver_table.name).selectable(false));
+                            name_label(
+                                ui,
+                                &driver_table.name,
+                                highlighted.contains(&driver_table.id),
+                            );
                         });
                     })
                     .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
@@ -299,7 +421,11 @@ fn element_tree_node(
                                 )
                                 .selectable(false),
                             );
-                            ui.add(egui::Label::new(&entry_context.name).selectable(false));
+                            name_label(
+                                ui,
+                                &entry_context.name,
+                                highlighted.contains(&entry_context.id),
+                            );
                         });
                     })
                     .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
@@ -308,9 +434,119 @@ fn element_tree_node(
         (Method::Leave, GraphicItem::EntryContext(_)) => {
             builder.close_dir();
         }
+        (Method::Visit, GraphicItem::Repeater(repeater)) => {
+            let parent_id = builder.parent_id();
+            builder.node(
+                NodeBuilder::dir(repeater.id)
+                    .icon(|ui| {
+                        egui::Image::new(egui::include_image!("../../../../images/array.png"))
+                            .tint(ui.visuals().widgets.noninteractive.fg_stroke.color)
+                            .paint_at(ui, ui.max_rect());
+                    })
+                    .label(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::Label::new(
+                                    RichText::new("Repeater").color(Color32::from_gray(120)),
+                                )
+                                .selectable(false),
+                            );
+                            name_label(ui, &repeater.name, highlighted.contains(&repeater.id));
+                        });
+                    })
+                    .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
+            )
+        }
+        (Method::Leave, GraphicItem::Repeater(_)) => {
+            builder.close_dir();
+        }
+        (Method::Visit, GraphicItem::Layout(layout)) => {
+            let parent_id = builder.parent_id();
+            builder.node(
+                NodeBuilder::dir(layout.id)
+                    .icon(|ui| {
+                        egui::Image::new(egui::include_image!("../../../../images/object.png"))
+                            .tint(ui.visuals().widgets.noninteractive.fg_stroke.color)
+                            .paint_at(ui, ui.max_rect());
+                    })
+                    .label(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::Label::new(
+                                    RichText::new("Layout").color(Color32::from_gray(120)),
+                                )
+                                .selectable(false),
+                            );
+                            name_label(ui, &layout.name, highlighted.contains(&layout.id));
+                        });
+                    })
+                    .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
+            )
+        }
+        (Method::Leave, GraphicItem::Layout(_)) => {
+            builder.close_dir();
+        }
+        (Method::Visit, GraphicItem::Group(group)) => {
+            let parent_id = builder.parent_id();
+            builder.node(
+                NodeBuilder::dir(group.id)
+                    .icon(|ui| {
+                        egui::Image::new(egui::include_image!("../../../../images/folder.png"))
+                            .tint(ui.visuals().widgets.noninteractive.fg_stroke.color)
+                            .paint_at(ui, ui.max_rect());
+                    })
+                    .label(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::Label::new(
+                                    RichText::new("Group").color(Color32::from_gray(120)),
+                                )
+                                .selectable(false),
+                            );
+                            name_label(ui, &group.name, highlighted.contains(&group.id));
+                        });
+                    })
+                    .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
+            )
+        }
+        (Method::Leave, GraphicItem::Group(_)) => {
+            builder.close_dir();
+        }
+        (Method::Visit, GraphicItem::ComponentInstance(instance)) => {
+            let parent_id = builder.parent_id();
+            builder.node(
+                NodeBuilder::leaf(instance.id)
+                    .icon(|ui| {
+                        egui::Image::new(egui::include_image!("../../../../images/graphic.png"))
+                            .tint(ui.visuals().widgets.noninteractive.fg_stroke.color)
+                            .paint_at(ui, ui.max_rect());
+                    })
+                    .label(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::Label::new(
+                                    RichText::new("Component").color(Color32::from_gray(120)),
+                                )
+                                .selectable(false),
+                            );
+                            name_label(ui, &instance.name, highlighted.contains(&instance.id));
+                        });
+                    })
+                    .context_menu(|ui| graphic_item_context_menu(ui, element, commands, parent_id)),
+            );
+        }
+        (Method::Leave, GraphicItem::ComponentInstance(_)) => (),
     }
 }
 
+fn name_label(ui: &mut Ui, name: &str, highlighted: bool) {
+    let mut text = RichText::new(name);
+    if highlighted {
+        text = text.color(ui.visuals().selection.stroke.color);
+    }
+    ui.add(egui::Label::new(text).selectable(false));
+}
+
 fn graphic_item_context_menu(
     ui: &mut Ui,
     graphic_item: &GraphicItem,
@@ -319,10 +555,17 @@ fn graphic_item_context_menu(
 ) {
     let (target, position) = match graphic_item {
         GraphicItem::Cell(cell) => (parent_id.unwrap_or_default(), DropPosition::After(cell.id)),
+        GraphicItem::ComponentInstance(instance) => (
+            parent_id.unwrap_or_default(),
+            DropPosition::After(instance.id),
+        ),
         GraphicItem::Root(Root { id, .. })
         | GraphicItem::ClipArea(ClipArea { id, .. })
         | GraphicItem::DriverTable(DriverTable { id, .. })
-        | GraphicItem::EntryContext(EntryContext { id, .. }) => (*id, DropPosition::Last),
+        | GraphicItem::EntryContext(EntryContext { id, .. })
+        | GraphicItem::Repeater(Repeater { id, .. })
+        | GraphicItem::Layout(Layout { id, .. })
+        | GraphicItem::Group(Group { id, .. }) => (*id, DropPosition::Last),
     };
     if ui.button("add cell").clicked() {
         commands.push(GraphicItemCommand::Add {
@@ -356,6 +599,65 @@ fn graphic_item_context_menu(
         });
         ui.close_menu();
     }
+    if ui.button("add repeater").clicked() {
+        commands.push(GraphicItemCommand::Add {
+            element: Repeater::new().into(),
+            target,
+            position,
+        });
+        ui.close_menu();
+    }
+    if ui.button("add layout").clicked() {
+        commands.push(GraphicItemCommand::Add {
+            element: Layout::new().into(),
+            target,
+            position,
+        });
+        ui.close_menu();
+    }
+    if ui.button("add group").clicked() {
+        commands.push(GraphicItemCommand::Add {
+            element: Group::new().into(),
+            target,
+            position,
+        });
+        ui.close_menu();
+    }
+    if ui.button("add component").clicked() {
+        commands.push(GraphicItemCommand::Add {
+            element: ComponentInstance::new().into(),
+            target,
+            position,
+        });
+        ui.close_menu();
+    }
+    ui.separator();
+    if let Some(parent_id) = parent_id {
+        if ui.button("copy").clicked() {
+            commands.push(GraphicItemCommand::Copy {
+                id: graphic_item.id(),
+            });
+            ui.close_menu();
+        }
+        if ui.button("cut").clicked() {
+            commands.push(GraphicItemCommand::Cut {
+                id: graphic_item.id(),
+            });
+            ui.close_menu();
+        }
+        if ui.button("duplicate").clicked() {
+            commands.push(GraphicItemCommand::Duplicate {
+                id: graphic_item.id(),
+                target: parent_id,
+                position: DropPosition::After(graphic_item.id()),
+            });
+            ui.close_menu();
+        }
+    }
+    if ui.button("paste").clicked() {
+        commands.push(GraphicItemCommand::Paste { target, position });
+        ui.close_menu();
+    }
     ui.separator();
     if ui.button("delete").clicked() {
         commands.push(GraphicItemCommand::Remove {
@@ -382,6 +684,7 @@ fn remove_element(component: &mut GraphicDefinition, id: GraphicItemId) -> Optio
                 }
             }
             GraphicItem::Cell(_) => ControlFlow::Continue(()),
+            GraphicItem::ComponentInstance(_) => ControlFlow::Continue(()),
             GraphicItem::ClipArea(clip_area) => {
                 if let Some(index) = clip_area.items.iter().position(|e| e.id() == id) {
                     ControlFlow::Break(Some(clip_area.items.remove(index)))
@@ -403,6 +706,27 @@ fn remove_element(component: &mut GraphicDefinition, id: GraphicItemId) -> Optio
                     ControlFlow::Continue(())
                 }
             }
+            GraphicItem::Repeater(repeater) => {
+                if let Some(index) = repeater.items.iter().position(|e| e.id() == id) {
+                    ControlFlow::Break(Some(repeater.items.remove(index)))
+                } else {
+                    ControlFlow::Continue(())
+                }
+            }
+            GraphicItem::Layout(layout) => {
+                if let Some(index) = layout.items.iter().position(|e| e.id() == id) {
+                    ControlFlow::Break(Some(layout.items.remove(index)))
+                } else {
+                    ControlFlow::Continue(())
+                }
+            }
+            GraphicItem::Group(group) => {
+                if let Some(index) = group.items.iter().position(|e| e.id() == id) {
+                    ControlFlow::Break(Some(group.items.remove(index)))
+                } else {
+                    ControlFlow::Continue(())
+                }
+            }
         }
     });
     match r {
@@ -411,7 +735,7 @@ fn remove_element(component: &mut GraphicDefinition, id: GraphicItemId) -> Optio
     }
 }
 
-fn insert_element(
+pub(crate) fn insert_element(
     component: &mut GraphicDefinition,
     target: GraphicItemId,
     position: DropPosition<GraphicItemId>,
@@ -422,6 +746,7 @@ fn insert_element(
             insert_into_vec(&mut root.items, position, element);
         }
         GraphicItem::Cell(_) => (),
+        GraphicItem::ComponentInstance(_) => (),
         GraphicItem::ClipArea(clip_area) => {
             insert_into_vec(&mut clip_area.items, position, element);
         }
@@ -431,6 +756,15 @@ fn insert_element(
         GraphicItem::EntryContext(entry_context) => {
             insert_into_vec(&mut entry_context.items, position, element);
         }
+        GraphicItem::Repeater(repeater) => {
+            insert_into_vec(&mut repeater.items, position, element);
+        }
+        GraphicItem::Layout(layout) => {
+            insert_into_vec(&mut layout.items, position, element);
+        }
+        GraphicItem::Group(group) => {
+            insert_into_vec(&mut group.items, position, element);
+        }
     });
 }
 
@@ -571,3 +905,128 @@ fn show_states_tree(
 
     edit_result
 }
+
+fn show_triggers(ui: &mut Ui, graphic: &mut GraphicDefinition) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    let mut remove_index = None;
+    for (index, trigger) in graphic.triggers.iter_mut().enumerate() {
+        ui.push_id(index, |ui| {
+            ui.group(|ui| {
+                ui_split(ui, "Event", |ui| {
+                    let mut combo = LComboBox::new(&mut trigger.event);
+                    for kind in RaceEventKind::ALL {
+                        combo = combo.add_option(kind, kind.name());
+                    }
+                    edit_result |= ui.add(combo).into();
+                });
+                ui_split(ui, "State", |ui| {
+                    let selected_name = graphic
+                        .states
+                        .iter()
+                        .find(|s| s.id == trigger.state)
+                        .map(|s| s.name.as_str())
+                        .unwrap_or("-");
+                    egui::ComboBox::from_id_source(ui.next_auto_id())
+                        .width(ui.available_width())
+                        .selected_text(selected_name)
+                        .show_ui(ui, |ui| {
+                            for state in graphic.states.iter() {
+                                let is_selected = state.id == trigger.state;
+                                if ui.selectable_label(is_selected, &state.name).clicked()
+                                    && !is_selected
+                                {
+                                    trigger.state = state.id;
+                                    edit_result = EditResult::FromId(ui.id());
+                                }
+                            }
+                        });
+                });
+                ui_split(ui, "Duration", |ui| {
+                    edit_result |= ui
+                        .add(
+                            DragValue::new(&mut trigger.duration)
+                                .clamp_range(0.0..=f32::MAX)
+                                .speed(0.1)
+                                .suffix(" s"),
+                        )
+                        .into();
+                });
+                if ui.button("Remove").clicked() {
+                    remove_index = Some(index);
+                }
+            });
+        });
+    }
+    if let Some(index) = remove_index {
+        graphic.triggers.remove(index);
+        edit_result = EditResult::FromId(ui.id());
+    }
+
+    let add_button_res = ui.add_sized(
+        vec2(ui.available_width(), 0.0),
+        egui::Button::new("Add trigger"),
+    );
+    if add_button_res.clicked() {
+        graphic.triggers.push(GraphicStateTrigger::new());
+        edit_result = EditResult::FromId(add_button_res.id);
+    }
+
+    edit_result
+}
+
+fn show_parameters(
+    ui: &mut Ui,
+    graphic: &mut GraphicDefinition,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    let mut remove_index = None;
+    for (index, parameter) in graphic.parameters.iter_mut().enumerate() {
+        ui.push_id(index, |ui| {
+            ui.group(|ui| {
+                ui_split(ui, "Name", |ui| {
+                    edit_result |= ui.text_edit_singleline(&mut parameter.name).into();
+                });
+                ui_split(ui, "Type", |ui| {
+                    edit_result |= ui
+                        .add(
+                            LComboBox::new_comparable(&mut parameter.default, |a, b| {
+                                a.value_type() == b.value_type()
+                            })
+                            .add_option(ParameterValue::new(ValueType::Number), "Number")
+                            .add_option(ParameterValue::new(ValueType::Text), "Text")
+                            .add_option(ParameterValue::new(ValueType::Tint), "Color")
+                            .add_option(ParameterValue::new(ValueType::Boolean), "Boolean")
+                            .add_option(ParameterValue::new(ValueType::Texture), "Image")
+                            .add_option(ParameterValue::new(ValueType::Font), "Font"),
+                        )
+                        .into();
+                });
+                ui_split(ui, "Default", |ui| {
+                    edit_result |=
+                        parameter_value_editor(ui, &mut parameter.default, reference_store).into();
+                });
+                if ui.button("Remove").clicked() {
+                    remove_index = Some(index);
+                }
+            });
+        });
+    }
+    if let Some(index) = remove_index {
+        graphic.parameters.remove(index);
+        edit_result = EditResult::FromId(ui.id());
+    }
+
+    let add_button_res = ui.add_sized(
+        vec2(ui.available_width(), 0.0),
+        egui::Button::new("Add parameter"),
+    );
+    if add_button_res.clicked() {
+        graphic.parameters.push(ComponentParameter::new());
+        edit_result = EditResult::FromId(add_button_res.id);
+    }
+
+    edit_result
+}
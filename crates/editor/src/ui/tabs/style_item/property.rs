@@ -1,4 +1,7 @@
-use backend::value_types::{Boolean, Font, Number, Property, Text, Texture, Tint, Value};
+use backend::{
+    style::graphic::ParameterValue,
+    value_types::{Boolean, Font, Number, Property, Text, Texture, Tint, Value},
+};
 use bevy_egui::egui::{self, vec2, DragValue, InnerResponse, Rect, Response, TextEdit, Ui, Widget};
 
 use crate::reference_store::{producer_id_editor, ReferenceStore};
@@ -105,6 +108,22 @@ where
     }
 }
 
+/// Editor for the value of a component parameter.
+pub fn parameter_value_editor(
+    ui: &mut Ui,
+    value: &mut ParameterValue,
+    reference_store: &ReferenceStore,
+) -> Response {
+    match value {
+        ParameterValue::Number(p) => ui.add(PropertyEditor::new(p, reference_store)),
+        ParameterValue::Text(p) => ui.add(PropertyEditor::new(p, reference_store)),
+        ParameterValue::Tint(p) => ui.add(PropertyEditor::new(p, reference_store)),
+        ParameterValue::Boolean(p) => ui.add(PropertyEditor::new(p, reference_store)),
+        ParameterValue::Texture(p) => ui.add(PropertyEditor::new(p, reference_store)),
+        ParameterValue::Font(p) => ui.add(PropertyEditor::new(p, reference_store)),
+    }
+}
+
 pub trait ValueTypeEditor {
     fn editor(&mut self, ui: &mut Ui) -> Response;
 }
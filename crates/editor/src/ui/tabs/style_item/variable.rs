@@ -1,12 +1,42 @@
 use std::mem::discriminant;
 
+use backend::value_types::{ProducerRef, Value};
 use bevy_egui::egui::{Response, Ui};
 
+use crate::reference_store::{any_producer_ref_editor, ReferenceStore};
+
 pub mod condition;
+pub mod debounce;
+pub mod delta;
 pub mod fixed_value;
+pub mod format;
+pub mod gap;
+pub mod hold;
 pub mod map;
+pub mod position_change;
+pub mod smooth;
+pub mod stopwatch;
+pub mod text_width;
+
+/// Shows a editor for a producer reference of a fixed type.
+pub fn producer_ref_editor<T: Value>(
+    ui: &mut Ui,
+    reference_store: &ReferenceStore,
+    producer_ref: &mut ProducerRef<T>,
+) -> Response {
+    let mut any_ref = producer_ref.clone().to_any_producer_ref();
+    let res = any_producer_ref_editor(ui, reference_store, &mut any_ref, |v| {
+        v.producer_ref.ty() == T::ty()
+    });
+    if res.changed() {
+        if let Some(typed_ref) = any_ref.to_typed() {
+            *producer_ref = typed_ref;
+        }
+    }
+    res
+}
 
-trait EguiComboBoxExtension {
+pub trait EguiComboBoxExtension {
     /// Shows the combobox with one entry for each variant.
     /// Compares variants based on their discriminants and not PartialEq.
     fn choose<T>(self, ui: &mut Ui, current: &mut T, other: Vec<(T, &str)>) -> Response;
@@ -0,0 +1,32 @@
+use backend::style::variables::delta::{Delta, DeltaMode};
+use bevy_egui::egui::Ui;
+
+use crate::{
+    reference_store::ReferenceStore,
+    ui::{combo_box::LComboBox, tabs::secondary_editor::ui_split, EditResult},
+};
+
+use super::producer_ref_editor;
+
+pub fn property_editor(
+    ui: &mut Ui,
+    value: &mut Delta,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui_split(ui, "Input", |ui| {
+        edit_result |= producer_ref_editor(ui, reference_store, &mut value.input).into();
+    });
+    ui_split(ui, "Output", |ui| {
+        edit_result |= ui
+            .add(
+                LComboBox::new(&mut value.mode)
+                    .add_option(DeltaMode::PreviousValue, "Previous value")
+                    .add_option(DeltaMode::PerSecond, "Change per second"),
+            )
+            .into();
+    });
+
+    edit_result
+}
@@ -0,0 +1,174 @@
+use backend::{
+    style::variables::format::{Format, NumberFormat, TextCase},
+    value_types::ValueType,
+};
+use bevy_egui::egui::{ComboBox, DragValue, Ui};
+
+use crate::{
+    reference_store::{any_producer_ref_editor, ReferenceStore},
+    ui::{combo_box::LComboBox, tabs::secondary_editor::ui_split, EditResult},
+};
+
+use super::EguiComboBoxExtension;
+
+pub fn property_editor(
+    ui: &mut Ui,
+    value: &mut Format,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui_split(ui, "Input", |ui| {
+        edit_result |= any_producer_ref_editor(ui, reference_store, &mut value.input, |v| match v
+            .producer_ref
+            .ty()
+        {
+            ValueType::Number => true,
+            ValueType::Text => true,
+            ValueType::Boolean => true,
+            _ => false,
+        })
+        .into();
+    });
+
+    if value.input.ty() == ValueType::Number {
+        ui_split(ui, "Number format", |ui| {
+            edit_result |= ComboBox::from_id_source(ui.next_auto_id())
+                .width(ui.available_width())
+                .choose(
+                    ui,
+                    &mut value.number_format,
+                    vec![
+                        (NumberFormat::default(), "Decimal"),
+                        (
+                            NumberFormat::Time {
+                                decimals: 3,
+                                always_sign: false,
+                                always_minutes: false,
+                            },
+                            "Time",
+                        ),
+                        (
+                            NumberFormat::Laps {
+                                always_sign: true,
+                                singular: String::from("LAP"),
+                                plural: String::from("LAPS"),
+                            },
+                            "Laps",
+                        ),
+                        (
+                            NumberFormat::Template {
+                                template: String::from("%.3f"),
+                            },
+                            "Template",
+                        ),
+                    ],
+                )
+                .into();
+        });
+        match &mut value.number_format {
+            NumberFormat::Decimal {
+                decimals,
+                always_sign,
+                min_digits,
+                thousands_separator,
+            } => {
+                ui_split(ui, "Decimals", |ui| {
+                    edit_result |= ui.add(DragValue::new(decimals).clamp_range(0..=9)).into();
+                });
+                ui_split(ui, "Always show sign", |ui| {
+                    edit_result |= ui.checkbox(always_sign, "").into();
+                });
+                ui_split(ui, "Min digits", |ui| {
+                    edit_result |= ui
+                        .add(DragValue::new(min_digits).clamp_range(0..=20))
+                        .into();
+                });
+                ui_split(ui, "Thousands separator", |ui| {
+                    edit_result |= ui.checkbox(thousands_separator, "").into();
+                });
+            }
+            NumberFormat::Time {
+                decimals,
+                always_sign,
+                always_minutes,
+            } => {
+                ui_split(ui, "Decimals", |ui| {
+                    edit_result |= ui.add(DragValue::new(decimals).clamp_range(0..=9)).into();
+                });
+                ui_split(ui, "Always show sign", |ui| {
+                    edit_result |= ui.checkbox(always_sign, "").into();
+                });
+                ui_split(ui, "Always show minutes", |ui| {
+                    edit_result |= ui.checkbox(always_minutes, "").into();
+                });
+            }
+            NumberFormat::Laps {
+                always_sign,
+                singular,
+                plural,
+            } => {
+                ui_split(ui, "Always show sign", |ui| {
+                    edit_result |= ui.checkbox(always_sign, "").into();
+                });
+                ui_split(ui, "Singular", |ui| {
+                    edit_result |= ui.text_edit_singleline(singular).into();
+                });
+                ui_split(ui, "Plural", |ui| {
+                    edit_result |= ui.text_edit_singleline(plural).into();
+                });
+            }
+            NumberFormat::Template { template } => {
+                ui_split(ui, "Template", |ui| {
+                    edit_result |= ui
+                        .text_edit_singleline(template)
+                        .on_hover_text(
+                            "A directive has the form %[flags][width][.precision]conversion.\n\
+                            Flags: '+' always show the sign, '0' pad with zeros, \
+                            ',' separate thousands.\n\
+                            Conversions: 'f' decimal, 'd' whole number, 't' time, \
+                            'T' time with minutes.\n\
+                            Use %% for a literal %.",
+                        )
+                        .into();
+                });
+            }
+        }
+    }
+    if value.input.ty() == ValueType::Boolean {
+        ui_split(ui, "True text", |ui| {
+            edit_result |= ui.text_edit_singleline(&mut value.true_text).into();
+        });
+        ui_split(ui, "False text", |ui| {
+            edit_result |= ui.text_edit_singleline(&mut value.false_text).into();
+        });
+    }
+    ui.separator();
+
+    ui_split(ui, "Initials", |ui| {
+        edit_result |= ui.checkbox(&mut value.initials, "").into();
+    });
+    ui_split(ui, "Case", |ui| {
+        edit_result |= ui
+            .add(
+                LComboBox::new(&mut value.text_case)
+                    .add_option(TextCase::Unchanged, "Unchanged")
+                    .add_option(TextCase::Uppercase, "Uppercase")
+                    .add_option(TextCase::Lowercase, "Lowercase"),
+            )
+            .into();
+    });
+    ui_split(ui, "Max length", |ui| {
+        edit_result |= ui
+            .add(DragValue::new(&mut value.max_length).clamp_range(0..=usize::MAX))
+            .into();
+    });
+    ui_split(ui, "Prefix", |ui| {
+        edit_result |= ui.text_edit_singleline(&mut value.prefix).into();
+    });
+    ui_split(ui, "Suffix", |ui| {
+        edit_result |= ui.text_edit_singleline(&mut value.suffix).into();
+    });
+
+    edit_result
+}
@@ -0,0 +1,49 @@
+use backend::style::variables::gap::{Gap, GapReference};
+use bevy_egui::egui::{DragValue, Ui};
+
+use crate::{
+    reference_store::ReferenceStore,
+    ui::{combo_box::LComboBox, tabs::secondary_editor::ui_split, EditResult},
+};
+
+pub fn property_editor(
+    ui: &mut Ui,
+    value: &mut Gap,
+    _reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui_split(ui, "Gap to", |ui| {
+        edit_result |= ui
+            .add(
+                LComboBox::new(&mut value.reference)
+                    .add_option(GapReference::Leader, "Leader")
+                    .add_option(GapReference::PositionAhead, "Position ahead"),
+            )
+            .into();
+    });
+    ui_split(ui, "Decimals", |ui| {
+        edit_result |= ui
+            .add(DragValue::new(&mut value.decimals).clamp_range(0..=3))
+            .into();
+    });
+    ui.separator();
+
+    ui_split(ui, "First position", |ui| {
+        edit_result |= ui.text_edit_singleline(&mut value.first_text).into();
+    });
+    ui_split(ui, "In pits", |ui| {
+        edit_result |= ui.text_edit_singleline(&mut value.pit_text).into();
+    });
+    ui_split(ui, "Out lap", |ui| {
+        edit_result |= ui.text_edit_singleline(&mut value.out_lap_text).into();
+    });
+    ui_split(ui, "Lap", |ui| {
+        edit_result |= ui.text_edit_singleline(&mut value.lap_singular).into();
+    });
+    ui_split(ui, "Laps", |ui| {
+        edit_result |= ui.text_edit_singleline(&mut value.lap_plural).into();
+    });
+
+    edit_result
+}
@@ -0,0 +1,33 @@
+use backend::style::variables::hold::Hold;
+use bevy_egui::egui::{DragValue, Ui};
+
+use crate::{
+    reference_store::ReferenceStore,
+    ui::{tabs::secondary_editor::ui_split, EditResult},
+};
+
+use super::producer_ref_editor;
+
+pub fn property_editor(
+    ui: &mut Ui,
+    value: &mut Hold,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui_split(ui, "Input", |ui| {
+        edit_result |= producer_ref_editor(ui, reference_store, &mut value.input).into();
+    });
+    ui_split(ui, "Hold time", |ui| {
+        edit_result |= ui
+            .add(
+                DragValue::new(&mut value.hold_time)
+                    .clamp_range(0.0..=f32::MAX)
+                    .speed(0.1)
+                    .suffix(" s"),
+            )
+            .into();
+    });
+
+    edit_result
+}
@@ -0,0 +1,49 @@
+use backend::{
+    position_history::PositionReference,
+    style::variables::position_change::{PositionChange, PositionChangeOutput},
+};
+use bevy_egui::egui::{DragValue, Ui};
+
+use crate::{
+    reference_store::ReferenceStore,
+    ui::{combo_box::LComboBox, tabs::secondary_editor::ui_split, EditResult},
+};
+
+pub fn property_editor(
+    ui: &mut Ui,
+    value: &mut PositionChange,
+    _reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui_split(ui, "Since", |ui| {
+        edit_result |= ui
+            .add(
+                LComboBox::new_comparable(&mut value.reference, |a, b| {
+                    std::mem::discriminant(a) == std::mem::discriminant(b)
+                })
+                .add_option(PositionReference::RaceStart, "Race start")
+                .add_option(PositionReference::LastLap, "Last lap")
+                .add_option(PositionReference::LapsAgo(2), "Laps ago"),
+            )
+            .into();
+    });
+    if let PositionReference::LapsAgo(laps) = &mut value.reference {
+        ui_split(ui, "Laps", |ui| {
+            edit_result |= ui
+                .add(DragValue::new(laps).clamp_range(1..=u32::MAX))
+                .into();
+        });
+    }
+    ui_split(ui, "Output", |ui| {
+        edit_result |= ui
+            .add(
+                LComboBox::new(&mut value.output)
+                    .add_option(PositionChangeOutput::Delta, "Positions gained")
+                    .add_option(PositionChangeOutput::Trend, "Trend"),
+            )
+            .into();
+    });
+
+    edit_result
+}
@@ -0,0 +1,90 @@
+use backend::style::variables::smooth::{Smooth, SmoothingMethod};
+use bevy_egui::egui::{ComboBox, DragValue, Ui};
+
+use crate::{
+    reference_store::ReferenceStore,
+    ui::{tabs::secondary_editor::ui_split, EditResult},
+};
+
+use super::{producer_ref_editor, EguiComboBoxExtension};
+
+pub fn property_editor(
+    ui: &mut Ui,
+    value: &mut Smooth,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui_split(ui, "Input", |ui| {
+        edit_result |= producer_ref_editor(ui, reference_store, &mut value.input).into();
+    });
+    ui_split(ui, "Smoothing", |ui| {
+        edit_result |= ComboBox::from_id_source(ui.next_auto_id())
+            .width(ui.available_width())
+            .choose(
+                ui,
+                &mut value.method,
+                vec![
+                    (
+                        SmoothingMethod::Average { time_constant: 0.5 },
+                        "Moving average",
+                    ),
+                    (SmoothingMethod::RateLimit { max_rate: 1.0 }, "Rate limit"),
+                    (
+                        SmoothingMethod::Hysteresis {
+                            step: 1.0,
+                            threshold: 0.1,
+                        },
+                        "Rounding hysteresis",
+                    ),
+                ],
+            )
+            .into();
+    });
+    ui.separator();
+
+    match &mut value.method {
+        SmoothingMethod::Average { time_constant } => {
+            ui_split(ui, "Time constant", |ui| {
+                edit_result |= ui
+                    .add(
+                        DragValue::new(time_constant)
+                            .clamp_range(0.0..=f32::MAX)
+                            .speed(0.01)
+                            .suffix(" s"),
+                    )
+                    .into();
+            });
+        }
+        SmoothingMethod::RateLimit { max_rate } => {
+            ui_split(ui, "Max change", |ui| {
+                edit_result |= ui
+                    .add(
+                        DragValue::new(max_rate)
+                            .clamp_range(0.0..=f32::MAX)
+                            .speed(0.1)
+                            .suffix(" /s"),
+                    )
+                    .into();
+            });
+        }
+        SmoothingMethod::Hysteresis { step, threshold } => {
+            ui_split(ui, "Step", |ui| {
+                edit_result |= ui
+                    .add(DragValue::new(step).clamp_range(0.0..=f32::MAX).speed(0.01))
+                    .into();
+            });
+            ui_split(ui, "Threshold", |ui| {
+                edit_result |= ui
+                    .add(
+                        DragValue::new(threshold)
+                            .clamp_range(0.0..=f32::MAX)
+                            .speed(0.01),
+                    )
+                    .into();
+            });
+        }
+    }
+
+    edit_result
+}
@@ -0,0 +1,23 @@
+use backend::style::variables::stopwatch::Stopwatch;
+use bevy_egui::egui::Ui;
+
+use crate::{
+    reference_store::ReferenceStore,
+    ui::{tabs::secondary_editor::ui_split, EditResult},
+};
+
+use super::producer_ref_editor;
+
+pub fn property_editor(
+    ui: &mut Ui,
+    value: &mut Stopwatch,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui_split(ui, "Run while", |ui| {
+        edit_result |= producer_ref_editor(ui, reference_store, &mut value.input).into();
+    });
+
+    edit_result
+}
@@ -0,0 +1,36 @@
+use backend::style::variables::text_width::TextWidth;
+use bevy_egui::egui::Ui;
+
+use crate::{
+    reference_store::ReferenceStore,
+    ui::{
+        tabs::{secondary_editor::ui_split, style_item::property::PropertyEditor},
+        EditResult,
+    },
+};
+
+use super::producer_ref_editor;
+
+pub fn property_editor(
+    ui: &mut Ui,
+    value: &mut TextWidth,
+    reference_store: &ReferenceStore,
+) -> EditResult {
+    let mut edit_result = EditResult::None;
+
+    ui_split(ui, "Text", |ui| {
+        edit_result |= producer_ref_editor(ui, reference_store, &mut value.input).into();
+    });
+    ui_split(ui, "Font", |ui| {
+        edit_result |= ui
+            .add(PropertyEditor::new(&mut value.font, reference_store))
+            .into();
+    });
+    ui_split(ui, "Text size", |ui| {
+        edit_result |= ui
+            .add(PropertyEditor::new(&mut value.size, reference_store))
+            .into();
+    });
+
+    edit_result
+}
@@ -173,6 +173,15 @@ fn show_node(
                             ui.close_menu();
                         }
                         ui.separator();
+                        clipboard_buttons(
+                            ui,
+                            messages,
+                            asset.id,
+                            parent_id,
+                            parent_id.expect("Should have a parent"),
+                            TreePosition::After(asset.id),
+                        );
+                        ui.separator();
                         if ui.button("delete").clicked() {
                             messages.push(UiMessage::StyleItemRemove(asset.id));
                             ui.close_menu();
@@ -183,6 +192,7 @@ fn show_node(
         }
 
         (Method::Visit, StyleItem::AssetFolder(folder)) => {
+            let parent_id = builder.parent_id();
             builder.node(
                 NodeBuilder::dir(folder.id)
                     .closer(folder_closer)
@@ -210,6 +220,15 @@ fn show_node(
                             ui.close_menu();
                         }
                         ui.separator();
+                        clipboard_buttons(
+                            ui,
+                            messages,
+                            folder.id,
+                            parent_id,
+                            folder.id,
+                            TreePosition::Last,
+                        );
+                        ui.separator();
                         if ui.button("delete").clicked() {
                             messages.push(UiMessage::StyleItemRemove(folder.id));
                             ui.close_menu();
@@ -256,6 +275,15 @@ fn show_node(
                             ui.close_menu();
                         }
                         ui.separator();
+                        clipboard_buttons(
+                            ui,
+                            messages,
+                            variable.id,
+                            parent_id,
+                            parent_id.expect("Should have a parent"),
+                            TreePosition::After(variable.id),
+                        );
+                        ui.separator();
                         if ui.button("delete").clicked() {
                             messages.push(UiMessage::StyleItemRemove(variable.id));
                             ui.close_menu();
@@ -266,6 +294,7 @@ fn show_node(
         }
 
         (Method::Visit, StyleItem::VariableFolder(folder)) => {
+            let parent_id = builder.parent_id();
             builder.node(
                 NodeBuilder::dir(folder.id)
                     .closer(folder_closer)
@@ -293,6 +322,15 @@ fn show_node(
                             ui.close_menu();
                         }
                         ui.separator();
+                        clipboard_buttons(
+                            ui,
+                            messages,
+                            folder.id,
+                            parent_id,
+                            folder.id,
+                            TreePosition::Last,
+                        );
+                        ui.separator();
                         if ui.button("delete").clicked() {
                             messages.push(UiMessage::StyleItemRemove(folder.id));
                             ui.close_menu();
@@ -344,6 +382,15 @@ fn show_node(
                             ui.close_menu();
                         }
                         ui.separator();
+                        clipboard_buttons(
+                            ui,
+                            messages,
+                            graphic.id,
+                            parent_id,
+                            parent_id.expect("Should have a parent"),
+                            TreePosition::After(graphic.id),
+                        );
+                        ui.separator();
                         if ui.button("delete").clicked() {
                             messages.push(UiMessage::StyleItemRemove(graphic.id));
                             ui.close_menu();
@@ -353,6 +400,7 @@ fn show_node(
             ControlFlow::Continue(())
         }
         (Method::Visit, StyleItem::GraphicFolder(folder)) => {
+            let parent_id = builder.parent_id();
             builder.node(
                 NodeBuilder::dir(folder.id)
                     .closer(folder_closer)
@@ -370,6 +418,15 @@ fn show_node(
                             ui.close_menu();
                         }
                         ui.separator();
+                        clipboard_buttons(
+                            ui,
+                            messages,
+                            folder.id,
+                            parent_id,
+                            folder.id,
+                            TreePosition::Last,
+                        );
+                        ui.separator();
                         if ui.button("delete").clicked() {
                             messages.push(UiMessage::StyleItemRemove(folder.id));
                             ui.close_menu();
@@ -389,6 +446,42 @@ fn show_node(
     }
 }
 
+fn clipboard_buttons(
+    ui: &mut Ui,
+    messages: &mut UiMessages,
+    id: StyleId,
+    parent_id: Option<StyleId>,
+    paste_target: StyleId,
+    paste_position: TreePosition<StyleId>,
+) {
+    if ui.button("copy").clicked() {
+        messages.push(UiMessage::StyleItemCopy(id));
+        ui.close_menu();
+    }
+    if ui.button("cut").clicked() {
+        messages.push(UiMessage::StyleItemCopy(id));
+        messages.push(UiMessage::StyleItemRemove(id));
+        ui.close_menu();
+    }
+    if let Some(parent_id) = parent_id {
+        if ui.button("duplicate").clicked() {
+            messages.push(UiMessage::StyleItemDuplicate {
+                id,
+                target: parent_id,
+                position: TreePosition::After(id),
+            });
+            ui.close_menu();
+        }
+    }
+    if ui.button("paste").clicked() {
+        messages.push(UiMessage::StyleItemPaste {
+            target: paste_target,
+            position: paste_position,
+        });
+        ui.close_menu();
+    }
+}
+
 fn folder_closer(ui: &mut Ui, state: CloserState) {
     let color = if state.is_hovered {
         ui.visuals().widgets.hovered.fg_stroke.color
@@ -11,6 +11,15 @@ struct InstanceData{
     @location(3) corner_offset_y: vec4<f32>,
     @location(4) rounding: vec4<f32>,
     @location(5) color: vec4<f32>,
+    @location(6) gradient_color: vec4<f32>,
+    @location(7) gradient_pos: vec2<f32>,
+    @location(8) gradient_kind: u32,
+    @location(9) gradient_spread: f32,
+    @location(10) gradient_param: f32,
+    @location(11) border_color: vec4<f32>,
+    @location(12) border_width: f32,
+    @location(13) blur: f32,
+    @location(14) is_shadow: u32,
 };
 
 struct VertexOutput {
@@ -20,6 +29,15 @@ struct VertexOutput {
     @location(2) rounding: vec4<f32>,
     @location(3) size: vec2<f32>,
     @location(4) edge_dist: vec4<f32>,
+    @location(5) gradient_color: vec4<f32>,
+    @location(6) gradient_pos: vec2<f32>,
+    @location(7) @interpolate(flat) gradient_kind: u32,
+    @location(8) gradient_spread: f32,
+    @location(9) gradient_param: f32,
+    @location(10) border_color: vec4<f32>,
+    @location(11) border_width: f32,
+    @location(12) blur: f32,
+    @location(13) @interpolate(flat) is_shadow: u32,
 };
 
 @group(1) @binding(0) var texture: texture_2d<f32>;
@@ -42,6 +60,15 @@ fn vertex(@builtin(vertex_index) vertex_index: u32, instance_data: InstanceData)
     );
     out.rounding = instance_data.rounding;
     out.size = instance_data.size;
+    out.gradient_color = instance_data.gradient_color;
+    out.gradient_pos = instance_data.gradient_pos;
+    out.gradient_kind = instance_data.gradient_kind;
+    out.gradient_spread = instance_data.gradient_spread;
+    out.gradient_param = instance_data.gradient_param;
+    out.border_color = instance_data.border_color;
+    out.border_width = instance_data.border_width;
+    out.blur = instance_data.blur;
+    out.is_shadow = instance_data.is_shadow;
     out.edge_dist = vec4(
         max(0.0, dist_to_edge(c0, c1, position.xy)),
         max(0.0, dist_to_edge(c1, c3, position.xy)),
@@ -78,12 +105,80 @@ fn cross2d(u: vec2<f32>, v: vec2<f32>) -> f32{
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    let mask = get_rounding_mask(vec2(in.edge_dist[3u], in.edge_dist[0u]), in.rounding[0])
-             * get_rounding_mask(vec2(in.edge_dist[0u], in.edge_dist[1u]), in.rounding[1])
-             * get_rounding_mask(vec2(in.edge_dist[3u], in.edge_dist[2u]), in.rounding[2])
-             * get_rounding_mask(vec2(in.edge_dist[1u], in.edge_dist[2u]), in.rounding[3]);
+    // Sample before branching, texture sampling requires uniform control flow.
+    let texture_color = textureSample(texture, texture_sampler, in.uv);
+    if in.is_shadow == 1u {
+        return vec4(1.0, 1.0, 1.0, get_shadow_mask(in)) * in.color;
+    }
 
-    return vec4(1.0, 1.0, 1.0, mask) * in.color * textureSample(texture, texture_sampler, in.uv);
+    let mask = get_mask(in.edge_dist, in.rounding);
+    var color = get_color(in) * texture_color;
+
+    // The border is the part of the cell outside of the cell shrunk by the border width.
+    if in.border_width > 0.0 {
+        let inner_mask = get_mask(
+            in.edge_dist - vec4(in.border_width),
+            max(in.rounding - vec4(in.border_width), vec4(0.0)),
+        );
+        color = mix(in.border_color, color, inner_mask);
+    }
+
+    return vec4(1.0, 1.0, 1.0, mask) * color;
+}
+
+// Get the mask of the cell shape from the distance to each edge.
+fn get_mask(edge_dist: vec4<f32>, rounding: vec4<f32>) -> f32 {
+    return get_rounding_mask(vec2(edge_dist[3u], edge_dist[0u]), rounding[0])
+         * get_rounding_mask(vec2(edge_dist[0u], edge_dist[1u]), rounding[1])
+         * get_rounding_mask(vec2(edge_dist[3u], edge_dist[2u]), rounding[2])
+         * get_rounding_mask(vec2(edge_dist[1u], edge_dist[2u]), rounding[3]);
+}
+
+// Get the mask of a shadow. The shadow fades out over twice the blur radius
+// starting from the inside of the edge.
+fn get_shadow_mask(in: VertexOutput) -> f32 {
+    let softness = max(in.blur * 2.0, 1.0);
+    return get_soft_mask(vec2(in.edge_dist[3u], in.edge_dist[0u]), in.rounding[0], softness)
+         * get_soft_mask(vec2(in.edge_dist[0u], in.edge_dist[1u]), in.rounding[1], softness)
+         * get_soft_mask(vec2(in.edge_dist[3u], in.edge_dist[2u]), in.rounding[2], softness)
+         * get_soft_mask(vec2(in.edge_dist[1u], in.edge_dist[2u]), in.rounding[3], softness);
+}
+
+fn get_soft_mask(edge_pos: vec2<f32>, rounding: f32, softness: f32) -> f32{
+    let center = max(rounding, softness);
+    let mask = vec2(
+        max(edge_pos.x, center),
+        max(edge_pos.y, center),
+    );
+    let dist = distance(edge_pos, mask);
+    return map_clamp(dist, center - softness, center, 1.0, 0.0);
+}
+
+// Get the color of the cell with the gradient applied.
+fn get_color(in: VertexOutput) -> vec4<f32> {
+    // Position of the pixel relative to the top left corner of the cell.
+    let to_pixel = in.uv * in.size - in.gradient_pos;
+    var t = 0.0;
+
+    // Linear gradient
+    if in.gradient_kind == 1u {
+        let n = vec2(sin(in.gradient_param), cos(in.gradient_param));
+        t = clamp(dot(n, to_pixel) / in.gradient_spread + 0.5, 0.0, 1.0);
+    }
+    // Radial gradient
+    else if in.gradient_kind == 2u {
+        t = clamp((length(to_pixel) - in.gradient_param) / in.gradient_spread, 0.0, 1.0);
+    }
+    // Conical gradient
+    else if in.gradient_kind == 3u {
+        let angle = (atan2(to_pixel.y, to_pixel.x) + in.gradient_param) / 6.2831853 + 0.5;
+        t = clamp(fract(angle), 0.0, 1.0);
+    }
+
+    // Turn linear value into a sin value.
+    t = (1.0 - cos(t * 3.1415926)) / 2.0;
+
+    return mix(in.color, in.gradient_color, t);
 }
 
 fn get_rounding_mask(edge_pos: vec2<f32>, rounding: f32) -> f32{
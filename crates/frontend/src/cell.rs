@@ -11,7 +11,9 @@ use bevy::{
     sprite::Anchor,
     text::{Font, Text, Text2dBundle, TextStyle},
 };
-use common::communication::TextAlignment;
+use common::communication::{
+    BorderStyle, Gradient, ShadowStyle, TextAlignment, TextOutlineStyle, TextShadowStyle,
+};
 
 use crate::cell_material::CellMaterial;
 
@@ -36,17 +38,26 @@ pub struct SetStyle {
     pub entity: Entity,
     pub style: CellStyle,
 }
-#[derive(Default)]
 pub struct CellStyle {
     pub text: String,
     pub text_color: Color,
     pub text_size: f32,
     pub text_alignment: TextAlignment,
     pub text_position: Vec2,
+    /// Distance between two lines of text relative to the text size.
+    pub line_height: f32,
+    pub text_outline: TextOutlineStyle,
+    pub text_shadow: TextShadowStyle,
     pub font: Option<Handle<Font>>,
     pub color: Color,
+    pub gradient: Gradient,
+    pub border: BorderStyle,
+    pub shadow: ShadowStyle,
     pub texture: Option<Handle<Image>>,
     pub pos: Vec3,
+    /// Rotation around the position in radians counter clockwise.
+    pub rotation: f32,
+    pub scale: Vec2,
     pub size: Vec2,
     // Order top left, top right, bottom left, bottom right,
     pub corner_offsets: [Vec2; 4],
@@ -54,6 +65,37 @@ pub struct CellStyle {
     // Order top left, top right, bottom left, bottom right,
     pub rounding: [f32; 4],
     pub render_layer: u8,
+    /// Opacity of the cell and its text.
+    pub opacity: f32,
+}
+impl Default for CellStyle {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            text_color: Color::default(),
+            text_size: 0.0,
+            text_alignment: TextAlignment::default(),
+            text_position: Vec2::ZERO,
+            line_height: 0.0,
+            text_outline: TextOutlineStyle::default(),
+            text_shadow: TextShadowStyle::default(),
+            font: None,
+            color: Color::default(),
+            gradient: Gradient::default(),
+            border: BorderStyle::default(),
+            shadow: ShadowStyle::default(),
+            texture: None,
+            pos: Vec3::ZERO,
+            rotation: 0.0,
+            scale: Vec2::ONE,
+            size: Vec2::ZERO,
+            corner_offsets: [Vec2::ZERO; 4],
+            visible: false,
+            rounding: [0.0; 4],
+            render_layer: 0,
+            opacity: 1.0,
+        }
+    }
 }
 
 #[derive(Component)]
@@ -85,7 +127,7 @@ impl EntityCommand for CreateCell {
                     marker: CellMarker,
                     no_furstrum_culling: NoFrustumCulling,
                 },
-                Foreground(foreground_id),
+                Foreground(vec![foreground_id]),
             ))
             .add_child(foreground_id);
     }
@@ -149,57 +191,161 @@ fn update_style(
         }
 
         material.color = style.color;
+        material.gradient = style.gradient;
+        material.border = style.border;
+        material.shadow = style.shadow;
+        material.opacity = style.opacity;
         material.texture = style.texture.clone();
         material.size = style.size;
         material.corner_offsets = style.corner_offsets;
         material.rounding = style.rounding.into();
 
         transform.translation = style.pos;
+        transform.rotation = Quat::from_rotation_z(style.rotation);
+        transform.scale = style.scale.extend(1.0);
         *render_layers = RenderLayers::layer(style.render_layer);
     }
 }
 
+/// The text entities of a cell. One entity for each line of text and text layer.
+///
+/// Entities are reused between style updates and are hidden when they are not needed.
 #[derive(Component)]
-pub struct Foreground(pub Entity);
+pub struct Foreground(pub Vec<Entity>);
 
 pub fn update_style_foreground(
-    cells: Query<&Foreground>,
-    mut texts: Query<(&mut Text, &mut Anchor, &mut Transform, &mut RenderLayers)>,
+    mut commands: Commands,
+    mut cells: Query<&mut Foreground>,
+    mut texts: Query<(
+        &mut Text,
+        &mut Anchor,
+        &mut Transform,
+        &mut RenderLayers,
+        &mut Visibility,
+    )>,
     mut events: EventReader<SetStyle>,
 ) {
     for event in events.read() {
-        let Ok(foreground) = cells.get(event.entity) else {
+        let Ok(mut foreground) = cells.get_mut(event.entity) else {
             continue;
         };
 
-        let Ok((mut text, mut anchor, mut transform, mut render_layers)) =
-            texts.get_mut(foreground.0)
-        else {
-            continue;
-        };
-        *text = Text::from_section(
-            event.style.text.clone(),
-            TextStyle {
-                font: match event.style.font.as_ref() {
-                    Some(handle) => handle.clone(),
-                    None => Handle::<Font>::default(),
-                },
-                font_size: event.style.text_size,
-                color: event.style.text_color,
+        let text_style = TextStyle {
+            font: match event.style.font.as_ref() {
+                Some(handle) => handle.clone(),
+                None => Handle::<Font>::default(),
             },
-        );
-        *anchor = match event.style.text_alignment {
+            font_size: event.style.text_size,
+            color: event.style.text_color,
+        };
+        let text_anchor = || match event.style.text_alignment {
             TextAlignment::Left => Anchor::CenterLeft,
             TextAlignment::Center => Anchor::Center,
             TextAlignment::Right => Anchor::CenterRight,
         };
+        let line_advance = event.style.text_size * event.style.line_height;
+        // Multiple lines are centered as a block around the text position.
+        let line_count = event.style.text.split('\n').count();
+        let first_line_y =
+            event.style.text_position.y - (line_count - 1) as f32 * line_advance / 2.0;
 
-        transform.translation = Vec3::new(
-            event.style.text_position.x,
-            -event.style.text_position.y,
-            1.0,
-        );
+        // Every line is drawn once for each layer. Layers are ordered back to front.
+        let mut layers = Vec::new();
+        let text_shadow = event.style.text_shadow;
+        if text_shadow.color.a() > 0.0 {
+            layers.push((
+                vec2(text_shadow.offset.x, -text_shadow.offset.y),
+                text_shadow.color,
+            ));
+        }
+        let text_outline = event.style.text_outline;
+        if text_outline.width > 0.0 && text_outline.color.a() > 0.0 {
+            // Four diagonal copies give a closed outline for usual widths
+            // while keeping the number of text entities per line low.
+            for direction in 0..4 {
+                let angle =
+                    std::f32::consts::FRAC_PI_4 + direction as f32 * std::f32::consts::FRAC_PI_2;
+                layers.push((
+                    Vec2::from_angle(angle) * text_outline.width,
+                    text_outline.color,
+                ));
+            }
+        }
+        layers.push((Vec2::ZERO, event.style.text_color));
+
+        let mut index = 0;
+        for (line_index, line) in event.style.text.split('\n').enumerate() {
+            for (layer_index, (offset, color)) in layers.iter().enumerate() {
+                let translation = Vec3::new(
+                    event.style.text_position.x + offset.x,
+                    -first_line_y - line_index as f32 * line_advance + offset.y,
+                    1.0 - (layers.len() - 1 - layer_index) as f32 * 0.01,
+                );
+                let section_style = TextStyle {
+                    color: color.with_a(color.a() * event.style.opacity),
+                    ..text_style.clone()
+                };
+                match foreground.0.get(index) {
+                    Some(line_entity) => {
+                        if let Ok((
+                            mut text,
+                            mut anchor,
+                            mut transform,
+                            mut render_layers,
+                            mut visibility,
+                        )) = texts.get_mut(*line_entity)
+                        {
+                            // Changing the text lays it out again. Only change it when
+                            // it is different so that unchanged lines and their
+                            // outline copies keep their layout.
+                            if !text_matches(&text, line, &section_style) {
+                                *text = Text::from_section(line.to_string(), section_style);
+                            }
+                            anchor.set_if_neq(text_anchor());
+                            if transform.translation != translation {
+                                transform.translation = translation;
+                            }
+                            render_layers.set_if_neq(RenderLayers::layer(event.style.render_layer));
+                            visibility.set_if_neq(Visibility::Inherited);
+                        }
+                    }
+                    None => {
+                        // Create a new entity for the line.
+                        let line_entity = commands
+                            .spawn(Text2dBundle {
+                                text: Text::from_section(line.to_string(), section_style),
+                                text_anchor: text_anchor(),
+                                transform: Transform::from_translation(translation),
+                                ..Default::default()
+                            })
+                            .insert(RenderLayers::layer(event.style.render_layer))
+                            .id();
+                        commands.entity(event.entity).add_child(line_entity);
+                        foreground.0.push(line_entity);
+                    }
+                }
+                index += 1;
+            }
+        }
 
-        *render_layers = RenderLayers::layer(event.style.render_layer);
+        // Hide the lines that are not used.
+        for line_entity in foreground.0.iter().skip(index) {
+            if let Ok((_, _, _, _, mut visibility)) = texts.get_mut(*line_entity) {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}
+
+/// Test if a text is a single section with the given value and style.
+fn text_matches(text: &Text, value: &str, style: &TextStyle) -> bool {
+    match text.sections.as_slice() {
+        [section] => {
+            section.value == value
+                && section.style.font == style.font
+                && section.style.font_size == style.font_size
+                && section.style.color == style.color
+        }
+        _ => false,
     }
 }
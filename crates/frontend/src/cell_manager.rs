@@ -54,23 +54,32 @@ impl CellManager {
                             text_size: style.text_size,
                             text_alignment: style.text_alignment,
                             text_position: style.text_position,
+                            line_height: style.line_height,
+                            text_outline: style.text_outline,
+                            text_shadow: style.text_shadow,
                             font: style
                                 .font
                                 .as_ref()
                                 .and_then(|id| asset_path_store.get(id))
                                 .and_then(|path| Some(asset_server.load(path))),
                             color: style.color,
+                            gradient: style.gradient,
+                            border: style.border,
+                            shadow: style.shadow,
                             texture: style
                                 .texture
                                 .as_ref()
                                 .and_then(|id| asset_path_store.get(id))
                                 .and_then(|path| Some(asset_server.load(path))),
                             pos: style.pos,
+                            rotation: style.rotation,
+                            scale: style.scale,
                             size: style.size,
                             corner_offsets: style.corner_offsets,
                             visible: style.visible,
                             rounding: style.rounding,
                             render_layer: style.render_layer,
+                            opacity: style.opacity,
                         },
                     });
                 }
@@ -105,12 +114,15 @@ impl CellManager {
                         entity: *cell,
                         style: CellStyle {
                             pos: style.pos,
+                            rotation: style.rotation,
+                            scale: style.scale,
                             size: style.size,
                             corner_offsets: style.corner_offsets,
                             rounding: style.rounding,
                             color: Color::WHITE,
                             visible: true,
                             texture: Some(texture.clone()),
+                            opacity: style.opacity,
                             ..Default::default()
                         },
                     });
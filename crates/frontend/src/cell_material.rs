@@ -30,6 +30,7 @@ use bevy::{
     utils::FloatOrd,
 };
 use bytemuck::{Pod, Zeroable};
+use common::communication::{BorderStyle, Gradient, ShadowStyle};
 use uuid::uuid;
 
 const INSTANCE_SHADER_HANDLE: Handle<Shader> =
@@ -68,18 +69,37 @@ impl Plugin for CellMaterialPlugin {
 
 /// A material to render a cell.
 /// Cells are rendered as instances.
-#[derive(Component, Default, Clone)]
+#[derive(Component, Clone)]
 pub struct CellMaterial {
     pub size: Vec2,
     pub corner_offsets: [Vec2; 4],
     pub rounding: [f32; 4],
     pub color: Color,
+    pub gradient: Gradient,
+    pub border: BorderStyle,
+    pub shadow: ShadowStyle,
+    pub opacity: f32,
     pub texture: Option<Handle<Image>>,
 }
+impl Default for CellMaterial {
+    fn default() -> Self {
+        Self {
+            size: Vec2::ZERO,
+            corner_offsets: [Vec2::ZERO; 4],
+            rounding: [0.0; 4],
+            color: Color::default(),
+            gradient: Gradient::default(),
+            border: BorderStyle::default(),
+            shadow: ShadowStyle::default(),
+            opacity: 1.0,
+            texture: None,
+        }
+    }
+}
 #[derive(Component)]
 pub struct ExtractedCellMaterial {
     material: CellMaterial,
-    transform: Vec3,
+    transform: GlobalTransform,
     render_layer: RenderLayers,
 }
 
@@ -98,15 +118,63 @@ impl ExtractComponent for CellMaterial {
     ) -> Option<Self::Out> {
         visibility.get().then_some(ExtractedCellMaterial {
             material: material.clone(),
-            transform: transform.translation(),
+            transform: *transform,
             render_layer: *layers,
         })
     }
 }
 impl ExtractedCellMaterial {
-    fn to_instance_data(&self) -> InstanceData {
+    /// The instances to render for this cell. The shadow, if any, is
+    /// rendered first so that it is behind the cell.
+    fn to_instance_data(&self) -> impl Iterator<Item = InstanceData> + '_ {
+        let cell = self.cell_instance_data();
+        let shadow = self.material.shadow;
+        let shadow = (shadow.color.a() > 0.0).then(|| InstanceData {
+            // Grow the shadow by the blur radius in all directions.
+            position: cell.position
+                + Vec3::new(
+                    shadow.offset.x - shadow.blur,
+                    -shadow.offset.y + shadow.blur,
+                    0.0,
+                ),
+            size: cell.size + Vec2::splat(shadow.blur * 2.0),
+            rounding: cell.rounding.map(|rounding| rounding + shadow.blur),
+            color: with_opacity(shadow.color, self.material.opacity),
+            blur: shadow.blur,
+            is_shadow: 1,
+            ..cell
+        });
+        shadow
+            .into_iter()
+            .chain(std::iter::once(cell))
+            .map(|instance| self.transformed(instance))
+    }
+
+    /// Move the corners of an instance from the local space of the cell into world space.
+    /// Rotation and scale are baked into the corner offsets.
+    fn transformed(&self, mut instance: InstanceData) -> InstanceData {
+        let translation = self.transform.translation();
+        for index in 0..4 {
+            let corner = self.transform.transform_point(Vec3::new(
+                instance.position.x
+                    + instance.corner_offset_x[index]
+                    + instance.size.x * WIDTH_COEF[index],
+                instance.position.y + instance.corner_offset_y[index]
+                    - instance.size.y * HEIGHT_COEF[index],
+                0.0,
+            ));
+            instance.corner_offset_x[index] =
+                corner.x - translation.x - instance.size.x * WIDTH_COEF[index];
+            instance.corner_offset_y[index] =
+                corner.y - translation.y + instance.size.y * HEIGHT_COEF[index];
+        }
+        instance.position = translation;
+        instance
+    }
+
+    fn cell_instance_data(&self) -> InstanceData {
         InstanceData {
-            position: self.transform,
+            position: Vec3::ZERO,
             size: self.material.size,
             corner_offset_x: [
                 self.material.corner_offsets[0].x,
@@ -121,11 +189,56 @@ impl ExtractedCellMaterial {
                 self.material.corner_offsets[3].y,
             ],
             rounding: self.material.rounding,
-            color: self.material.color.as_linear_rgba_f32(),
+            color: with_opacity(self.material.color, self.material.opacity),
+            border_color: with_opacity(self.material.border.color, self.material.opacity),
+            border_width: self.material.border.width,
+            blur: 0.0,
+            is_shadow: 0,
+            ..gradient_instance_data(&self.material.gradient, self.material.opacity)
         }
     }
 }
 
+/// Get the linear color with its alpha multiplied by the opacity.
+fn with_opacity(color: Color, opacity: f32) -> [f32; 4] {
+    let mut color = color.as_linear_rgba_f32();
+    color[3] *= opacity;
+    color
+}
+
+/// Instance data with only the gradient fields set.
+fn gradient_instance_data(gradient: &Gradient, opacity: f32) -> InstanceData {
+    let (kind, color, position, spread, param) = match gradient {
+        Gradient::None => (0, Color::NONE, Vec2::ZERO, 1.0, 0.0),
+        Gradient::Linear {
+            color,
+            position,
+            spread,
+            angle,
+        } => (1, *color, *position, *spread, angle.to_radians()),
+        Gradient::Radial {
+            color,
+            position,
+            spread,
+            distance,
+        } => (2, *color, *position, *spread, *distance),
+        Gradient::Conical {
+            color,
+            position,
+            angle,
+        } => (3, *color, *position, 1.0, angle.to_radians()),
+    };
+    InstanceData {
+        gradient_color: with_opacity(color, opacity),
+        gradient_position: position,
+        gradient_kind: kind,
+        // A spread of zero would divide by zero in the shader.
+        gradient_spread: spread.max(0.001),
+        gradient_param: param,
+        ..InstanceData::zeroed()
+    }
+}
+
 /// The data that stays the same for each group of cells.
 #[derive(AsBindGroup)]
 struct UniformData {
@@ -134,6 +247,11 @@ struct UniformData {
     texture: Option<Handle<Image>>,
 }
 
+// Coefficients to get the corners of a cell from its size.
+// Must match the coefficients in the shader.
+const WIDTH_COEF: [f32; 4] = [0.0, 1.0, 0.0, 1.0];
+const HEIGHT_COEF: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+
 /// The data that is associated with each instance of a cell.
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
@@ -144,6 +262,18 @@ struct InstanceData {
     corner_offset_y: [f32; 4],
     rounding: [f32; 4],
     color: [f32; 4],
+    gradient_color: [f32; 4],
+    gradient_position: Vec2,
+    // 0: None, 1: Linear, 2: Radial, 3: Conical
+    gradient_kind: u32,
+    gradient_spread: f32,
+    // Angle for linear and conical gradients, distance for radial gradients.
+    gradient_param: f32,
+    border_color: [f32; 4],
+    border_width: f32,
+    blur: f32,
+    // 1 if this instance is the shadow of a cell.
+    is_shadow: u32,
 }
 
 /// The extracted and grouped cell material data
@@ -183,8 +313,9 @@ fn group_instance_data(mut commands: Commands, query: Query<(Entity, &ExtractedC
         sorted_entries.sort_by(|(_, extracted_a, hash_a), (_, extracted_b, hash_b)| {
             extracted_a
                 .transform
+                .translation()
                 .z
-                .partial_cmp(&extracted_b.transform.z)
+                .partial_cmp(&extracted_b.transform.translation().z)
                 .unwrap_or(std::cmp::Ordering::Equal)
                 .then(hash_a.cmp(&hash_b))
         });
@@ -217,10 +348,10 @@ fn group_instance_data(mut commands: Commands, query: Query<(Entity, &ExtractedC
                         uniform: UniformData {
                             texture: extracted.material.texture.clone(),
                         },
-                        z_pos: extracted.transform.z,
+                        z_pos: extracted.transform.translation().z,
                         per_instance: group
                             .iter()
-                            .map(|(_, extracted)| extracted.to_instance_data())
+                            .flat_map(|(_, extracted)| extracted.to_instance_data())
                             .collect(),
                     },
                 ))
@@ -394,6 +525,60 @@ impl SpecializedRenderPipeline for CellMaterialPipline {
                     offset: 68,
                     shader_location: 5,
                 },
+                // gradient_color
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 84,
+                    shader_location: 6,
+                },
+                // gradient_position
+                VertexAttribute {
+                    format: VertexFormat::Float32x2,
+                    offset: 100,
+                    shader_location: 7,
+                },
+                // gradient_kind
+                VertexAttribute {
+                    format: VertexFormat::Uint32,
+                    offset: 108,
+                    shader_location: 8,
+                },
+                // gradient_spread
+                VertexAttribute {
+                    format: VertexFormat::Float32,
+                    offset: 112,
+                    shader_location: 9,
+                },
+                // gradient_param
+                VertexAttribute {
+                    format: VertexFormat::Float32,
+                    offset: 116,
+                    shader_location: 10,
+                },
+                // border_color
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 120,
+                    shader_location: 11,
+                },
+                // border_width
+                VertexAttribute {
+                    format: VertexFormat::Float32,
+                    offset: 136,
+                    shader_location: 12,
+                },
+                // blur
+                VertexAttribute {
+                    format: VertexFormat::Float32,
+                    offset: 140,
+                    shader_location: 13,
+                },
+                // is_shadow
+                VertexAttribute {
+                    format: VertexFormat::Uint32,
+                    offset: 144,
+                    shader_location: 14,
+                },
             ],
         };
 
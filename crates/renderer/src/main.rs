@@ -89,10 +89,18 @@ fn setup_cell(
                 text_size: 40.0,
                 text_alignment: common::communication::TextAlignment::Center,
                 text_position: vec2(0.0, 0.0),
+                line_height: 1.0,
+                text_outline: common::communication::TextOutlineStyle::default(),
+                text_shadow: common::communication::TextShadowStyle::default(),
                 font: None,
                 color: Color::BLUE,
+                gradient: common::communication::Gradient::None,
+                border: common::communication::BorderStyle::default(),
+                shadow: common::communication::ShadowStyle::default(),
                 texture: None,
                 pos: vec3(-150.0, 150.0, 0.0),
+                rotation: 0.0,
+                scale: Vec2::ONE,
                 size: vec2(300.0, 300.0),
                 corner_offsets: [
                     vec2(0.0, 0.0),
@@ -103,6 +111,7 @@ fn setup_cell(
                 visible: true,
                 rounding: [0.0, 0.0, 50.0, 0.0],
                 render_layer: 0,
+                opacity: 1.0,
             },
         });
     }